@@ -1,30 +1,144 @@
-use std::{process::Stdio, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use scopeguard::defer;
 use tauri::{AppHandle, Manager, State};
+use tauri_plugin_opener::OpenerExt;
+use tauri_specta::Event;
 use tokio_util::sync::CancellationToken;
 
 use crate::{
     errors::ErrorKind,
     pob::{
-        InstallCancelToken,
+        ActivityState, DryRunReport, GameProfile, InstallCancelTokens, InstallOutcome,
+        UpdateDownloadEstimate,
+        config::{BackupFailurePolicy, DownloadConfig, PobConfig},
+        error::PobError,
         google_drive::GoogleDriveFileInfo,
         manager::PobManager,
-        progress::{InstallPhase, InstallReporter, InstallStatus, TauriProgressSink},
-        version::PobVersion,
+        progress::{
+            InstallFinished, InstallPhase, InstallProgress, InstallReporter, InstallResult,
+            InstallStatus, RecordingProgressSink, TauriProgressSink,
+        },
+        reclaim::ReclaimReport,
+        recovery::RecoveryAction,
+        snapshot::{BackupEntry, BackupSnapshot},
+        verify::{ArchiveReport, BackupCheck, InstallDiff, IntegrityReport},
+        version::{PatternSample, PobVersion, UpdateState, UpdateStatus},
     },
-    util::generate_task_id,
+    util::{generate_task_id, is_task_id},
 };
 
 type Result<T, E = ErrorKind> = std::result::Result<T, E>;
 
+/// Parent of the per-task temp dirs, under the portable root in portable mode.
+fn temp_base_dir(manager: &PobManager, app: &AppHandle) -> Result<PathBuf> {
+    match manager.portable_temp_dir() {
+        Some(dir) => Ok(dir),
+        None => Ok(app.path().temp_dir()?),
+    }
+}
+
+/// Check a task id the frontend got from `new_task_id` before it names anything.
+fn checked_task_id(task_id: String) -> Result<String> {
+    if !is_task_id(&task_id, "pob") {
+        return Err(ErrorKind::Domain(format!(
+            "잘못된 작업 ID입니다: {task_id}"
+        )));
+    }
+    Ok(task_id)
+}
+
+/// Isolated temp dir for one task.
+fn task_temp_dir(manager: &PobManager, app: &AppHandle, task_id: &str) -> Result<PathBuf> {
+    Ok(temp_base_dir(manager, app)?.join(task_id))
+}
+
+/// Emit the fire-once `InstallFinished` event for an operation's outcome.
+/// `Ok` carries the version now installed, if any.
+fn emit_finished<T>(
+    app: &AppHandle,
+    task_id: &str,
+    started: Instant,
+    outcome: &Result<T>,
+    version: impl FnOnce(&T) -> Option<String>,
+    kept_download: Option<&Path>,
+) {
+    let result = match outcome {
+        Ok(value) => InstallResult::Success {
+            version: version(value),
+        },
+        Err(ErrorKind::Cancelled) => InstallResult::Cancelled,
+        Err(e) => InstallResult::Failed {
+            code: e.code().to_string(),
+            message: e.message(),
+            kept_download: kept_download.map(|path| path.display().to_string()),
+        },
+    };
+    let finished = InstallFinished {
+        task_id: task_id.to_string(),
+        result,
+        elapsed_ms: u32::try_from(started.elapsed().as_millis()).unwrap_or(u32::MAX),
+    };
+
+    if let Err(e) = finished.emit(app) {
+        tracing::warn!(task_id = %task_id, error = %e, "Failed to emit install finished event");
+    }
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn fetch_pob(
     refresh: bool,
+    folder_id: Option<String>,
     manager: State<'_, PobManager>,
 ) -> Result<GoogleDriveFileInfo> {
-    Ok(manager.fetch_latest_file(refresh).await?)
+    let latest = match folder_id {
+        Some(folder_id) => manager.fetch_latest_file_in(&folder_id, refresh).await?,
+        None => manager.fetch_latest_file(refresh).await?,
+    };
+    Ok(latest)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn update_state(refresh: bool, manager: State<'_, PobManager>) -> Result<UpdateState> {
+    Ok(manager.update_state(refresh).await?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn check_for_update(manager: State<'_, PobManager>) -> Result<UpdateStatus> {
+    Ok(manager.check_for_update().await?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn update_download_estimate(
+    manager: State<'_, PobManager>,
+) -> Result<Option<UpdateDownloadEstimate>> {
+    Ok(manager.update_download_estimate().await?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_versions(
+    from: Option<String>,
+    to: Option<String>,
+    with_metadata: bool,
+    manager: State<'_, PobManager>,
+) -> Result<Vec<GoogleDriveFileInfo>> {
+    let versions = manager
+        .list_versions(from.as_deref(), to.as_deref())
+        .await?;
+    if !with_metadata {
+        return Ok(versions);
+    }
+    Ok(manager.prefetch_metadata(versions).await?)
 }
 
 #[tauri::command]
@@ -34,6 +148,15 @@ pub async fn parse_version(file_name: String) -> Result<String> {
     Ok(version)
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn test_version_pattern(
+    pattern: String,
+    samples: Vec<String>,
+) -> Result<Vec<PatternSample>> {
+    Ok(crate::pob::version::test_name_pattern(&pattern, samples)?)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn installed_pob_info(manager: State<'_, PobManager>) -> Result<Option<PobVersion>> {
@@ -42,86 +165,367 @@ pub async fn installed_pob_info(manager: State<'_, PobManager>) -> Result<Option
 
 #[tauri::command]
 #[specta::specta]
-pub async fn uninstall_pob(manager: State<'_, PobManager>, app: AppHandle) -> Result<()> {
+pub async fn uninstall_pob(
+    force: bool,
+    manager: State<'_, PobManager>,
+    app: AppHandle,
+) -> Result<String> {
     // Acquire exclusive lock for uninstall operation
     let _guard = manager
         .try_write_lock()
-        .ok_or_else(|| ErrorKind::Conflict("이미 다른 작업이 진행 중입니다.".into()))?;
+        .ok_or_else(|| ErrorKind::operation_in_progress("이미 다른 작업이 진행 중입니다."))?;
+    if force {
+        manager.terminate_pob().await?;
+    }
 
+    let started = Instant::now();
     let task_id = generate_task_id("pob");
-    let reporter = InstallReporter::new(task_id, Arc::new(TauriProgressSink::new(app)));
+    let sink = RecordingProgressSink::new(
+        Arc::new(TauriProgressSink::new(app.clone())),
+        &manager.event_log_path(),
+    );
+    let reporter = InstallReporter::new(&task_id, Arc::new(sink));
 
-    manager.uninstall(&reporter).await?;
-    Ok(())
+    let result = manager.uninstall(&reporter).await.map_err(ErrorKind::from);
+    emit_finished(&app, &task_id, started, &result, |_| None, None);
+    result.map(|()| task_id)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn previous_pob_info(manager: State<'_, PobManager>) -> Result<Option<PobVersion>> {
+    Ok(manager.previous_version().await?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_changelog(manager: State<'_, PobManager>) -> Result<Option<String>> {
+    Ok(manager.changelog().await?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn revert_to_previous(
+    manager: State<'_, PobManager>,
+    app: AppHandle,
+) -> Result<Option<PobVersion>> {
+    let _guard = manager
+        .try_write_lock()
+        .ok_or_else(|| ErrorKind::operation_in_progress("이미 다른 작업이 진행 중입니다."))?;
+
+    let started = Instant::now();
+    let task_id = generate_task_id("pob");
+    let sink = RecordingProgressSink::new(
+        Arc::new(TauriProgressSink::new(app.clone())),
+        &manager.event_log_path(),
+    );
+    let reporter = InstallReporter::new(&task_id, Arc::new(sink));
+
+    let result = manager
+        .revert_to_previous(&reporter)
+        .await
+        .map_err(ErrorKind::from);
+    emit_finished(
+        &app,
+        &task_id,
+        started,
+        &result,
+        |restored| restored.as_ref().map(|v| v.version.clone()),
+        None,
+    );
+    result
 }
 
+// Id for an install or repair, taken before starting it so it can be
+// cancelled before its first progress event
+#[tauri::command]
+#[specta::specta]
+pub async fn new_task_id() -> Result<String> {
+    Ok(generate_task_id("pob"))
+}
+
+// `on_backup_failure` overrides the configured policy for this install, e.g.
+// once the user answered a `backup_failed` prompt
 #[tauri::command]
 #[specta::specta]
 pub async fn install_pob(
+    task_id: String,
     file_data: Option<GoogleDriveFileInfo>,
+    allow_downgrade: bool,
+    force: bool,
+    dry_run: bool,
+    on_backup_failure: Option<BackupFailurePolicy>,
     manager: State<'_, PobManager>,
-    cancel_state: State<'_, InstallCancelToken>,
+    cancel_state: State<'_, InstallCancelTokens>,
     app: AppHandle,
-) -> Result<bool> {
+) -> Result<InstallOutcome> {
     // Acquire exclusive lock for install operation (Issue 5: RwLock)
     let _guard = manager
         .try_write_lock()
-        .ok_or_else(|| ErrorKind::Conflict("이미 다른 설치 작업이 진행 중입니다.".into()))?;
+        .ok_or_else(|| ErrorKind::operation_in_progress("이미 다른 설치 작업이 진행 중입니다."))?;
 
-    let task_id = generate_task_id("pob");
+    let started = Instant::now();
+    let task_id = checked_task_id(task_id)?;
 
-    // Create reporter
-    let reporter = InstallReporter::new(&task_id, Arc::new(TauriProgressSink::new(app.clone())));
+    // Create reporter (events are also recorded for replay_last_install)
+    let sink = RecordingProgressSink::new(
+        Arc::new(TauriProgressSink::new(app.clone())),
+        &manager.event_log_path(),
+    );
+    let reporter =
+        InstallReporter::new(&task_id, Arc::new(sink)).with_phase_weights(manager.phase_weights());
     reporter.report(
         InstallPhase::Preparing,
-        InstallStatus::Started { total_size: None },
+        InstallStatus::Started {
+            total_size: None,
+            estimated_secs: None,
+        },
     );
 
     // Issue 1: Store cancellation token in managed state (no event listener)
     let cancel_token = CancellationToken::new();
-    cancel_state.set(cancel_token.clone());
+    cancel_state.register(&task_id, cancel_token.clone());
 
     // Issue 1: Ensure token is cleared on all exit paths (via defer)
     defer! {
-        cancel_state.take();
+        cancel_state.remove(&task_id);
     }
 
-    // Get file info
-    let file_info = match file_data {
-        Some(data) => data,
-        None => manager.fetch_latest_file(false).await?,
-    };
+    let mut kept_download = None;
+    let result: Result<(Option<String>, Option<DryRunReport>)> = async {
+        // Get file info (cancellable before any bytes flow)
+        let file_info = manager
+            .prepare_install(file_data, &cancel_token, &reporter)
+            .await?;
+        // A dry run replaces nothing, so a downgrade is harmless
+        if !dry_run {
+            manager.check_downgrade(&file_info, allow_downgrade).await?;
+        }
+        let version = crate::pob::version::parse_from_name(&file_info.name)
+            .ok()
+            .filter(|_| !dry_run);
 
-    // Issue 4: Create isolated per-task temp directory
-    let base_temp = app.path().temp_dir()?;
-    let temp_dir = base_temp.join(&task_id);
-    tokio::fs::create_dir_all(&temp_dir)
-        .await
-        .map_err(|e| ErrorKind::Io(format!("임시 디렉토리 생성 실패: {}", e)))?;
+        // Issue 4: Create isolated per-task temp directory
+        let temp_dir = task_temp_dir(&manager, &app, &task_id)?;
+        tokio::fs::create_dir_all(&temp_dir)
+            .await
+            .map_err(|e| ErrorKind::Io(format!("임시 디렉토리 생성 실패: {}", e)))?;
 
-    // Execute install with guaranteed temp cleanup
-    let result = manager
-        .install(file_info, temp_dir.clone(), cancel_token, reporter)
-        .await;
+        // Close PoB only now, after the downgrade check could still refuse
+        if force && !dry_run {
+            manager.terminate_pob().await?;
+        }
 
-    // Issue 4: Always cleanup temp subdirectory
-    let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        // Execute install with guaranteed temp cleanup
+        let file_name = file_info.name.clone();
+        let result = if dry_run {
+            manager
+                .dry_run_install(file_info, temp_dir.clone(), cancel_token, reporter)
+                .await
+                .map(Some)
+        } else {
+            let on_backup_failure = on_backup_failure.unwrap_or(manager.config().on_backup_failure);
+            manager
+                .install(
+                    file_info,
+                    temp_dir.clone(),
+                    on_backup_failure,
+                    cancel_token,
+                    reporter,
+                )
+                .await
+                .map(|()| None)
+        };
+
+        // Keep the archive for inspection before the temp dir goes away
+        if result
+            .as_ref()
+            .is_err_and(|e| !matches!(e, PobError::Cancelled))
+        {
+            kept_download = manager.keep_failed_download(&temp_dir, &file_name).await;
+        }
+
+        // Issue 4: Always cleanup temp subdirectory
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
 
-    result?;
-    Ok(true)
+        let report = result?;
+        Ok((version, report))
+    }
+    .await;
+
+    // Every path past the lock ends here, including rollback and cancellation
+    emit_finished(
+        &app,
+        &task_id,
+        started,
+        &result,
+        |(version, _)| version.clone(),
+        kept_download.as_deref(),
+    );
+    // Cloned: the deferred cleanup still borrows `task_id`
+    result.map(|(_, dry_run)| InstallOutcome {
+        task_id: task_id.clone(),
+        dry_run,
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn check_install_integrity(manager: State<'_, PobManager>) -> Result<IntegrityReport> {
+    Ok(manager.check_integrity().await?)
+}
+
+// Full reinstall of the installed release through install_pob: user data goes
+// through a backup and restore, and the replaced install is kept as the previous one
+#[tauri::command]
+#[specta::specta]
+pub async fn reinstall_pob(
+    task_id: String,
+    force: bool,
+    manager: State<'_, PobManager>,
+    cancel_state: State<'_, InstallCancelTokens>,
+    app: AppHandle,
+) -> Result<InstallOutcome> {
+    // Same release as installed, so the usual backup and restore keep user data
+    let file_info = manager.repair_target().await?;
+    tracing::info!(operation = "reinstall", file = %file_info.name, "Reinstalling installed release");
+    install_pob(
+        task_id,
+        Some(file_info),
+        false,
+        force,
+        false,
+        None,
+        manager,
+        cancel_state,
+        app,
+    )
+    .await
+}
+
+// In-place repair: user data is copied straight from the damaged install, with
+// no backup, and the damaged install is discarded rather than kept as previous
+#[tauri::command]
+#[specta::specta]
+pub async fn repair_pob(
+    task_id: String,
+    manager: State<'_, PobManager>,
+    cancel_state: State<'_, InstallCancelTokens>,
+    app: AppHandle,
+) -> Result<InstallOutcome> {
+    let _guard = manager
+        .try_write_lock()
+        .ok_or_else(|| ErrorKind::operation_in_progress("이미 다른 설치 작업이 진행 중입니다."))?;
+
+    let started = Instant::now();
+    let task_id = checked_task_id(task_id)?;
+    let sink = RecordingProgressSink::new(
+        Arc::new(TauriProgressSink::new(app.clone())),
+        &manager.event_log_path(),
+    );
+    let reporter = InstallReporter::new(&task_id, Arc::new(sink));
+
+    let cancel_token = CancellationToken::new();
+    cancel_state.register(&task_id, cancel_token.clone());
+    defer! {
+        cancel_state.remove(&task_id);
+    }
+
+    let result: Result<Option<String>> = async {
+        let temp_dir = task_temp_dir(&manager, &app, &task_id)?;
+        tokio::fs::create_dir_all(&temp_dir)
+            .await
+            .map_err(|e| ErrorKind::Io(format!("임시 디렉토리 생성 실패: {}", e)))?;
+
+        let result = manager
+            .repair(temp_dir.clone(), cancel_token, reporter)
+            .await;
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        result?;
+
+        let installed = manager.installed_version().await?;
+        Ok(installed.map(|v| v.version))
+    }
+    .await;
+
+    emit_finished(&app, &task_id, started, &result, Option::clone, None);
+    // Cloned: the deferred cleanup still borrows `task_id`
+    result.map(|_| InstallOutcome {
+        task_id: task_id.clone(),
+        dry_run: None,
+    })
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn cancel_install_pob(cancel_state: State<'_, InstallCancelToken>) -> Result<()> {
+pub async fn take_recovery_action(
+    manager: State<'_, PobManager>,
+) -> Result<Option<RecoveryAction>> {
+    Ok(manager.take_recovery_action())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn cancel_install_pob(
+    task_id: String,
+    cancel_state: State<'_, InstallCancelTokens>,
+) -> Result<()> {
     // Issue 1: Directly cancel via managed state (no event needed)
-    cancel_state.cancel();
+    if !cancel_state.cancel(&task_id) {
+        // Usually it finished while the request was in flight
+        tracing::debug!(task_id = %task_id, "No running task to cancel");
+    }
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn execute_pob(manager: State<'_, PobManager>) -> Result<()> {
+pub async fn quiesce(
+    manager: State<'_, PobManager>,
+    cancel_state: State<'_, InstallCancelTokens>,
+) -> Result<ActivityState> {
+    // Go idle: refuse new network activity, then stop whatever is running
+    manager.quiesce();
+    let cancelled_operation = cancel_state.is_active();
+    cancel_state.cancel_all();
+    Ok(ActivityState {
+        quiesced: true,
+        cancelled_operation,
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn resume_activity(manager: State<'_, PobManager>) -> Result<ActivityState> {
+    manager.resume_activity();
+    Ok(ActivityState {
+        quiesced: false,
+        cancelled_operation: false,
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn is_pob_running(manager: State<'_, PobManager>) -> Result<bool> {
+    Ok(manager.is_pob_running())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn execute_pob(
+    profile: Option<GameProfile>,
+    manager: State<'_, PobManager>,
+) -> Result<()> {
+    /// Upper bound for process creation; slow/network paths or broken shell
+    /// associations can otherwise block spawn indefinitely on Windows.
+    const SPAWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+    // Launching mid-install would lock files the swap is about to move.
+    // Held until spawn returns so an install can't start underneath the launch.
+    let _guard = manager.try_read_lock().ok_or_else(|| {
+        ErrorKind::operation_in_progress("설치 또는 제거 작업 중에는 POB를 실행할 수 없습니다.")
+    })?;
+
     let exe_path = manager.exe_path();
     if !exe_path.exists() {
         return Err(ErrorKind::NotFound(format!(
@@ -130,13 +534,63 @@ pub async fn execute_pob(manager: State<'_, PobManager>) -> Result<()> {
         )));
     }
 
-    tracing::info!(operation = "execute", path = %exe_path.display(), "Launching POB executable");
-    tokio::process::Command::new(exe_path)
-        .stdin(Stdio::null())
-        .stderr(Stdio::null())
-        .stdout(Stdio::null())
-        .spawn()
-        .map_err(|e| ErrorKind::Io(format!("POB 실행에 실패했습니다: {}", e)))?;
+    // Reject directories and symlinks/reparse points before handing the path to the OS
+    let metadata = tokio::fs::symlink_metadata(&exe_path).await?;
+    if !metadata.is_file() {
+        return Err(ErrorKind::Io(format!(
+            "POB 실행 파일이 일반 파일이 아닙니다: {}",
+            exe_path.display()
+        )));
+    }
+
+    // Start in the chosen game's PoB folder; without a profile the working
+    // directory is inherited as before
+    let working_dir = match profile {
+        Some(profile) => {
+            let dir = manager.install_path().join(profile.dir_name());
+            if !dir.is_dir() {
+                return Err(ErrorKind::NotFound(format!(
+                    "{} 폴더를 찾을 수 없습니다: {}",
+                    profile.dir_name(),
+                    dir.display()
+                )));
+            }
+            Some(dir)
+        }
+        None => None,
+    };
+
+    tracing::info!(operation = "execute", path = %exe_path.display(), ?profile, "Launching POB executable");
+    let spawn = tokio::task::spawn_blocking(move || {
+        let mut command = std::process::Command::new(exe_path);
+        if let Some(dir) = working_dir {
+            command.current_dir(dir);
+        }
+        command
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .stdout(Stdio::null())
+            .spawn()
+    });
+
+    match tokio::time::timeout(SPAWN_TIMEOUT, spawn).await {
+        Ok(joined) => {
+            joined
+                .map_err(|e| ErrorKind::Domain(e.to_string()))?
+                .map_err(|e| ErrorKind::Io(format!("POB 실행에 실패했습니다: {}", e)))?;
+        }
+        Err(_) => {
+            tracing::warn!(
+                operation = "execute",
+                timeout = ?SPAWN_TIMEOUT,
+                "POB process did not start within timeout"
+            );
+            return Err(ErrorKind::Io(format!(
+                "POB 실행이 {}초 내에 시작되지 않았습니다",
+                SPAWN_TIMEOUT.as_secs()
+            )));
+        }
+    }
 
     Ok(())
 }
@@ -146,3 +600,371 @@ pub async fn execute_pob(manager: State<'_, PobManager>) -> Result<()> {
 pub async fn get_install_path(manager: State<'_, PobManager>) -> Result<String, ErrorKind> {
     Ok(manager.install_path().to_string_lossy().to_string())
 }
+
+#[tauri::command]
+#[specta::specta]
+pub async fn open_install_folder(manager: State<'_, PobManager>, app: AppHandle) -> Result<()> {
+    let install_path = manager.install_path();
+    if !install_path.is_dir() {
+        return Err(ErrorKind::NotFound(format!(
+            "설치 폴더가 없습니다: {}",
+            install_path.display()
+        )));
+    }
+
+    app.opener()
+        .open_path(install_path.to_string_lossy(), None::<&str>)
+        .map_err(|e| ErrorKind::Io(format!("설치 폴더를 열 수 없습니다: {e}")))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn open_log_folder(manager: State<'_, PobManager>, app: AppHandle) -> Result<()> {
+    let log_dir = match manager.portable_log_dir() {
+        Some(dir) => dir,
+        None => app.path().app_log_dir()?,
+    };
+    // Absent until the first log is written
+    tokio::fs::create_dir_all(&log_dir)
+        .await
+        .map_err(|e| ErrorKind::Io(format!("로그 폴더를 만들 수 없습니다: {e}")))?;
+
+    app.opener()
+        .open_path(log_dir.to_string_lossy(), None::<&str>)
+        .map_err(|e| ErrorKind::Io(format!("로그 폴더를 열 수 없습니다: {e}")))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_config(manager: State<'_, PobManager>) -> Result<PobConfig> {
+    Ok(manager.config())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_config(config: PobConfig, manager: State<'_, PobManager>) -> Result<()> {
+    Ok(manager.set_config(config).await?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_download_config(manager: State<'_, PobManager>) -> Result<DownloadConfig> {
+    Ok(manager.config().download)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_download_config(
+    config: DownloadConfig,
+    manager: State<'_, PobManager>,
+) -> Result<()> {
+    let mut full = manager.config();
+    full.download = config;
+    Ok(manager.set_config(full).await?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn replay_last_install(manager: State<'_, PobManager>) -> Result<Vec<InstallProgress>> {
+    Ok(manager.replay_last_install().await?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn relocate_data_dir(
+    new_dir: String,
+    manager: State<'_, PobManager>,
+    app: AppHandle,
+) -> Result<()> {
+    let _guard = manager
+        .try_write_lock()
+        .ok_or_else(|| ErrorKind::operation_in_progress("이미 다른 작업이 진행 중입니다."))?;
+
+    // The redirect always lives in the default location so startup can find it
+    let app_dir = app.path().app_local_data_dir()?;
+    let reporter = InstallReporter::new(
+        generate_task_id("relocate"),
+        Arc::new(TauriProgressSink::new(app)),
+    );
+
+    manager
+        .relocate_data_dir(PathBuf::from(new_dir), &app_dir, &reporter)
+        .await?;
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_install_root(
+    path: String,
+    manager: State<'_, PobManager>,
+    app: AppHandle,
+) -> Result<()> {
+    let _guard = manager
+        .try_write_lock()
+        .ok_or_else(|| ErrorKind::operation_in_progress("이미 다른 작업이 진행 중입니다."))?;
+
+    // Moves to another volume are copies, which report byte progress
+    let reporter = InstallReporter::new(
+        generate_task_id("install_root"),
+        Arc::new(TauriProgressSink::new(app)),
+    );
+    manager
+        .set_install_root(PathBuf::from(path), &reporter)
+        .await?;
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn rebuild_version_info(
+    version: String,
+    manager: State<'_, PobManager>,
+) -> Result<PobVersion> {
+    let _guard = manager
+        .try_write_lock()
+        .ok_or_else(|| ErrorKind::operation_in_progress("이미 다른 작업이 진행 중입니다."))?;
+
+    // file_id is best-effort; rebuilding must work offline
+    let latest = match manager.fetch_latest_file(false).await {
+        Ok(latest) => Some(latest),
+        Err(e) => {
+            tracing::warn!(
+                operation = "rebuild_version",
+                error = %e,
+                "Could not fetch latest release, leaving file_id empty"
+            );
+            None
+        }
+    };
+
+    Ok(manager
+        .rebuild_version_info(&version, latest.as_ref())
+        .await?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn diff_against_latest(
+    manager: State<'_, PobManager>,
+    cancel_state: State<'_, InstallCancelTokens>,
+    app: AppHandle,
+) -> Result<InstallDiff> {
+    // Shared lock: read-only against the install, but it must not change underneath
+    let _guard = manager.try_read_lock().ok_or_else(|| {
+        ErrorKind::operation_in_progress("설치 또는 제거 작업 중에는 검사할 수 없습니다.")
+    })?;
+
+    let task_id = generate_task_id("pob");
+    let reporter = InstallReporter::new(&task_id, Arc::new(TauriProgressSink::new(app.clone())));
+
+    let cancel_token = CancellationToken::new();
+    cancel_state.register(&task_id, cancel_token.clone());
+    defer! {
+        cancel_state.remove(&task_id);
+    }
+
+    let temp_dir = task_temp_dir(&manager, &app, &task_id)?;
+    tokio::fs::create_dir_all(&temp_dir)
+        .await
+        .map_err(|e| ErrorKind::Io(format!("임시 디렉토리 생성 실패: {}", e)))?;
+
+    let result = manager
+        .diff_against_latest(&temp_dir, cancel_token, &reporter)
+        .await;
+    let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+
+    Ok(result?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn verify_install(
+    full: Option<bool>,
+    manager: State<'_, PobManager>,
+    cancel_state: State<'_, InstallCancelTokens>,
+    app: AppHandle,
+) -> Result<InstallDiff> {
+    let _guard = manager.try_read_lock().ok_or_else(|| {
+        ErrorKind::operation_in_progress("설치 또는 제거 작업 중에는 검사할 수 없습니다.")
+    })?;
+
+    let task_id = generate_task_id("pob");
+    let reporter = InstallReporter::new(&task_id, Arc::new(TauriProgressSink::new(app)));
+
+    let cancel_token = CancellationToken::new();
+    cancel_state.register(&task_id, cancel_token.clone());
+    defer! {
+        cancel_state.remove(&task_id);
+    }
+
+    // Quick (size + mtime) unless a deep CRC pass is asked for
+    Ok(manager
+        .verify_install(full.unwrap_or(false), cancel_token, &reporter)
+        .await?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn test_backup_integrity(
+    manager: State<'_, PobManager>,
+    cancel_state: State<'_, InstallCancelTokens>,
+    app: AppHandle,
+) -> Result<BackupCheck> {
+    // Shared lock: an install would rewrite the backup while it's being read
+    let _guard = manager.try_read_lock().ok_or_else(|| {
+        ErrorKind::operation_in_progress("설치 또는 제거 작업 중에는 검사할 수 없습니다.")
+    })?;
+
+    let task_id = generate_task_id("pob");
+    let reporter = InstallReporter::new(&task_id, Arc::new(TauriProgressSink::new(app)));
+
+    let cancel_token = CancellationToken::new();
+    cancel_state.register(&task_id, cancel_token.clone());
+    defer! {
+        cancel_state.remove(&task_id);
+    }
+
+    Ok(manager.check_backup(cancel_token, &reporter).await?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn create_manual_backup(
+    label: Option<String>,
+    manager: State<'_, PobManager>,
+    app: AppHandle,
+) -> Result<BackupSnapshot> {
+    // Shared lock: reads the install, which an install or uninstall would be rewriting
+    let _guard = manager.try_read_lock().ok_or_else(|| {
+        ErrorKind::operation_in_progress("설치 또는 제거 작업 중에는 백업할 수 없습니다.")
+    })?;
+
+    let reporter = InstallReporter::new(
+        generate_task_id("pob"),
+        Arc::new(TauriProgressSink::new(app)),
+    );
+
+    Ok(manager.create_manual_backup(label, &reporter).await?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_backups(manager: State<'_, PobManager>) -> Result<Vec<BackupEntry>> {
+    Ok(manager.list_backups().await?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn restore_from_backup(
+    name: String,
+    manager: State<'_, PobManager>,
+    app: AppHandle,
+) -> Result<()> {
+    let _guard = manager
+        .try_write_lock()
+        .ok_or_else(|| ErrorKind::operation_in_progress("이미 다른 작업이 진행 중입니다."))?;
+
+    let reporter = InstallReporter::new(
+        generate_task_id("pob"),
+        Arc::new(TauriProgressSink::new(app)),
+    );
+
+    Ok(manager.restore_from_backup(&name, &reporter).await?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_snapshots(manager: State<'_, PobManager>) -> Result<Vec<BackupSnapshot>> {
+    Ok(manager.list_snapshots().await?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn restore_snapshot(
+    id: String,
+    manager: State<'_, PobManager>,
+    app: AppHandle,
+) -> Result<()> {
+    let _guard = manager
+        .try_write_lock()
+        .ok_or_else(|| ErrorKind::operation_in_progress("이미 다른 작업이 진행 중입니다."))?;
+
+    let reporter = InstallReporter::new(
+        generate_task_id("pob"),
+        Arc::new(TauriProgressSink::new(app)),
+    );
+
+    Ok(manager.restore_snapshot(&id, &reporter).await?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_backup_targets(manager: State<'_, PobManager>) -> Result<Vec<String>> {
+    Ok(manager
+        .load_backup_targets()
+        .iter()
+        .map(|path| path.to_string_lossy().replace('\\', "/"))
+        .collect())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_backup_targets(
+    targets: Vec<String>,
+    manager: State<'_, PobManager>,
+) -> Result<()> {
+    // An install reads the targets for its backup and again for the restore
+    let _guard = manager
+        .try_write_lock()
+        .ok_or_else(|| ErrorKind::operation_in_progress("이미 다른 작업이 진행 중입니다."))?;
+
+    Ok(manager.save_backup_targets(targets).await?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn inspect_archive(
+    zip_path: String,
+    manager: State<'_, PobManager>,
+) -> Result<ArchiveReport> {
+    Ok(manager.inspect_archive(PathBuf::from(zip_path)).await?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn reclaim_space(
+    target_bytes: f64,
+    manager: State<'_, PobManager>,
+    app: AppHandle,
+) -> Result<ReclaimReport> {
+    // Exclusive: pruning must not race an install writing temp files or the backup
+    let _guard = manager
+        .try_write_lock()
+        .ok_or_else(|| ErrorKind::operation_in_progress("이미 다른 작업이 진행 중입니다."))?;
+
+    let temp_dir = temp_base_dir(&manager, &app)?;
+    let log_dir = manager
+        .portable_log_dir()
+        .or_else(|| app.path().app_log_dir().ok());
+
+    Ok(manager
+        .reclaim_space(target_bytes.max(0.0) as u64, temp_dir, log_dir)
+        .await?)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn export_diagnostics(
+    dest_zip: String,
+    manager: State<'_, PobManager>,
+    app: AppHandle,
+) -> Result<String> {
+    let log_dir = manager
+        .portable_log_dir()
+        .or_else(|| app.path().app_log_dir().ok());
+    let path = manager
+        .export_diagnostics(PathBuf::from(dest_zip), log_dir)
+        .await?;
+    Ok(path.to_string_lossy().to_string())
+}
@@ -17,11 +17,78 @@ pub enum ErrorKind {
     /// Resource not found (e.g., file not on Google Drive)
     NotFound(String),
     /// Conflict state (e.g., PoB is running)
-    Conflict(String),
+    Conflict(ConflictInfo),
     /// Other domain errors
     Domain(String),
 }
 
+/// Why an operation was refused as a conflict, so the UI can offer the right remedy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictCode {
+    /// Another install/uninstall/check holds the operation lock (wait)
+    OperationInProgress,
+    /// PoB is running (close PoB)
+    PobRunning,
+    /// Install folder is locked by another process (close PoB/Explorer)
+    FileInUse,
+    /// Target folder can't be written (pick another folder)
+    DirNotWritable,
+    /// Network activity is paused (resume)
+    Quiesced,
+    /// User data couldn't be backed up before an update (pick a backup failure policy)
+    BackupFailed,
+    /// Update skipped after a failed backup under the `SkipUpdate` policy (install unchanged)
+    UpdateSkipped,
+    /// Target release is older than the install (confirm, then retry with `allow_downgrade`)
+    Downgrade,
+}
+
+#[derive(Debug, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictInfo {
+    pub code: ConflictCode,
+    /// Korean display string, for when the UI has no special handling for `code`
+    pub detail: String,
+}
+
+impl ErrorKind {
+    pub fn conflict(code: ConflictCode, message: impl Into<String>) -> Self {
+        ErrorKind::Conflict(ConflictInfo {
+            code,
+            detail: message.into(),
+        })
+    }
+
+    /// Refusal because another operation holds the operation lock.
+    pub fn operation_in_progress(message: impl Into<String>) -> Self {
+        Self::conflict(ConflictCode::OperationInProgress, message)
+    }
+
+    /// Serialized `kind` tag, for payloads that carry the category as a plain string.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::Cancelled => "cancelled",
+            ErrorKind::Network(_) => "network",
+            ErrorKind::Io(_) => "io",
+            ErrorKind::NotFound(_) => "notFound",
+            ErrorKind::Conflict(_) => "conflict",
+            ErrorKind::Domain(_) => "domain",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            ErrorKind::Cancelled => PobError::Cancelled.to_string(),
+            ErrorKind::Network(msg)
+            | ErrorKind::Io(msg)
+            | ErrorKind::NotFound(msg)
+            | ErrorKind::Domain(msg) => msg.clone(),
+            ErrorKind::Conflict(info) => info.detail.clone(),
+        }
+    }
+}
+
 impl From<tauri::Error> for ErrorKind {
     fn from(err: tauri::Error) -> Self {
         ErrorKind::Domain(err.to_string())
@@ -35,18 +102,61 @@ impl From<PobError> for ErrorKind {
             PobError::Cancelled => ErrorKind::Cancelled,
 
             // Conflict states
-            PobError::ProcessRunning => {
-                ErrorKind::Conflict("PoB가 실행 중입니다. 종료 후 다시 시도해주세요.".into())
-            }
+            PobError::ProcessRunning => ErrorKind::conflict(
+                ConflictCode::PobRunning,
+                "PoB가 실행 중입니다. 종료 후 다시 시도해주세요.",
+            ),
+            PobError::FileInUse(path) => ErrorKind::conflict(
+                ConflictCode::FileInUse,
+                format!(
+                    "파일이 사용 중입니다: {path}. PoB와 파일 탐색기를 닫은 후 다시 시도해주세요."
+                ),
+            ),
+            PobError::DirNotWritable(path) => ErrorKind::conflict(
+                ConflictCode::DirNotWritable,
+                format!("쓰기 권한이 없는 폴더입니다: {path}. 다른 폴더를 선택해주세요."),
+            ),
+            PobError::Quiesced => ErrorKind::conflict(
+                ConflictCode::Quiesced,
+                "활동이 일시 중지된 상태입니다. 재개 후 다시 시도해주세요.",
+            ),
+            PobError::BackupFailed(reason) => ErrorKind::conflict(
+                ConflictCode::BackupFailed,
+                format!(
+                    "사용자 데이터 백업에 실패했습니다: {reason}. 설정에서 백업 실패 시 동작을 선택할 수 있습니다."
+                ),
+            ),
+            PobError::UpdateSkipped(reason) => ErrorKind::conflict(
+                ConflictCode::UpdateSkipped,
+                format!(
+                    "사용자 데이터 백업에 실패해 이번 업데이트를 건너뛰었습니다: {reason}. 기존 설치는 그대로 유지됩니다."
+                ),
+            ),
+            PobError::Downgrade { installed, target } => ErrorKind::conflict(
+                ConflictCode::Downgrade,
+                format!(
+                    "설치된 버전({installed})이 설치하려는 버전({target})보다 최신입니다. 다운그레이드하려면 확인 후 다시 시도해주세요."
+                ),
+            ),
 
             // Network issues
             PobError::Network(e) => ErrorKind::Network(e.to_string()),
+            e @ PobError::DownloadTruncated { .. } => ErrorKind::Network(e.to_string()),
 
             // IO/filesystem issues
             PobError::Io(e) => ErrorKind::Io(e.to_string()),
 
             // Not found
             PobError::NotFoundFromDrive(msg) => ErrorKind::NotFound(msg),
+            PobError::NotInstalled => ErrorKind::NotFound(PobError::NotInstalled.to_string()),
+            PobError::NoPreviousVersion => {
+                ErrorKind::NotFound(PobError::NoPreviousVersion.to_string())
+            }
+            PobError::ManifestMissing => ErrorKind::NotFound(PobError::ManifestMissing.to_string()),
+            PobError::NoBackup => ErrorKind::NotFound(PobError::NoBackup.to_string()),
+            PobError::UnknownInstalledRelease => {
+                ErrorKind::NotFound(PobError::UnknownInstalledRelease.to_string())
+            }
 
             // Domain errors (everything else)
             other => ErrorKind::Domain(other.to_string()),
@@ -81,13 +191,102 @@ mod tests {
         let error_kind: ErrorKind = pob_error.into();
 
         match error_kind {
-            ErrorKind::Conflict(msg) => {
-                assert!(msg.contains("PoB가 실행 중입니다"));
+            ErrorKind::Conflict(info) => {
+                assert_eq!(info.code, ConflictCode::PobRunning);
+                assert!(info.detail.contains("PoB가 실행 중입니다"));
+            }
+            _ => panic!("Expected Conflict"),
+        }
+    }
+
+    #[test]
+    fn test_error_conversion_file_in_use() {
+        let err = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+        let pob_error = PobError::from_locked_io(err, std::path::Path::new("PoeCharm"));
+
+        match ErrorKind::from(pob_error) {
+            ErrorKind::Conflict(info) => {
+                assert_eq!(info.code, ConflictCode::FileInUse);
+                assert!(info.detail.contains("PoeCharm"));
+                assert!(info.detail.contains("파일 탐색기"));
             }
             _ => panic!("Expected Conflict"),
         }
     }
 
+    #[test]
+    fn test_error_conversion_quiesced() {
+        match ErrorKind::from(PobError::Quiesced) {
+            ErrorKind::Conflict(info) => {
+                assert_eq!(info.code, ConflictCode::Quiesced);
+                assert!(info.detail.contains("일시 중지"));
+            }
+            _ => panic!("Expected Conflict"),
+        }
+    }
+
+    #[test]
+    fn test_error_conversion_backup_failed() {
+        match ErrorKind::from(PobError::BackupFailed("disk full".into())) {
+            ErrorKind::Conflict(info) => {
+                assert_eq!(info.code, ConflictCode::BackupFailed);
+                assert!(info.detail.contains("disk full"));
+            }
+            _ => panic!("Expected Conflict"),
+        }
+    }
+
+    #[test]
+    fn test_error_conversion_update_skipped() {
+        match ErrorKind::from(PobError::UpdateSkipped("disk full".into())) {
+            ErrorKind::Conflict(info) => {
+                assert_eq!(info.code, ConflictCode::UpdateSkipped);
+                assert!(info.detail.contains("disk full"));
+            }
+            _ => panic!("Expected Conflict"),
+        }
+    }
+
+    #[test]
+    fn test_error_conversion_dir_not_writable() {
+        match ErrorKind::from(PobError::DirNotWritable("D:\\Games".into())) {
+            ErrorKind::Conflict(info) => {
+                assert_eq!(info.code, ConflictCode::DirNotWritable);
+                assert!(info.detail.contains("D:\\Games"));
+            }
+            _ => panic!("Expected Conflict"),
+        }
+    }
+
+    #[test]
+    fn test_conflict_serialization() {
+        let json = serde_json::to_value(ErrorKind::operation_in_progress("busy")).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "kind": "conflict",
+                "message": { "code": "operation_in_progress", "detail": "busy" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_error_kind_code_matches_tag() {
+        let kinds = vec![
+            ErrorKind::Cancelled,
+            ErrorKind::Network("n".into()),
+            ErrorKind::Io("i".into()),
+            ErrorKind::NotFound("f".into()),
+            ErrorKind::operation_in_progress("c"),
+            ErrorKind::Domain("d".into()),
+        ];
+
+        for kind in kinds {
+            let json = serde_json::to_value(&kind).unwrap();
+            assert_eq!(json["kind"], kind.code());
+        }
+    }
+
     #[tokio::test]
     async fn test_error_conversion_network() {
         let reqwest_error = reqwest::get("http://invalid.invalid").await.unwrap_err();
@@ -127,6 +326,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_error_conversion_not_installed() {
+        let error_kind: ErrorKind = PobError::NotInstalled.into();
+
+        match error_kind {
+            ErrorKind::NotFound(msg) => assert!(msg.contains("설치되어 있지 않습니다")),
+            _ => panic!("Expected NotFound"),
+        }
+    }
+
+    #[test]
+    fn test_error_conversion_no_backup() {
+        match ErrorKind::from(PobError::NoBackup) {
+            ErrorKind::NotFound(msg) => assert!(msg.contains("백업")),
+            _ => panic!("Expected NotFound"),
+        }
+    }
+
+    #[test]
+    fn test_error_conversion_unknown_installed_release() {
+        match ErrorKind::from(PobError::UnknownInstalledRelease) {
+            ErrorKind::NotFound(msg) => assert!(msg.contains("릴리스")),
+            _ => panic!("Expected NotFound"),
+        }
+    }
+
+    #[test]
+    fn test_error_conversion_download_truncated() {
+        let pob_error = PobError::DownloadTruncated {
+            expected: 100,
+            actual: 40,
+        };
+        let error_kind: ErrorKind = pob_error.into();
+
+        match error_kind {
+            ErrorKind::Network(msg) => assert!(msg.contains("40/100")),
+            other => panic!("Expected Network, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_error_conversion_download_failed() {
         let pob_error = PobError::DownloadFailed("connection timeout".to_string());
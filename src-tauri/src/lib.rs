@@ -5,12 +5,17 @@ pub mod util;
 use std::{path::Path, time::Duration};
 
 use tauri::Manager;
-use tauri_plugin_tracing::{Builder as TracingBuilder, LevelFilter, Rotation, RotationStrategy};
+use tauri_plugin_tracing::{
+    Builder as TracingBuilder, LevelFilter, Rotation, RotationStrategy, Target,
+};
 use tauri_specta::{collect_commands, collect_events};
 
 use crate::pob::{
-    InstallCancelToken, google_drive::GoogleDriveClient, manager::PobManager,
-    progress::InstallProgress,
+    InstallCancelTokens,
+    config::{portable_log_dir, portable_root, resolve_data_dir},
+    google_drive::GoogleDriveClient,
+    manager::{DEFAULT_FOLDER_ID, PobManager},
+    progress::{InstallFinished, InstallProgress, OverallProgress},
 };
 
 /// Issue 4: Cleanup orphaned temp directories from previous crashes.
@@ -57,46 +62,81 @@ pub fn run() {
 
     let specta_builder = specta_builder();
 
+    // A `portable.txt` next to the executable keeps everything beside it (e.g. on a USB drive)
+    let portable = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().and_then(portable_root));
+
+    let logging = TracingBuilder::default()
+        .with_max_level(LevelFilter::DEBUG) // Set max log level to DEBUG
+        .with_target("exile_rs_lib", LevelFilter::TRACE)
+        .with_target("h2", LevelFilter::WARN)
+        .with_target("hyper", LevelFilter::WARN)
+        .with_colors();
+    // Enable file logging to platform log directory, or the portable root's
+    let logging = match &portable {
+        Some(root) => logging.target(Target::Folder {
+            path: portable_log_dir(root),
+            file_name: None,
+        }),
+        None => logging.with_file_logging(),
+    };
+    let logging = logging
+        .with_rotation(Rotation::Daily) // Rotate log files daily
+        .with_rotation_strategy(RotationStrategy::KeepSome(7)) // Keep last 7 log files
+        .with_file(true) // Show source file in logs
+        .with_line_number(true) // Show line number in logs
+        .with_target_display(true) // Show module target in logs
+        .with_level(true) // Show log level in logs
+        .with_default_subscriber(); // Set as global tracing subscriber
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_store::Builder::default().build())
         .plugin(tauri_plugin_updater::Builder::new().build())
-        .plugin(
-            TracingBuilder::default()
-                .with_max_level(LevelFilter::DEBUG) // Set max log level to DEBUG
-                .with_target("exile_rs_lib", LevelFilter::TRACE)
-                .with_target("h2", LevelFilter::WARN)
-                .with_target("hyper", LevelFilter::WARN)
-                .with_colors()
-                .with_file_logging() // Enable file logging to platform log directory
-                .with_rotation(Rotation::Daily) // Rotate log files daily
-                .with_rotation_strategy(RotationStrategy::KeepSome(7)) // Keep last 7 log files
-                .with_file(true) // Show source file in logs
-                .with_line_number(true) // Show line number in logs
-                .with_target_display(true) // Show module target in logs
-                .with_level(true) // Show log level in logs
-                .with_default_subscriber() // Set as global tracing subscriber
-                .build(),
-        )
+        .plugin(logging.build())
         .invoke_handler(specta_builder.invoke_handler())
-        .manage(InstallCancelToken::default())
+        .manage(InstallCancelTokens::default())
         .setup(move |app| {
             specta_builder.mount_events(app.handle());
 
             let client = GoogleDriveClient::new(client);
 
-            let data_dir = app
-                .path()
-                .app_local_data_dir()
-                .expect("Failed to get app local data dir");
+            let pob_manager = match portable {
+                Some(root) => {
+                    tracing::info!(operation = "setup", root = %root.display(), "Running in portable mode");
+                    PobManager::new_portable(client, root, DEFAULT_FOLDER_ID.to_string())
+                }
+                None => {
+                    let app_dir = app
+                        .path()
+                        .app_local_data_dir()
+                        .expect("Failed to get app local data dir");
+                    // Follow a previous relocate_data_dir, if any
+                    PobManager::new(
+                        client,
+                        resolve_data_dir(&app_dir),
+                        DEFAULT_FOLDER_ID.to_string(),
+                    )
+                }
+            };
 
             // Issue 4: Cleanup orphaned temp directories from previous crashes
-            if let Ok(temp_dir) = app.path().temp_dir() {
+            if let Some(temp_dir) = pob_manager
+                .portable_temp_dir()
+                .or_else(|| app.path().temp_dir().ok())
+            {
                 cleanup_orphaned_temp_dirs(&temp_dir);
             }
 
-            let pob_manager = PobManager::new(client, data_dir);
+            // Settle an install the previous run died in; the frontend asks the user if needed
+            if let Err(e) = pob_manager.recover_incomplete_install() {
+                tracing::error!(operation = "recovery", error = %e, "Failed to recover interrupted install");
+            }
+            // Repair backup dirs left inconsistent by a crash mid-backup
+            pob_manager.recover_backup_swap();
+            pob_manager.migrate_legacy_backup();
             app.manage(pob_manager);
 
             Ok(())
@@ -109,15 +149,58 @@ fn specta_builder() -> tauri_specta::Builder {
     let builder = tauri_specta::Builder::new()
         .commands(collect_commands![
             commands::fetch_pob,
+            commands::list_versions,
+            commands::update_state,
+            commands::check_for_update,
+            commands::update_download_estimate,
             commands::installed_pob_info,
+            commands::new_task_id,
             commands::install_pob,
+            commands::check_install_integrity,
+            commands::reinstall_pob,
+            commands::repair_pob,
             commands::cancel_install_pob,
+            commands::take_recovery_action,
+            commands::quiesce,
+            commands::resume_activity,
             commands::parse_version,
+            commands::test_version_pattern,
             commands::uninstall_pob,
+            commands::previous_pob_info,
+            commands::get_changelog,
+            commands::revert_to_previous,
+            commands::is_pob_running,
             commands::execute_pob,
             commands::get_install_path,
+            commands::open_install_folder,
+            commands::open_log_folder,
+            commands::get_config,
+            commands::set_config,
+            commands::get_download_config,
+            commands::set_download_config,
+            commands::replay_last_install,
+            commands::relocate_data_dir,
+            commands::set_install_root,
+            commands::rebuild_version_info,
+            commands::diff_against_latest,
+            commands::verify_install,
+            commands::test_backup_integrity,
+            commands::create_manual_backup,
+            commands::list_backups,
+            commands::restore_from_backup,
+            commands::list_snapshots,
+            commands::restore_snapshot,
+            commands::get_backup_targets,
+            commands::save_backup_targets,
+            commands::inspect_archive,
+            commands::reclaim_space,
+            commands::export_diagnostics,
         ])
-        .events(collect_events![InstallProgress,]);
+        .events(collect_events![
+            InstallProgress,
+            InstallFinished,
+            OverallProgress
+        ]);
 
     #[cfg(debug_assertions)]
     {
@@ -0,0 +1,213 @@
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::pob::{error::PobError, verify::REQUIRED_DIRS};
+
+/// User data preserved across installs when no manifest overrides it
+pub const DEFAULT_BACKUP_TARGETS: &[&str] = &[
+    "POE1 POB/Builds",
+    "POE2 POB/Builds",
+    "POE1 POB/Settings.xml",
+    "POE2 POB/Settings.xml",
+    "Data/Fonts",
+];
+
+/// Install-relative paths backed up and restored around installs, persisted
+/// as `<data_dir>/backup_manifest.json`. Absent means [`DEFAULT_BACKUP_TARGETS`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupManifest {
+    pub targets: Vec<String>,
+}
+
+impl BackupManifest {
+    /// Validate and normalize `targets`, dropping duplicates. At least one is required.
+    pub fn new(targets: Vec<String>) -> Result<Self, PobError> {
+        if targets.is_empty() {
+            return Err(PobError::InvalidConfig(
+                "백업 대상을 하나 이상 지정해야 합니다".into(),
+            ));
+        }
+        let mut normalized: Vec<String> = Vec::with_capacity(targets.len());
+        for target in targets {
+            let path = validate_target(&target)?;
+            let target = path.to_string_lossy().replace('\\', "/");
+            if !normalized.contains(&target) {
+                normalized.push(target);
+            }
+        }
+        Ok(Self {
+            targets: normalized,
+        })
+    }
+
+    /// Load the manifest at `path`. `None` if absent or unreadable, so the
+    /// defaults apply. Entries that fail validation are skipped.
+    pub fn load(path: &Path) -> Option<Self> {
+        let data = match std::fs::read_to_string(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                tracing::warn!(
+                    operation = "backup_manifest",
+                    path = %path.display(),
+                    error = %e,
+                    "Failed to read backup manifest, using defaults"
+                );
+                return None;
+            }
+        };
+
+        let manifest: Self = serde_json::from_str(&data)
+            .inspect_err(|e| {
+                tracing::warn!(
+                    operation = "backup_manifest",
+                    path = %path.display(),
+                    error = %e,
+                    "Failed to parse backup manifest, using defaults"
+                );
+            })
+            .ok()?;
+        let targets = manifest
+            .targets
+            .into_iter()
+            .filter(|target| match validate_target(target) {
+                Ok(_) => true,
+                Err(e) => {
+                    tracing::warn!(operation = "backup_manifest", target = %target, error = %e, "Skipping invalid backup target");
+                    false
+                }
+            })
+            .collect();
+        Some(Self { targets })
+    }
+
+    pub async fn save(&self, path: &Path) -> Result<(), PobError> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let data = serde_json::to_string_pretty(self)?;
+        tokio::fs::write(path, data).await?;
+        Ok(())
+    }
+
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.targets.iter().map(PathBuf::from).collect()
+    }
+}
+
+/// Parse `target` as a path inside the install. Absolute paths, drive
+/// prefixes and `..` are rejected so a manifest can't pull in arbitrary files,
+/// and so are whole program folders like `POE1 POB`, which restoring would
+/// roll back to the old release.
+fn validate_target(target: &str) -> Result<PathBuf, PobError> {
+    let path = Path::new(target.trim());
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => normalized.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(PobError::InvalidConfig(format!(
+                    "설치 폴더 밖을 가리키는 백업 대상입니다: {target}"
+                )));
+            }
+        }
+    }
+    if normalized.as_os_str().is_empty() {
+        return Err(PobError::InvalidConfig(format!(
+            "백업 대상 경로가 비어 있습니다: {target:?}"
+        )));
+    }
+    if REQUIRED_DIRS
+        .iter()
+        .any(|dir| normalized.as_os_str().eq_ignore_ascii_case(dir))
+    {
+        return Err(PobError::InvalidConfig(format!(
+            "프로그램 폴더 전체는 백업 대상이 될 수 없습니다: {target}"
+        )));
+    }
+    Ok(normalized)
+}
+
+/// Resolve the backup manifest location under `data_dir`.
+pub fn backup_manifest_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("backup_manifest.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_rejects_escaping_targets() {
+        for target in ["../secrets", "Builds/../../x", "/etc/passwd", "", "  ", "."] {
+            assert!(
+                matches!(
+                    BackupManifest::new(vec![target.into()]),
+                    Err(PobError::InvalidConfig(_))
+                ),
+                "{target:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_new_rejects_empty_list_and_program_folders() {
+        assert!(matches!(
+            BackupManifest::new(Vec::new()),
+            Err(PobError::InvalidConfig(_))
+        ));
+        for target in ["POE1 POB", "./POE2 POB/", "Data", "poe1 pob"] {
+            assert!(
+                matches!(
+                    BackupManifest::new(vec!["POE1 POB/Builds".into(), target.into()]),
+                    Err(PobError::InvalidConfig(_))
+                ),
+                "{target:?} should be rejected"
+            );
+        }
+        // Folders inside them are fine
+        assert!(BackupManifest::new(vec!["Data/Fonts".into()]).is_ok());
+    }
+
+    #[test]
+    fn test_new_normalizes_and_dedupes() {
+        let manifest = BackupManifest::new(vec![
+            "POE1 POB/Builds".into(),
+            "./POE1 POB/Builds/".into(),
+            " Data/Fonts ".into(),
+        ])
+        .unwrap();
+        assert_eq!(manifest.targets, ["POE1 POB/Builds", "Data/Fonts"]);
+    }
+
+    #[tokio::test]
+    async fn test_load_round_trip_and_fallbacks() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = backup_manifest_path(temp.path());
+        assert!(BackupManifest::load(&path).is_none());
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(BackupManifest::load(&path).is_none());
+
+        // A hand-edited file keeps its valid entries
+        std::fs::write(
+            &path,
+            r#"{"targets": ["POE1 POB/Builds", "../outside", "POE2 POB/Custom"]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            BackupManifest::load(&path).unwrap().targets,
+            ["POE1 POB/Builds", "POE2 POB/Custom"]
+        );
+
+        let manifest = BackupManifest::new(vec!["Data/Fonts".into()]).unwrap();
+        manifest.save(&path).await.unwrap();
+        assert_eq!(
+            BackupManifest::load(&path).unwrap().paths(),
+            [PathBuf::from("Data/Fonts")]
+        );
+    }
+}
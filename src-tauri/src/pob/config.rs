@@ -0,0 +1,521 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::pob::{error::PobError, version::compile_name_pattern};
+
+/// User-tunable PoB manager settings, persisted as `<data_dir>/config.json`.
+/// Missing fields fall back to their defaults so older files keep loading.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PobConfig {
+    /// Which extracted files get their ZIP modification time restored
+    pub mtime_policy: MtimePolicy,
+    /// Download tuning, applied from the next install onward
+    pub download: DownloadConfig,
+    /// Skip extracting archive entries under existing user-data paths,
+    /// since restore would overwrite them anyway
+    pub skip_user_data_on_extract: bool,
+    /// What to do with archive entries whose path escapes the install folder
+    pub on_dangerous_path: DangerousPathPolicy,
+    /// Release filename regex with a named `version` group, for folders that
+    /// don't follow the default Korean naming. `None` uses the default.
+    pub archive_name_pattern: Option<String>,
+    /// Move the downloaded archive to `failed/` instead of deleting it when
+    /// an install fails after the download, for manual inspection
+    pub keep_download_on_failure: bool,
+    /// What to do when user data can't be backed up during an install
+    pub on_backup_failure: BackupFailurePolicy,
+    /// Cap on file handles and connections held at once across all
+    /// concurrent operations (downloads, extraction, range fetches)
+    pub max_open_handles: u32,
+    /// Folder holding the install and its backup, e.g. on a roomier drive.
+    /// `None` keeps them in the data dir. Only changed via `set_install_root`.
+    pub install_root: Option<String>,
+    /// How many timestamped backups installs keep; the oldest are pruned
+    pub max_backups: u32,
+    /// Google Drive API key. When set, the release folder is listed through
+    /// the Drive API instead of scraping its page
+    pub drive_api_key: Option<String>,
+    /// How long the latest release found in a folder is reused before it's
+    /// fetched again. `0` fetches every time
+    pub latest_cache_ttl_secs: u32,
+}
+
+impl Default for PobConfig {
+    fn default() -> Self {
+        Self {
+            mtime_policy: MtimePolicy::default(),
+            download: DownloadConfig::default(),
+            skip_user_data_on_extract: false,
+            on_dangerous_path: DangerousPathPolicy::default(),
+            archive_name_pattern: None,
+            keep_download_on_failure: false,
+            on_backup_failure: BackupFailurePolicy::default(),
+            max_open_handles: 32,
+            install_root: None,
+            max_backups: 3,
+            drive_api_key: None,
+            latest_cache_ttl_secs: 600,
+        }
+    }
+}
+
+impl PobConfig {
+    /// Most handles a single operation holds at once (a connection plus its file)
+    pub const MIN_OPEN_HANDLES: u32 = 2;
+    pub const MAX_OPEN_HANDLES: u32 = 1024;
+    pub const MAX_BACKUPS: u32 = 50;
+
+    /// Load config from `path`, falling back to defaults if absent or unreadable.
+    pub fn load(path: &Path) -> Self {
+        let data = match std::fs::read_to_string(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Self::default(),
+            Err(e) => {
+                tracing::warn!(
+                    operation = "config",
+                    path = %path.display(),
+                    error = %e,
+                    "Failed to read config, using defaults"
+                );
+                return Self::default();
+            }
+        };
+
+        serde_json::from_str(&data).unwrap_or_else(|e| {
+            tracing::warn!(
+                operation = "config",
+                path = %path.display(),
+                error = %e,
+                "Failed to parse config, using defaults"
+            );
+            Self::default()
+        })
+    }
+
+    pub fn validate(&self) -> Result<(), PobError> {
+        self.download.validate()?;
+        if let Some(pattern) = &self.archive_name_pattern {
+            compile_name_pattern(pattern)?;
+        }
+        if !(Self::MIN_OPEN_HANDLES..=Self::MAX_OPEN_HANDLES).contains(&self.max_open_handles) {
+            return Err(PobError::InvalidConfig(format!(
+                "동시 파일 핸들 수는 {}~{} 사이여야 합니다: {}",
+                Self::MIN_OPEN_HANDLES,
+                Self::MAX_OPEN_HANDLES,
+                self.max_open_handles
+            )));
+        }
+        if !(1..=Self::MAX_BACKUPS).contains(&self.max_backups) {
+            return Err(PobError::InvalidConfig(format!(
+                "보관할 백업 수는 1~{} 사이여야 합니다: {}",
+                Self::MAX_BACKUPS,
+                self.max_backups
+            )));
+        }
+        if self
+            .drive_api_key
+            .as_deref()
+            .is_some_and(|key| key.trim().is_empty() || key.contains(char::is_whitespace))
+        {
+            return Err(PobError::InvalidConfig(
+                "Google Drive API 키가 비어 있거나 공백을 포함합니다".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn save(&self, path: &Path) -> Result<(), PobError> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let data = serde_json::to_string_pretty(self)?;
+        tokio::fs::write(path, data).await?;
+        Ok(())
+    }
+}
+
+/// Controls whether `set_modified` is applied to extracted files.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase", tag = "kind", content = "extensions")]
+pub enum MtimePolicy {
+    /// Preserve mtime for every file (original behavior)
+    #[default]
+    All,
+    /// Never touch mtime (fewest syscalls)
+    None,
+    /// Preserve mtime only for the listed extensions (e.g. `["lua", "xml"]`)
+    Extensions(Vec<String>),
+}
+
+impl MtimePolicy {
+    pub fn should_preserve(&self, path: &Path) -> bool {
+        match self {
+            MtimePolicy::All => true,
+            MtimePolicy::None => false,
+            MtimePolicy::Extensions(list) => {
+                let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+                    return false;
+                };
+                list.iter()
+                    .any(|allowed| allowed.trim_start_matches('.').eq_ignore_ascii_case(ext))
+            }
+        }
+    }
+}
+
+/// Handling of ZIP entries with absolute or `..`-escaping paths.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum DangerousPathPolicy {
+    /// Skip such entries and keep extracting; the total is logged
+    #[default]
+    Skip,
+    /// Treat the archive as untrusted and fail the extraction
+    Abort,
+}
+
+/// Handling of a failed user-data backup, after the new version was already extracted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum BackupFailurePolicy {
+    /// Fail the install with a `backup_failed` conflict the UI can prompt on
+    #[default]
+    Abort,
+    /// Install without a backup and skip restoring; user data stays in the previous install
+    Continue,
+    /// Skip this update with an `update_skipped` conflict, leaving the existing install untouched
+    SkipUpdate,
+}
+
+/// Tunable parameters of the archive download.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DownloadConfig {
+    /// Write buffer size in bytes
+    pub buffer_size: u32,
+    /// Preallocate the target file to the reported content length
+    pub preallocate: bool,
+    /// Maximum downloads in flight at once across all operations
+    pub max_concurrent_downloads: u32,
+    /// Updates at least this many MiB are flagged as large before downloading
+    pub large_download_mb: u32,
+    /// Size probes in flight at once when listing versions with metadata
+    pub metadata_prefetch_concurrency: u32,
+}
+
+impl DownloadConfig {
+    pub const MIN_BUFFER_SIZE: u32 = 4 * 1024;
+    pub const MAX_BUFFER_SIZE: u32 = 8 * 1024 * 1024;
+    pub const MAX_CONCURRENT_DOWNLOADS: u32 = 8;
+    /// Kept low: bursts of probes trip Drive's rate limiting
+    pub const MAX_METADATA_PREFETCH_CONCURRENCY: u32 = 8;
+
+    pub fn validate(&self) -> Result<(), PobError> {
+        if !(Self::MIN_BUFFER_SIZE..=Self::MAX_BUFFER_SIZE).contains(&self.buffer_size) {
+            return Err(PobError::InvalidConfig(format!(
+                "버퍼 크기는 {}~{} 바이트 사이여야 합니다: {}",
+                Self::MIN_BUFFER_SIZE,
+                Self::MAX_BUFFER_SIZE,
+                self.buffer_size
+            )));
+        }
+        if !(1..=Self::MAX_CONCURRENT_DOWNLOADS).contains(&self.max_concurrent_downloads) {
+            return Err(PobError::InvalidConfig(format!(
+                "동시 다운로드 수는 1~{} 사이여야 합니다: {}",
+                Self::MAX_CONCURRENT_DOWNLOADS,
+                self.max_concurrent_downloads
+            )));
+        }
+        if !(1..=Self::MAX_METADATA_PREFETCH_CONCURRENCY)
+            .contains(&self.metadata_prefetch_concurrency)
+        {
+            return Err(PobError::InvalidConfig(format!(
+                "동시 정보 조회 수는 1~{} 사이여야 합니다: {}",
+                Self::MAX_METADATA_PREFETCH_CONCURRENCY,
+                self.metadata_prefetch_concurrency
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Default for DownloadConfig {
+    fn default() -> Self {
+        Self {
+            buffer_size: 64 * 1024,
+            preallocate: true,
+            max_concurrent_downloads: 2,
+            large_download_mb: 500,
+            metadata_prefetch_concurrency: 3,
+        }
+    }
+}
+
+/// Resolve the config file location under `data_dir`.
+pub fn config_file_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("config.json")
+}
+
+/// Pointer left in the default app data dir after [`relocate_data_dir`], so the
+/// next launch finds the moved data.
+///
+/// [`relocate_data_dir`]: crate::pob::manager::PobManager::relocate_data_dir
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DataDirRedirect {
+    data_dir: PathBuf,
+}
+
+fn data_dir_redirect_path(app_dir: &Path) -> PathBuf {
+    app_dir.join("data_dir.json")
+}
+
+/// Resolve the effective data dir, following a relocation redirect if one exists.
+/// A redirect to a missing directory is ignored so a detached drive doesn't brick startup.
+pub fn resolve_data_dir(app_dir: &Path) -> PathBuf {
+    let path = data_dir_redirect_path(app_dir);
+    let Ok(data) = std::fs::read_to_string(&path) else {
+        return app_dir.to_path_buf();
+    };
+    match serde_json::from_str::<DataDirRedirect>(&data) {
+        Ok(redirect) if redirect.data_dir.is_dir() => redirect.data_dir,
+        Ok(redirect) => {
+            tracing::warn!(
+                operation = "config",
+                path = %redirect.data_dir.display(),
+                "Relocated data dir is missing, using default"
+            );
+            app_dir.to_path_buf()
+        }
+        Err(e) => {
+            tracing::warn!(
+                operation = "config",
+                path = %path.display(),
+                error = %e,
+                "Failed to parse data dir redirect, using default"
+            );
+            app_dir.to_path_buf()
+        }
+    }
+}
+
+/// Point future launches at `data_dir`. Relocating back to `app_dir` removes the redirect.
+pub async fn save_data_dir_redirect(app_dir: &Path, data_dir: &Path) -> Result<(), PobError> {
+    let path = data_dir_redirect_path(app_dir);
+    if data_dir == app_dir {
+        return match tokio::fs::remove_file(&path).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        };
+    }
+    tokio::fs::create_dir_all(app_dir).await?;
+    let redirect = DataDirRedirect {
+        data_dir: data_dir.to_path_buf(),
+    };
+    tokio::fs::write(&path, serde_json::to_string_pretty(&redirect)?).await?;
+    Ok(())
+}
+
+/// Marker file next to the executable that switches the app into portable mode.
+const PORTABLE_MARKER: &str = "portable.txt";
+
+/// Portable data root for an executable in `exe_dir`, if a [`PORTABLE_MARKER`] sits
+/// next to it. Install, backups, settings, temp files and logs then all live beneath it.
+pub fn portable_root(exe_dir: &Path) -> Option<PathBuf> {
+    exe_dir
+        .join(PORTABLE_MARKER)
+        .is_file()
+        .then(|| exe_dir.join("data"))
+}
+
+/// Temp folder under a portable `root`, used instead of the system temp dir.
+pub fn portable_temp_dir(root: &Path) -> PathBuf {
+    root.join("temp")
+}
+
+/// Log folder under a portable `root`, used instead of the platform log dir.
+pub fn portable_log_dir(root: &Path) -> PathBuf {
+    root.join("logs")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mtime_policy_all_and_none() {
+        let path = Path::new("Data/Fonts/font.ttf");
+        assert!(MtimePolicy::All.should_preserve(path));
+        assert!(!MtimePolicy::None.should_preserve(path));
+    }
+
+    #[test]
+    fn test_mtime_policy_extensions() {
+        let policy = MtimePolicy::Extensions(vec!["lua".into(), ".XML".into()]);
+
+        assert!(policy.should_preserve(Path::new("POE1 POB/Launch.lua")));
+        assert!(policy.should_preserve(Path::new("POE1 POB/Settings.xml")));
+        assert!(!policy.should_preserve(Path::new("Data/Fonts/font.ttf")));
+        assert!(!policy.should_preserve(Path::new("README")));
+    }
+
+    #[test]
+    fn test_config_defaults_on_missing_fields() {
+        let config: PobConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.mtime_policy, MtimePolicy::All);
+        assert_eq!(config.on_dangerous_path, DangerousPathPolicy::Skip);
+
+        let config: PobConfig = serde_json::from_str(r#"{"onDangerousPath":"abort"}"#).unwrap();
+        assert_eq!(config.on_dangerous_path, DangerousPathPolicy::Abort);
+    }
+
+    #[test]
+    fn test_config_serialization() {
+        let config = PobConfig {
+            mtime_policy: MtimePolicy::Extensions(vec!["lua".into()]),
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["mtimePolicy"]["kind"], "extensions");
+        assert_eq!(json["mtimePolicy"]["extensions"][0], "lua");
+    }
+
+    #[test]
+    fn test_download_config_validation() {
+        assert!(DownloadConfig::default().validate().is_ok());
+
+        let too_small = DownloadConfig {
+            buffer_size: 1024,
+            ..Default::default()
+        };
+        assert!(matches!(
+            too_small.validate(),
+            Err(PobError::InvalidConfig(_))
+        ));
+
+        let too_large = DownloadConfig {
+            buffer_size: DownloadConfig::MAX_BUFFER_SIZE + 1,
+            ..Default::default()
+        };
+        assert!(too_large.validate().is_err());
+
+        let no_downloads = DownloadConfig {
+            max_concurrent_downloads: 0,
+            ..Default::default()
+        };
+        assert!(no_downloads.validate().is_err());
+
+        let too_many_probes = DownloadConfig {
+            metadata_prefetch_concurrency: DownloadConfig::MAX_METADATA_PREFETCH_CONCURRENCY + 1,
+            ..Default::default()
+        };
+        assert!(too_many_probes.validate().is_err());
+    }
+
+    #[test]
+    fn test_max_open_handles_validation() {
+        assert!(PobConfig::default().validate().is_ok());
+        let too_few = PobConfig {
+            max_open_handles: PobConfig::MIN_OPEN_HANDLES - 1,
+            ..Default::default()
+        };
+        assert!(matches!(
+            too_few.validate(),
+            Err(PobError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_max_backups_validation() {
+        let none_kept = PobConfig {
+            max_backups: 0,
+            ..Default::default()
+        };
+        assert!(none_kept.validate().is_err());
+        let too_many = PobConfig {
+            max_backups: PobConfig::MAX_BACKUPS + 1,
+            ..Default::default()
+        };
+        assert!(too_many.validate().is_err());
+    }
+
+    #[test]
+    fn test_drive_api_key_validation() {
+        let key = |key: &str| PobConfig {
+            drive_api_key: Some(key.into()),
+            ..Default::default()
+        };
+        assert!(key("AIzaSyA-example_key").validate().is_ok());
+        assert!(key("").validate().is_err());
+        assert!(key("AIza Sy").validate().is_err());
+    }
+
+    #[test]
+    fn test_archive_name_pattern_validation() {
+        let custom = PobConfig {
+            archive_name_pattern: Some(r"PoB (?P<version>[\d.]+)\.zip".into()),
+            ..Default::default()
+        };
+        assert!(custom.validate().is_ok());
+
+        let no_group = PobConfig {
+            archive_name_pattern: Some(r"PoB ([\d.]+)\.zip".into()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            no_group.validate(),
+            Err(PobError::InvalidConfig(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_config_save_and_load() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = config_file_path(temp.path());
+
+        assert_eq!(PobConfig::load(&path).mtime_policy, MtimePolicy::All);
+
+        let config = PobConfig {
+            mtime_policy: MtimePolicy::None,
+            ..Default::default()
+        };
+        config.save(&path).await.unwrap();
+
+        assert_eq!(PobConfig::load(&path).mtime_policy, MtimePolicy::None);
+    }
+
+    #[tokio::test]
+    async fn test_data_dir_redirect() {
+        let app_dir = tempfile::tempdir().unwrap();
+        let moved = tempfile::tempdir().unwrap();
+
+        assert_eq!(resolve_data_dir(app_dir.path()), app_dir.path());
+
+        save_data_dir_redirect(app_dir.path(), moved.path())
+            .await
+            .unwrap();
+        assert_eq!(resolve_data_dir(app_dir.path()), moved.path());
+
+        save_data_dir_redirect(app_dir.path(), app_dir.path())
+            .await
+            .unwrap();
+        assert!(!data_dir_redirect_path(app_dir.path()).exists());
+        assert_eq!(resolve_data_dir(app_dir.path()), app_dir.path());
+    }
+
+    #[test]
+    fn test_portable_root_requires_marker() {
+        let exe_dir = tempfile::tempdir().unwrap();
+        assert_eq!(portable_root(exe_dir.path()), None);
+
+        std::fs::write(exe_dir.path().join(PORTABLE_MARKER), "").unwrap();
+        assert_eq!(
+            portable_root(exe_dir.path()),
+            Some(exe_dir.path().join("data"))
+        );
+    }
+}
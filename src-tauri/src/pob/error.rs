@@ -1,3 +1,5 @@
+use std::{io, path::Path};
+
 /// Domain-specific errors for PoB operations.
 /// These are internal errors; they get mapped to ErrorKind for IPC.
 #[derive(Debug, thiserror::Error)]
@@ -11,6 +13,50 @@ pub enum PobError {
     #[error("PoB가 실행 중입니다")]
     ProcessRunning,
 
+    /// Network activity is paused via `quiesce` until resumed
+    #[error("활동이 일시 중지된 상태입니다")]
+    Quiesced,
+
+    /// Folder is locked by another process (PoB, Explorer, antivirus)
+    #[error("파일이 사용 중입니다: {0}")]
+    FileInUse(String),
+
+    /// Folder rejects writes, so nothing can be installed or moved there
+    #[error("쓰기 권한이 없는 폴더입니다: {0}")]
+    DirNotWritable(String),
+
+    /// Backing up user data failed under the `Abort` backup failure policy
+    #[error("사용자 데이터 백업 실패: {0}")]
+    BackupFailed(String),
+
+    /// Backing up user data failed under the `SkipUpdate` policy; the install was left as it was
+    #[error("사용자 데이터 백업 실패로 업데이트를 건너뛰었습니다: {0}")]
+    UpdateSkipped(String),
+
+    /// Installing `target` would replace the newer `installed` version
+    #[error("설치된 버전({installed})보다 오래된 버전({target})입니다")]
+    Downgrade { installed: String, target: String },
+
+    /// No PoB install present where one is required
+    #[error("POB가 설치되어 있지 않습니다")]
+    NotInstalled,
+
+    /// No retained previous install to revert to
+    #[error("되돌릴 이전 버전이 없습니다")]
+    NoPreviousVersion,
+
+    /// No backup has been taken yet
+    #[error("백업이 없습니다")]
+    NoBackup,
+
+    /// Install predates manifests, so it can't be verified offline
+    #[error("설치 검증 정보가 없습니다. 재설치 후 다시 시도해주세요")]
+    ManifestMissing,
+
+    /// The version file doesn't say which release is installed
+    #[error("설치된 릴리스를 알 수 없습니다. 최신 버전을 다시 설치해주세요")]
+    UnknownInstalledRelease,
+
     /// File not found on Google Drive
     #[error("Google Drive에서 파일을 찾을 수 없습니다: {0}")]
     NotFoundFromDrive(String),
@@ -20,6 +66,20 @@ pub enum PobError {
     #[error("다운로드 실패: {0}")]
     DownloadFailed(String),
 
+    /// Drive kept answering with its virus-scan confirmation page instead of the file
+    #[error(
+        "Google Drive 다운로드 확인 페이지를 통과하지 못했습니다. 브라우저에서 직접 받아주세요"
+    )]
+    DriveConfirmationRequired,
+
+    /// Download ended before `expected` bytes arrived; resuming or retrying helps
+    #[error("다운로드가 중간에 끊겼습니다: {actual}/{expected} 바이트 수신")]
+    DownloadTruncated { expected: u64, actual: u64 },
+
+    /// Not enough free space where the install would be extracted
+    #[error("디스크 공간이 부족합니다: {required} 바이트 필요, {available} 바이트 사용 가능")]
+    InsufficientSpace { required: u64, available: u64 },
+
     /// Extraction failed with context
     #[error("압축 해제 실패: {0}")]
     ExtractFailed(String),
@@ -28,6 +88,22 @@ pub enum PobError {
     #[error("버전 파싱 실패: {0}")]
     VersionParseError(String),
 
+    /// An operation needs more handles than `max_open_handles` allows in total
+    #[error("리소스 한도 초과: {0}")]
+    ResourceLimit(String),
+
+    /// Rejected settings value
+    #[error("잘못된 설정: {0}")]
+    InvalidConfig(String),
+
+    /// Rejected data directory relocation target
+    #[error("잘못된 데이터 폴더: {0}")]
+    InvalidDataDir(String),
+
+    /// Data directory copy failed or didn't verify
+    #[error("데이터 폴더 이동 실패: {0}")]
+    RelocateFailed(String),
+
     // === Wrapped external errors ===
     /// Network errors (reqwest)
     #[error("네트워크 에러: {0}")]
@@ -53,3 +129,63 @@ pub enum PobError {
     #[error("작업 조인 에러: {0}")]
     Join(#[from] tokio::task::JoinError),
 }
+
+impl PobError {
+    /// Classify a rename/remove failure on `path`, turning "held open by another
+    /// process" errors into [`PobError::FileInUse`] and keeping the rest as I/O.
+    pub fn from_locked_io(err: io::Error, path: &Path) -> Self {
+        if is_locked_io(&err) {
+            tracing::warn!(path = %path.display(), error = %err, "Path is locked by another process");
+            PobError::FileInUse(path.display().to_string())
+        } else {
+            PobError::Io(err)
+        }
+    }
+}
+
+/// Access denied, or a Windows sharing/lock violation.
+fn is_locked_io(err: &io::Error) -> bool {
+    /// `ERROR_SHARING_VIOLATION` and `ERROR_LOCK_VIOLATION`
+    #[cfg(windows)]
+    const LOCK_ERRORS: &[i32] = &[32, 33];
+    #[cfg(not(windows))]
+    const LOCK_ERRORS: &[i32] = &[];
+
+    err.kind() == io::ErrorKind::PermissionDenied
+        || err
+            .raw_os_error()
+            .is_some_and(|code| LOCK_ERRORS.contains(&code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_locked_io_permission_denied() {
+        let err = io::Error::from(io::ErrorKind::PermissionDenied);
+        match PobError::from_locked_io(err, Path::new("PoeCharm")) {
+            PobError::FileInUse(path) => assert_eq!(path, "PoeCharm"),
+            other => panic!("Expected FileInUse, got {other:?}"),
+        }
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_from_locked_io_sharing_violation() {
+        let err = io::Error::from_raw_os_error(32);
+        assert!(matches!(
+            PobError::from_locked_io(err, Path::new("PoeCharm")),
+            PobError::FileInUse(_)
+        ));
+    }
+
+    #[test]
+    fn test_from_locked_io_keeps_other_errors() {
+        let err = io::Error::from(io::ErrorKind::NotFound);
+        assert!(matches!(
+            PobError::from_locked_io(err, Path::new("PoeCharm")),
+            PobError::Io(_)
+        ));
+    }
+}
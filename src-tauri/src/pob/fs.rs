@@ -0,0 +1,203 @@
+use std::{fs::Metadata, io, path::Path};
+
+use futures_util::future::BoxFuture;
+
+/// Filesystem operations used by the install/backup/rollback workflow.
+///
+/// Routing them through a trait lets tests inject failures at exact steps
+/// (e.g. the second rename of a swap). Blocking work that already runs inside
+/// `spawn_blocking` (extraction, relocation copy) uses `std::fs` directly.
+pub trait Fs: Send + Sync {
+    fn rename<'a>(&'a self, from: &'a Path, to: &'a Path) -> BoxFuture<'a, io::Result<()>>;
+    fn create_dir_all<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, io::Result<()>>;
+    fn remove_dir_all<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, io::Result<()>>;
+    fn remove_file<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, io::Result<()>>;
+    fn write<'a>(&'a self, path: &'a Path, contents: &'a [u8]) -> BoxFuture<'a, io::Result<()>>;
+    fn copy<'a>(&'a self, from: &'a Path, to: &'a Path) -> BoxFuture<'a, io::Result<u64>>;
+    fn metadata<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, io::Result<Metadata>>;
+}
+
+/// [`Fs`] backed by `tokio::fs`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TokioFs;
+
+impl Fs for TokioFs {
+    fn rename<'a>(&'a self, from: &'a Path, to: &'a Path) -> BoxFuture<'a, io::Result<()>> {
+        Box::pin(tokio::fs::rename(from, to))
+    }
+
+    fn create_dir_all<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, io::Result<()>> {
+        Box::pin(tokio::fs::create_dir_all(path))
+    }
+
+    fn remove_dir_all<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, io::Result<()>> {
+        Box::pin(tokio::fs::remove_dir_all(path))
+    }
+
+    fn remove_file<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, io::Result<()>> {
+        Box::pin(tokio::fs::remove_file(path))
+    }
+
+    fn write<'a>(&'a self, path: &'a Path, contents: &'a [u8]) -> BoxFuture<'a, io::Result<()>> {
+        Box::pin(tokio::fs::write(path, contents))
+    }
+
+    fn copy<'a>(&'a self, from: &'a Path, to: &'a Path) -> BoxFuture<'a, io::Result<u64>> {
+        Box::pin(tokio::fs::copy(from, to))
+    }
+
+    fn metadata<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, io::Result<Metadata>> {
+        Box::pin(tokio::fs::metadata(path))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{
+        path::PathBuf,
+        sync::{Arc, Mutex},
+    };
+
+    use tokio_util::sync::CancellationToken;
+
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub(crate) enum FsOp {
+        Rename,
+        CreateDirAll,
+        RemoveDirAll,
+        RemoveFile,
+        Write,
+        Copy,
+        Metadata,
+    }
+
+    /// Real filesystem that fails chosen operations on chosen paths.
+    ///
+    /// Each registered fault fires once; register it again to fail repeatedly.
+    /// For `rename` and `copy` the destination is matched.
+    #[derive(Default)]
+    pub(crate) struct FaultyFs {
+        faults: Mutex<Vec<(FsOp, PathBuf, io::ErrorKind)>>,
+        cancels: Mutex<Vec<(FsOp, PathBuf, CancellationToken)>>,
+    }
+
+    impl FaultyFs {
+        pub(crate) fn new() -> Arc<Self> {
+            Arc::new(Self::default())
+        }
+
+        pub(crate) fn fail(&self, op: FsOp, path: impl Into<PathBuf>) {
+            self.fail_with(op, path, io::ErrorKind::Other);
+        }
+
+        /// [`Self::fail`] with a specific error kind, e.g. `CrossesDevices`.
+        pub(crate) fn fail_with(&self, op: FsOp, path: impl Into<PathBuf>, kind: io::ErrorKind) {
+            self.faults.lock().unwrap().push((op, path.into(), kind));
+        }
+
+        /// Cancel `token` when `op` next runs on `path` without an injected fault,
+        /// simulating a user cancel landing mid-operation.
+        pub(crate) fn cancel_on(
+            &self,
+            op: FsOp,
+            path: impl Into<PathBuf>,
+            token: CancellationToken,
+        ) {
+            self.cancels.lock().unwrap().push((op, path.into(), token));
+        }
+
+        fn check(&self, op: FsOp, path: &Path) -> io::Result<()> {
+            let mut faults = self.faults.lock().unwrap();
+            if let Some(i) = faults.iter().position(|(o, p, _)| *o == op && p == path) {
+                let (_, _, kind) = faults.remove(i);
+                return Err(io::Error::new(
+                    kind,
+                    format!("injected {op:?} failure: {}", path.display()),
+                ));
+            }
+
+            let mut cancels = self.cancels.lock().unwrap();
+            if let Some(i) = cancels.iter().position(|(o, p, _)| *o == op && p == path) {
+                cancels.remove(i).2.cancel();
+            }
+            Ok(())
+        }
+    }
+
+    impl Fs for FaultyFs {
+        fn rename<'a>(&'a self, from: &'a Path, to: &'a Path) -> BoxFuture<'a, io::Result<()>> {
+            Box::pin(async move {
+                self.check(FsOp::Rename, to)?;
+                TokioFs.rename(from, to).await
+            })
+        }
+
+        fn create_dir_all<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, io::Result<()>> {
+            Box::pin(async move {
+                self.check(FsOp::CreateDirAll, path)?;
+                TokioFs.create_dir_all(path).await
+            })
+        }
+
+        fn remove_dir_all<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, io::Result<()>> {
+            Box::pin(async move {
+                self.check(FsOp::RemoveDirAll, path)?;
+                TokioFs.remove_dir_all(path).await
+            })
+        }
+
+        fn remove_file<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, io::Result<()>> {
+            Box::pin(async move {
+                self.check(FsOp::RemoveFile, path)?;
+                TokioFs.remove_file(path).await
+            })
+        }
+
+        fn write<'a>(
+            &'a self,
+            path: &'a Path,
+            contents: &'a [u8],
+        ) -> BoxFuture<'a, io::Result<()>> {
+            Box::pin(async move {
+                self.check(FsOp::Write, path)?;
+                TokioFs.write(path, contents).await
+            })
+        }
+
+        fn copy<'a>(&'a self, from: &'a Path, to: &'a Path) -> BoxFuture<'a, io::Result<u64>> {
+            Box::pin(async move {
+                self.check(FsOp::Copy, to)?;
+                TokioFs.copy(from, to).await
+            })
+        }
+
+        fn metadata<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, io::Result<Metadata>> {
+            Box::pin(async move {
+                self.check(FsOp::Metadata, path)?;
+                TokioFs.metadata(path).await
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_faulty_fs_only_fails_configured_path() {
+        let temp = tempfile::tempdir().unwrap();
+        let (a, b, c) = (
+            temp.path().join("a"),
+            temp.path().join("b"),
+            temp.path().join("c"),
+        );
+        let fs = FaultyFs::new();
+        fs.fail(FsOp::Rename, &c);
+
+        fs.create_dir_all(&a).await.unwrap();
+        fs.rename(&a, &b).await.unwrap();
+        assert!(fs.rename(&b, &c).await.is_err());
+        assert!(fs.metadata(&b).await.unwrap().is_dir());
+
+        // Faults are one-shot
+        fs.rename(&b, &c).await.unwrap();
+    }
+}
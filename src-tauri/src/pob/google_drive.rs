@@ -1,8 +1,14 @@
-use reqwest::{Response, header};
+use std::{
+    hash::{BuildHasher, Hasher},
+    sync::RwLock,
+    time::Duration,
+};
+
+use reqwest::{RequestBuilder, Response, header};
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
-use crate::pob::error::PobError;
+use crate::pob::{error::PobError, version::sort_newest_first};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
@@ -10,6 +16,38 @@ pub struct GoogleDriveFileInfo {
     pub id: String,
     pub name: String,
     pub is_folder: bool,
+    /// Size in bytes as listed in the folder, rounded to the displayed unit.
+    /// `None` for folders or when the listing doesn't show one.
+    pub size: Option<f64>,
+    /// Last-modified date as displayed in the folder listing
+    pub modified: Option<String>,
+    /// Probed download details, only when requested (see `PobManager::prefetch_metadata`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<FileMetadata>,
+}
+
+#[cfg(test)]
+impl GoogleDriveFileInfo {
+    /// A release file `id` named like the default folder's uploads.
+    pub(crate) fn test_release(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            name: "POE1&2 통합 한글 POB (2024.05.20).zip".to_string(),
+            is_folder: false,
+            size: None,
+            modified: None,
+            metadata: None,
+        }
+    }
+}
+
+/// Download details of a file, probed with a one-byte Range request.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FileMetadata {
+    /// Size in bytes, if the server reported one
+    pub size: Option<f64>,
+    pub accepts_ranges: bool,
 }
 
 /// Information about a file for download planning
@@ -23,21 +61,264 @@ pub struct FileDownloadInfo {
     pub download_url: String,
 }
 
+/// How requests are retried after transient network errors (see [`is_retryable`]).
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Total tries per request, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for each one after
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// Random spread applied to each delay, as a fraction of it (0.0..=1.0)
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(8),
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retry.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Delay before retrying after failed attempt number `attempt` (1-based).
+    pub fn delay(&self, attempt: u32) -> Duration {
+        // `RandomState` is seeded randomly per instance; good enough for jitter
+        let random = std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish();
+        self.delay_with(attempt, random as f64 / u64::MAX as f64 * 2.0 - 1.0)
+    }
+
+    /// [`Self::delay`] with the jitter sample `spread` in `-1.0..=1.0` chosen by the caller.
+    fn delay_with(&self, attempt: u32, spread: f64) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(1u32 << attempt.saturating_sub(1).min(16))
+            .min(self.max_delay);
+        let factor = 1.0 + self.jitter.clamp(0.0, 1.0) * spread.clamp(-1.0, 1.0);
+        exponential.mul_f64(factor)
+    }
+}
+
+/// Timeouts, dropped connections and server-side failures, which may succeed on retry.
+/// Other request errors (a bad URL, a redirect loop) would fail the same way again.
+pub fn is_retryable(err: &reqwest::Error) -> bool {
+    if let Some(status) = err.status() {
+        return status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+    }
+    err.is_timeout() || err.is_connect() || err.is_body()
+}
+
+/// Host serving file downloads, separate from the folder listing pages
+const DOWNLOAD_HOST: &str = "https://drive.usercontent.google.com";
+
+/// Drive API v3 file listing, used instead of the folder page when an API key is set
+const FILES_API_URL: &str = "https://www.googleapis.com/drive/v3/files";
+
+/// Listing pages followed at most, in case the API never stops returning a token
+const MAX_API_PAGES: usize = 50;
+
+/// MIME type the Drive API reports for folders
+const FOLDER_MIME_TYPE: &str = "application/vnd.google-apps.folder";
+
 pub struct GoogleDriveClient {
     inner: reqwest::Client,
+    retry: RetryPolicy,
+    download_host: String,
+    /// Drive API key; folders are listed through the API while set
+    api_key: RwLock<Option<String>>,
 }
 
 impl GoogleDriveClient {
     pub fn new(client: reqwest::Client) -> Self {
-        Self { inner: client }
+        Self {
+            inner: client,
+            retry: RetryPolicy::default(),
+            download_host: DOWNLOAD_HOST.to_string(),
+            api_key: RwLock::new(None),
+        }
+    }
+
+    /// List folders through the Drive API with `api_key`, or by scraping the
+    /// folder page for `None`.
+    pub fn set_api_key(&self, api_key: Option<String>) {
+        *self.api_key.write().unwrap() = api_key;
+    }
+
+    /// Download from `host` instead of Google Drive, e.g. a local fake server.
+    #[cfg(test)]
+    pub(crate) fn with_download_host(mut self, host: String) -> Self {
+        self.download_host = host;
+        self
     }
 
-    pub async fn fetch_folder(
+    fn download_url(&self, file_id: &str) -> String {
+        format!("{}/download?confirm=t&id={}", self.download_host, file_id)
+    }
+
+    /// Replace the default [`RetryPolicy`].
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry
+    }
+
+    /// Send the request built by `build`, retrying transient failures per the
+    /// [`RetryPolicy`]. `build` runs once per attempt.
+    async fn send(
+        &self,
+        what: &str,
+        build: impl Fn() -> RequestBuilder,
+    ) -> Result<Response, PobError> {
+        let mut attempt = 1;
+        loop {
+            match build().send().await.and_then(Response::error_for_status) {
+                Ok(res) => return Ok(res),
+                Err(e) if attempt < self.retry.max_attempts && is_retryable(&e) => {
+                    let delay = self.retry.delay(attempt);
+                    tracing::warn!(
+                        request = what,
+                        attempt,
+                        max_attempts = self.retry.max_attempts,
+                        delay_ms = delay.as_millis() as u64,
+                        error = %e,
+                        "Transient network error, retrying"
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// Send the download request for `file_id` built by `build` from a URL.
+    /// Large files get Drive's virus-scan warning page instead of the file; its
+    /// confirmation form is followed once with the same request.
+    async fn send_download(
+        &self,
+        what: &str,
+        file_id: &str,
+        build: impl Fn(&str) -> RequestBuilder,
+    ) -> Result<Response, PobError> {
+        let url = self.download_url(file_id);
+        let res = self.send(what, || build(&url)).await?;
+        if !is_html(&res) {
+            return Ok(res);
+        }
+
+        let page_url = res.url().clone();
+        let body = res.text().await?;
+        let Some(confirm_url) = html_parser::parse_confirmation_form(&body, &page_url) else {
+            tracing::error!(
+                file_id = %file_id,
+                html_sample = html_sample(&body),
+                "Download returned HTML without a confirmation form - Google Drive UI may have changed"
+            );
+            return Err(PobError::DriveConfirmationRequired);
+        };
+
+        tracing::info!(file_id = %file_id, "Following Google Drive virus-scan confirmation");
+        let res = self.send(what, || build(confirm_url.as_str())).await?;
+        if is_html(&res) {
+            return Err(PobError::DriveConfirmationRequired);
+        }
+        Ok(res)
+    }
+
+    /// Every file in the folder, through the Drive API when an API key is set
+    /// and from the folder page otherwise. API results are followed across pages.
+    pub async fn fetch_all_files(
+        &self,
+        folder_id: &str,
+    ) -> Result<Vec<GoogleDriveFileInfo>, PobError> {
+        let api_key = self.api_key.read().unwrap().clone();
+        match api_key {
+            Some(api_key) => self.fetch_folder_api(folder_id, &api_key).await,
+            None => self.fetch_folder_html(folder_id).await,
+        }
+    }
+
+    /// Files in the folder from the Drive API v3, which unlike the folder page
+    /// doesn't change with Google's UI. Follows `nextPageToken` up to [`MAX_API_PAGES`].
+    pub async fn fetch_folder_api(
+        &self,
+        folder_id: &str,
+        api_key: &str,
+    ) -> Result<Vec<GoogleDriveFileInfo>, PobError> {
+        let query = format!("'{}' in parents and trashed = false", folder_id);
+        let mut files = Vec::new();
+        let mut page_token: Option<String> = None;
+        for page in 1..=MAX_API_PAGES {
+            let res = self
+                .send("folder api", || {
+                    // A header rather than `key=`, so the key stays out of error strings and logs
+                    let request = self
+                        .inner
+                        .get(FILES_API_URL)
+                        .header("X-Goog-Api-Key", api_key)
+                        .query(&[
+                            ("q", query.as_str()),
+                            ("pageSize", "1000"),
+                            (
+                                "fields",
+                                "nextPageToken,files(id,name,mimeType,size,modifiedTime)",
+                            ),
+                        ]);
+                    match &page_token {
+                        Some(token) => request.query(&[("pageToken", token.as_str())]),
+                        None => request,
+                    }
+                })
+                .await?;
+
+            let list: ApiFileList = res.json().await?;
+            page_token = list.next_page_token.clone();
+            files.extend(list.into_files());
+            if page_token.is_none() {
+                break;
+            }
+            if page == MAX_API_PAGES {
+                tracing::warn!(
+                    folder_id = %folder_id,
+                    pages = page,
+                    files = files.len(),
+                    "Folder listing still has more pages, stopping"
+                );
+            }
+        }
+
+        if files.is_empty() {
+            return Err(PobError::NotFoundFromDrive(
+                "Google Drive 폴더에서 파일을 찾을 수 없습니다".to_string(),
+            ));
+        }
+        Ok(files)
+    }
+
+    /// Files in the folder scraped from its HTML page.
+    pub async fn fetch_folder_html(
         &self,
         folder_id: &str,
     ) -> Result<Vec<GoogleDriveFileInfo>, PobError> {
         let url = format!("https://drive.google.com/drive/folders/{}", folder_id);
-        let res = self.inner.get(url).send().await?.error_for_status()?;
+        let res = self.send("folder", || self.inner.get(&url)).await?;
 
         let body = res.text().await?;
 
@@ -46,7 +327,7 @@ impl GoogleDriveClient {
         if files.is_empty() {
             tracing::error!(
                 folder_id = %folder_id,
-                html_sample = &body[..body.len().min(500)],
+                html_sample = html_sample(&body),
                 "No files found in Google Drive folder or failed to parse HTML - Google Drive UI may have changed"
             );
 
@@ -61,22 +342,13 @@ impl GoogleDriveClient {
         &self,
         folder_id: &str,
     ) -> Result<Option<GoogleDriveFileInfo>, PobError> {
-        let mut files = self.fetch_folder(folder_id).await?;
-        files.retain(|f| !f.is_folder);
-
-        files.sort_by(|a, b| b.name.cmp(&a.name));
-
-        Ok(files.into_iter().next())
+        let files = self.fetch_all_files(folder_id).await?;
+        Ok(latest_release(files))
     }
 
     pub async fn get_file(&self, file_id: &str) -> Result<Response, PobError> {
-        let url = format!(
-            "https://drive.usercontent.google.com/download?confirm=t&id={}",
-            file_id
-        );
-        let res = self.inner.get(url).send().await?.error_for_status()?;
-
-        Ok(res)
+        self.send_download("file", file_id, |url| self.inner.get(url))
+            .await
     }
 
     /// Get file download info (size, Range support) via HEAD request
@@ -85,20 +357,13 @@ impl GoogleDriveClient {
         &self,
         file_id: &str,
     ) -> Result<FileDownloadInfo, PobError> {
-        let url = format!(
-            "https://drive.usercontent.google.com/download?confirm=t&id={}",
-            file_id
-        );
-
         // First do a GET with Range header to check if Range is supported
         // HEAD requests don't always work with Google Drive
         let res = self
-            .inner
-            .get(&url)
-            .header(header::RANGE, "bytes=0-0")
-            .send()
-            .await?
-            .error_for_status()?;
+            .send_download("file info", file_id, |url| {
+                self.inner.get(url).header(header::RANGE, "bytes=0-0")
+            })
+            .await?;
 
         let status = res.status();
         let headers = res.headers().clone();
@@ -114,13 +379,7 @@ impl GoogleDriveClient {
 
         // Get content length from Content-Range header (for 206) or Content-Length
         let content_length = if status == reqwest::StatusCode::PARTIAL_CONTENT {
-            // Content-Range: bytes 0-0/12345678
-            headers
-                .get(header::CONTENT_RANGE)
-                .and_then(|v| v.to_str().ok())
-                .and_then(|v| v.split('/').next_back())
-                .and_then(|v| v.parse::<u64>().ok())
-                .unwrap_or(0)
+            content_range_total(&headers).unwrap_or(0)
         } else {
             headers
                 .get(header::CONTENT_LENGTH)
@@ -144,6 +403,21 @@ impl GoogleDriveClient {
         })
     }
 
+    /// Download from byte `start` to the end of the file, for resuming.
+    /// Servers without Range support answer `200` with the whole file instead of `206`.
+    pub async fn get_file_range_open(
+        &self,
+        file_id: &str,
+        start: u64,
+    ) -> Result<Response, PobError> {
+        self.send_download("file range", file_id, |url| {
+            self.inner
+                .get(url)
+                .header(header::RANGE, format!("bytes={}-", start))
+        })
+        .await
+    }
+
     /// Download a specific byte range of a file
     pub async fn get_file_range(
         &self,
@@ -151,27 +425,107 @@ impl GoogleDriveClient {
         start: u64,
         end: u64,
     ) -> Result<Response, PobError> {
-        let url = format!(
-            "https://drive.usercontent.google.com/download?confirm=t&id={}",
-            file_id
-        );
-
         let range_header = format!("bytes={}-{}", start, end);
-        let res = self
-            .inner
-            .get(url)
-            .header(header::RANGE, range_header)
-            .send()
-            .await?
-            .error_for_status()?;
+        self.send_download("file range", file_id, |url| {
+            self.inner.get(url).header(header::RANGE, &range_header)
+        })
+        .await
+    }
+}
 
-        Ok(res)
+/// Response of the Drive API v3 `files.list`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiFileList {
+    #[serde(default)]
+    files: Vec<ApiFile>,
+    /// Present while more files follow on another page
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiFile {
+    id: String,
+    name: String,
+    mime_type: String,
+    /// Bytes as a decimal string, absent for folders
+    size: Option<String>,
+    /// RFC 3339 timestamp
+    modified_time: Option<String>,
+}
+
+impl ApiFileList {
+    fn into_files(self) -> Vec<GoogleDriveFileInfo> {
+        self.files
+            .into_iter()
+            .map(ApiFile::into_file_info)
+            .collect()
+    }
+}
+
+impl ApiFile {
+    fn into_file_info(self) -> GoogleDriveFileInfo {
+        let is_folder = self.mime_type == FOLDER_MIME_TYPE;
+        // Displayed like the folder page's column, e.g. `Jun 11, 2024`
+        let modified = self
+            .modified_time
+            .and_then(|time| chrono::DateTime::parse_from_rfc3339(&time).ok())
+            .map(|time| time.format("%b %-d, %Y").to_string());
+        GoogleDriveFileInfo {
+            id: self.id,
+            name: self.name,
+            is_folder,
+            size: self
+                .size
+                .filter(|_| !is_folder)
+                .and_then(|size| size.parse::<u64>().ok())
+                .map(|bytes| bytes as f64),
+            modified,
+            metadata: None,
+        }
+    }
+}
+
+/// Newest `.zip` file by the date parsed from its name. Names without a
+/// date only win when no file has one.
+pub fn latest_release(mut files: Vec<GoogleDriveFileInfo>) -> Option<GoogleDriveFileInfo> {
+    files.retain(|f| !f.is_folder && f.name.to_ascii_lowercase().ends_with(".zip"));
+    sort_newest_first(&mut files);
+    files.into_iter().next()
+}
+
+/// Whether `res` is an HTML page rather than file content.
+fn is_html(res: &Response) -> bool {
+    res.headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.trim_start().to_ascii_lowercase().starts_with("text/html"))
+}
+
+/// First 500 characters of an unexpected HTML page, for logging. Cut on a
+/// character boundary, as Drive pages are often Korean.
+fn html_sample(body: &str) -> &str {
+    match body.char_indices().nth(500) {
+        Some((end, _)) => &body[..end],
+        None => body,
     }
 }
 
+/// Full file size from a `206` response's `Content-Range: bytes 0-0/12345678`.
+pub fn content_range_total(headers: &header::HeaderMap) -> Option<u64> {
+    headers
+        .get(header::CONTENT_RANGE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split('/').next_back())
+        .and_then(|v| v.parse::<u64>().ok())
+}
+
 mod html_parser {
     use std::sync::LazyLock;
 
+    use regex::Regex;
+    use reqwest::Url;
     use scraper::Selector;
 
     use crate::pob::google_drive::GoogleDriveFileInfo;
@@ -181,6 +535,12 @@ mod html_parser {
     static NAME_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("strong").unwrap());
     static SIZE_SELECTOR: LazyLock<Selector> =
         LazyLock::new(|| Selector::parse("td[data-column-field=\"3\"] [aria-label]").unwrap());
+    static MODIFIED_SELECTOR: LazyLock<Selector> =
+        LazyLock::new(|| Selector::parse("td[data-column-field=\"2\"]").unwrap());
+    static FORM_SELECTOR: LazyLock<Selector> =
+        LazyLock::new(|| Selector::parse("form#download-form").unwrap());
+    static HIDDEN_INPUT_SELECTOR: LazyLock<Selector> =
+        LazyLock::new(|| Selector::parse("input[type=\"hidden\"][name]").unwrap());
 
     pub fn parse_google_drive_folder_html(html: &str) -> Vec<GoogleDriveFileInfo> {
         scraper::Html::parse_document(html)
@@ -194,16 +554,265 @@ mod html_parser {
 
         let name = row.select(&NAME_SELECTOR).next()?.text().next()?;
 
-        let is_folder = row
+        let size_label = row
             .select(&SIZE_SELECTOR)
             .next()
-            .and_then(|e| e.attr("aria-label"))
-            .map(|label| label.contains("not available"))?;
+            .and_then(|e| e.attr("aria-label"))?;
+        let is_folder = size_label.contains("not available");
+        let size = if is_folder {
+            None
+        } else {
+            parse_size_label(size_label).map(|bytes| bytes as f64)
+        };
+
+        let modified = row
+            .select(&MODIFIED_SELECTOR)
+            .next()
+            .map(|e| e.text().collect::<String>().trim().to_string())
+            .filter(|text| !text.is_empty());
 
         Some(GoogleDriveFileInfo {
             id: id.to_string(),
             name: name.to_string(),
             is_folder,
+            size,
+            modified,
+            metadata: None,
         })
     }
+
+    /// URL the virus-scan warning page's "Download anyway" form submits to,
+    /// resolved against `page_url`. `None` unless it carries a `confirm` or `uuid` token.
+    pub fn parse_confirmation_form(html: &str, page_url: &Url) -> Option<Url> {
+        let document = scraper::Html::parse_document(html);
+        let form = document.select(&FORM_SELECTOR).next()?;
+        let mut url = page_url.join(form.attr("action").unwrap_or("")).ok()?;
+
+        let fields: Vec<(&str, &str)> = form
+            .select(&HIDDEN_INPUT_SELECTOR)
+            .filter_map(|input| Some((input.attr("name")?, input.attr("value").unwrap_or(""))))
+            .collect();
+        if !fields
+            .iter()
+            .any(|(name, _)| matches!(*name, "confirm" | "uuid"))
+        {
+            return None;
+        }
+
+        url.query_pairs_mut().clear().extend_pairs(fields);
+        Some(url)
+    }
+
+    /// Bytes in a size label like `123 MB` or `1.5 GB` (binary units, as Drive uses).
+    pub(super) fn parse_size_label(label: &str) -> Option<u64> {
+        static RE: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"(?i)(\d+(?:[.,]\d+)?)\s*(bytes?|B|KB|MB|GB|TB)\b").unwrap()
+        });
+
+        let caps = RE.captures(label)?;
+        let value: f64 = caps[1].replace(',', ".").parse().ok()?;
+        let exponent = match caps[2].to_ascii_uppercase().as_str() {
+            "KB" => 1,
+            "MB" => 2,
+            "GB" => 3,
+            "TB" => 4,
+            _ => 0,
+        };
+        Some((value * 1024f64.powi(exponent)).round() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Url;
+
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_backs_off_and_caps() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(4),
+            jitter: 0.5,
+        };
+        assert_eq!(policy.delay_with(1, 0.0), Duration::from_millis(500));
+        assert_eq!(policy.delay_with(2, 0.0), Duration::from_secs(1));
+        assert_eq!(policy.delay_with(4, 0.0), Duration::from_secs(4));
+        assert_eq!(policy.delay_with(30, 0.0), Duration::from_secs(4));
+
+        assert_eq!(policy.delay_with(2, 1.0), Duration::from_millis(1500));
+        assert_eq!(policy.delay_with(2, -1.0), Duration::from_millis(500));
+        let random = policy.delay(2);
+        assert!(random >= Duration::from_millis(500) && random <= Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn test_latest_release_picks_newest_date() {
+        let file = |name: &str, is_folder| GoogleDriveFileInfo {
+            name: name.to_string(),
+            is_folder,
+            ..GoogleDriveFileInfo::test_release(name)
+        };
+        let files = vec![
+            file("POE1&2 통합 한글 POB (2024.05.20).zip", false),
+            file("readme.txt", false),
+            file("POE1&2 통합 한글 POB  (2024.10.02) (v2).zip", false),
+            file("POE1&2 통합 한글 POB (2025.01.01)", true),
+            file("POE1&2 통합 한글 POB(2024.09.30).zip", false),
+            file("zz-notes.txt", false),
+            // A dated changelog newer than every release
+            file("패치노트 2024.12.01.txt", false),
+        ];
+        // Lexically `zz-notes.txt` and the `POB(` spacing would win
+        assert_eq!(
+            latest_release(files).unwrap().name,
+            "POE1&2 통합 한글 POB  (2024.10.02) (v2).zip"
+        );
+
+        let undated = vec![
+            file("readme.txt", false),
+            file("release.zip", false),
+            file("old", true),
+        ];
+        assert_eq!(latest_release(undated).unwrap().name, "release.zip");
+        assert!(latest_release(vec![file("old", true), file("readme.txt", false)]).is_none());
+    }
+
+    #[test]
+    fn test_parse_size_label() {
+        use html_parser::parse_size_label;
+
+        assert_eq!(parse_size_label("123 MB"), Some(123 * 1024 * 1024));
+        assert_eq!(parse_size_label("1.5 GB"), Some(1536 * 1024 * 1024));
+        assert_eq!(parse_size_label("Size: 2 KB"), Some(2048));
+        assert_eq!(parse_size_label("512 bytes"), Some(512));
+        assert_eq!(parse_size_label("not available"), None);
+    }
+
+    #[test]
+    fn test_parse_folder_html_size_and_modified() {
+        let html = r#"<table><tbody>
+            <tr data-id="zip-id">
+                <td><strong>POE1&amp;2 통합 한글 POB (2024.06.11).zip</strong></td>
+                <td data-column-field="2"> Jun 11, 2024 </td>
+                <td data-column-field="3"><span aria-label="123 MB">123 MB</span></td>
+            </tr>
+            <tr data-id="folder-id">
+                <td><strong>old</strong></td>
+                <td data-column-field="2"></td>
+                <td data-column-field="3"><span aria-label="Size not available">—</span></td>
+            </tr>
+        </tbody></table>"#;
+
+        let files = html_parser::parse_google_drive_folder_html(html);
+        assert_eq!(files.len(), 2);
+
+        assert_eq!(files[0].id, "zip-id");
+        assert!(!files[0].is_folder);
+        assert_eq!(files[0].size, Some(123.0 * 1024.0 * 1024.0));
+        assert_eq!(files[0].modified.as_deref(), Some("Jun 11, 2024"));
+
+        assert!(files[1].is_folder);
+        assert_eq!(files[1].size, None);
+        assert_eq!(files[1].modified, None);
+    }
+
+    #[test]
+    fn test_html_sample_cuts_on_char_boundary() {
+        let body = "가".repeat(600);
+        assert_eq!(html_sample(&body).chars().count(), 500);
+        assert_eq!(html_sample("<html>"), "<html>");
+    }
+
+    #[test]
+    fn test_api_file_list_into_files() {
+        let json = r#"{
+            "files": [
+                {
+                    "id": "zip-id",
+                    "name": "POE1&2 통합 한글 POB (2024.06.11).zip",
+                    "mimeType": "application/x-zip-compressed",
+                    "size": "128974848",
+                    "modifiedTime": "2024-06-11T09:12:45.000Z"
+                },
+                {
+                    "id": "folder-id",
+                    "name": "old",
+                    "mimeType": "application/vnd.google-apps.folder",
+                    "modifiedTime": "2023-01-02T00:00:00.000Z"
+                }
+            ]
+        }"#;
+
+        let files = serde_json::from_str::<ApiFileList>(json)
+            .unwrap()
+            .into_files();
+        assert_eq!(files.len(), 2);
+
+        assert_eq!(files[0].id, "zip-id");
+        assert!(!files[0].is_folder);
+        assert_eq!(files[0].size, Some(128974848.0));
+        assert_eq!(files[0].modified.as_deref(), Some("Jun 11, 2024"));
+
+        assert!(files[1].is_folder);
+        assert_eq!(files[1].size, None);
+
+        // An empty folder omits `files`
+        assert!(
+            serde_json::from_str::<ApiFileList>("{}")
+                .unwrap()
+                .into_files()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_api_file_list_next_page_token() {
+        let list: ApiFileList = serde_json::from_str(
+            r#"{"nextPageToken": "~!!~AI9FV7T", "files": [{"id": "a", "name": "a.zip", "mimeType": "application/zip"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(list.next_page_token.as_deref(), Some("~!!~AI9FV7T"));
+        assert_eq!(list.into_files().len(), 1);
+
+        let last: ApiFileList = serde_json::from_str(r#"{"files": []}"#).unwrap();
+        assert!(last.next_page_token.is_none());
+    }
+
+    #[test]
+    fn test_parse_confirmation_form() {
+        // Trimmed from Drive's "can't scan this file for viruses" page
+        let html = r#"<!DOCTYPE html><html><head><title>Google Drive - Virus scan warning</title></head>
+            <body><div class="uc-main"><div id="uc-text">
+            <p class="uc-warning-caption">Google Drive can't scan this file for viruses.</p>
+            <p class="uc-warning-subcaption"><span class="uc-name-size"><a href="/open?id=drive_id">POB.zip</a> (245M)</span>
+            is too large for Google to scan for viruses. Would you still like to download this file?</p>
+            <form id="download-form" action="https://drive.usercontent.google.com/download" method="get">
+                <input type="submit" id="uc-download-link" class="goog-inline-block jfk-button jfk-button-action" value="Download anyway"/>
+                <input type="hidden" name="id" value="drive_id">
+                <input type="hidden" name="export" value="download">
+                <input type="hidden" name="confirm" value="t">
+                <input type="hidden" name="uuid" value="0c4a3b1e-5f2d-4e8a-9b7c-1d2e3f4a5b6c">
+            </form></div></div></body></html>"#;
+        let page_url =
+            Url::parse("https://drive.usercontent.google.com/download?confirm=t&id=drive_id")
+                .unwrap();
+
+        let url = html_parser::parse_confirmation_form(html, &page_url).unwrap();
+        assert_eq!(url.path(), "/download");
+        let query: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+        assert!(query.contains(&("id".into(), "drive_id".into())));
+        assert!(query.contains(&("confirm".into(), "t".into())));
+        assert!(query.contains(&("uuid".into(), "0c4a3b1e-5f2d-4e8a-9b7c-1d2e3f4a5b6c".into())));
+
+        // Any other HTML, e.g. a quota error page
+        assert!(
+            html_parser::parse_confirmation_form(
+                "<html><body>Quota exceeded</body></html>",
+                &page_url
+            )
+            .is_none()
+        );
+    }
 }
@@ -2,18 +2,52 @@ use std::{
     collections::HashMap,
     num::NonZeroU32,
     path::{Path, PathBuf},
+    sync::{
+        Arc, RwLock as StdRwLock,
+        atomic::{AtomicBool, Ordering},
+    },
     time::{Duration, Instant},
 };
 
-use tokio::{fs, sync::Mutex, sync::RwLock};
+use tokio::sync::{Mutex, OwnedSemaphorePermit, RwLock, Semaphore};
 use tokio_util::sync::CancellationToken;
 
 use crate::{
     pob::{
+        DryRunReport, UpdateDownloadEstimate,
+        backup_manifest::{BackupManifest, DEFAULT_BACKUP_TARGETS, backup_manifest_path},
+        config::{
+            BackupFailurePolicy, DangerousPathPolicy, PobConfig, config_file_path,
+            portable_log_dir, portable_temp_dir, save_data_dir_redirect,
+        },
+        diagnostics::{DiagnosticFile, logs_newest_first, write_bundle},
         error::PobError,
-        google_drive::{GoogleDriveClient, GoogleDriveFileInfo},
-        progress::{InstallPhase, InstallReporter, InstallStatus},
-        version::PobVersion,
+        fs::{Fs, TokioFs},
+        google_drive::{
+            FileMetadata, GoogleDriveClient, GoogleDriveFileInfo, content_range_total, is_retryable,
+        },
+        progress::{
+            InstallPhase, InstallProgress, InstallReporter, InstallStatus, PhaseWeights,
+            SpeedTracker, ThroughputWindow, read_event_log,
+        },
+        reclaim::{ReclaimReport, reclaim, surplus_logs, task_temp_dirs},
+        recovery::{InstallState, RecoveryAction, install_state_path},
+        snapshot::{
+            BackupEntry, BackupSnapshot, STAMP_FORMAT, backup_entry, backups_newest_first,
+            next_backup_name, normalize_label, snapshots_newest_first, staged_backups,
+            unique_snapshot_dir,
+        },
+        stats::{InstallStats, PhaseClock, stats_file_path},
+        verify::{
+            ArchiveEntry, ArchiveReport, BackupCheck, EOCD_SEARCH_BYTES, InstallDiff,
+            InstallManifest, IntegrityReport, REQUIRED_DIRS, check_backup, diff_entries,
+            diff_install, find_central_directory, inspect_archive, parse_central_directory,
+            verify_manifest,
+        },
+        version::{
+            PobVersion, UpdateState, UpdateStatus, days_between, parse_from_name, set_name_pattern,
+            validate_version, version_date, versions_in_range,
+        },
     },
     util::{async_copy_dir_recursive, datetime_to_systemtime},
 };
@@ -21,11 +55,66 @@ use crate::{
 /// Progress report throttling interval in milliseconds
 const PROGRESS_REPORT_INTERVAL_MS: u64 = 100;
 
+/// Release notes some uploads ship at the archive root, in lookup order
+const CHANGELOG_FILES: &[&str] = &["changelog.txt", "변경사항.txt"];
+
+/// Read size when writing out an archive entry, between progress updates
+const EXTRACT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Extractions smaller than this don't update the throughput measurement
+const MIN_MEASURED_EXTRACT_BYTES: u64 = 16 * 1024 * 1024;
+
+/// How many archives `keep_download_on_failure` retains in `failed/`
+const KEPT_FAILED_DOWNLOADS: usize = 3;
+
+/// Marker in a retained previous install whose user data was never backed up
+/// (`on_backup_failure = Continue`); such an install is never pruned or replaced
+const UNBACKED_MARKER: &str = ".exile_unbacked";
+
+/// Google Drive folder holding the official releases
+pub const DEFAULT_FOLDER_ID: &str = "1_5YhTy59gkyJpWqPuKA_z1cnobQcS8gi";
+
 pub struct PobManager {
     client: GoogleDriveClient,
-    data_dir: PathBuf,
+    /// Filesystem used by the async install/backup/rollback steps.
+    fs: Arc<dyn Fs>,
+    /// Root of everything the manager owns. Only changes via [`Self::relocate_data_dir`].
+    data_dir: StdRwLock<PathBuf>,
+    /// Temp files and logs also live under `data_dir` (see [`Self::new_portable`]).
+    portable: bool,
+
+    /// Advisory cache of the latest release per folder. Reads clone out under the
+    /// lock, and installs take their `file_info` by value, so a refresh mid-install
+    /// never changes what that install downloads. Entries older than
+    /// `latest_cache_ttl_secs` are fetched again. Cleared after a successful install.
+    cached_result: Mutex<HashMap<String, (Instant, GoogleDriveFileInfo)>>,
+    /// Google Drive folder releases are looked up in. Changed via [`Self::set_folder_id`].
+    folder_id: StdRwLock<String>,
 
-    cached_result: Mutex<HashMap<String, GoogleDriveFileInfo>>,
+    /// Persisted user settings, loaded once at construction.
+    config: StdRwLock<PobConfig>,
+    /// Held while settings are saved and applied, so a save never writes back
+    /// a snapshot older than one saved meanwhile.
+    config_save: Mutex<()>,
+
+    /// Measurements from previous installs, used for time estimates.
+    stats: StdRwLock<InstallStats>,
+
+    /// Global cap on downloads in flight, shared by every download path.
+    /// Replaced (not resized) when the setting changes; outstanding permits
+    /// keep the old semaphore alive until they drop.
+    download_slots: StdRwLock<Arc<Semaphore>>,
+
+    /// Global cap on file handles and connections, shared by downloads,
+    /// extraction and range fetches so together they can't exhaust OS limits.
+    /// Replaced like `download_slots` when the setting changes.
+    handle_slots: StdRwLock<Arc<Semaphore>>,
+
+    /// Set by [`Self::quiesce`]; new downloads and folder fetches fail until resumed.
+    quiesced: AtomicBool,
+
+    /// What startup recovery found, until the frontend takes it.
+    pending_recovery: StdRwLock<Option<RecoveryAction>>,
 
     /// Lock for mutating operations (install, uninstall).
     /// Write lock = exclusive access for install/uninstall.
@@ -33,105 +122,640 @@ pub struct PobManager {
 }
 
 impl PobManager {
-    pub fn new(client: GoogleDriveClient, data_dir: PathBuf) -> Self {
+    pub fn new(client: GoogleDriveClient, data_dir: PathBuf, folder_id: String) -> Self {
+        let config = PobConfig::load(&config_file_path(&data_dir));
+        if let Err(e) = set_name_pattern(config.archive_name_pattern.as_deref()) {
+            tracing::warn!(
+                operation = "config",
+                error = %e,
+                "Invalid archive name pattern, using the default naming"
+            );
+        }
+        client.set_api_key(config.drive_api_key.clone());
+        let stats = InstallStats::load(&stats_file_path(&data_dir));
+        let download_slots = Semaphore::new(config.download.max_concurrent_downloads as usize);
+        let handle_slots = Semaphore::new(config.max_open_handles as usize);
         Self {
             client,
-            data_dir,
+            fs: Arc::new(TokioFs),
+            data_dir: StdRwLock::new(data_dir),
+            portable: false,
             cached_result: Mutex::new(HashMap::new()),
+            folder_id: StdRwLock::new(folder_id),
+            config: StdRwLock::new(config),
+            config_save: Mutex::new(()),
+            stats: StdRwLock::new(stats),
+            download_slots: StdRwLock::new(Arc::new(download_slots)),
+            handle_slots: StdRwLock::new(Arc::new(handle_slots)),
+            quiesced: AtomicBool::new(false),
+            pending_recovery: StdRwLock::new(None),
             operation_lock: RwLock::new(()),
         }
     }
 
+    /// Manager for a portable install rooted at `root` (see [`portable_root`]).
+    /// Nothing is written outside it, and the data dir can't be relocated.
+    ///
+    /// [`portable_root`]: crate::pob::config::portable_root
+    pub fn new_portable(client: GoogleDriveClient, root: PathBuf, folder_id: String) -> Self {
+        Self {
+            portable: true,
+            ..Self::new(client, root, folder_id)
+        }
+    }
+
+    /// Swap the filesystem implementation, e.g. to inject failures in tests.
+    #[cfg(test)]
+    pub(crate) fn with_fs(mut self, fs: Arc<dyn Fs>) -> Self {
+        self.fs = fs;
+        self
+    }
+
     /// Try to acquire exclusive lock for install/uninstall.
     /// Returns guard on success, None if another operation in progress.
     pub fn try_write_lock(&self) -> Option<tokio::sync::RwLockWriteGuard<'_, ()>> {
         self.operation_lock.try_write().ok()
     }
 
+    /// Try to acquire shared lock for read-only operations (e.g. launching PoB).
+    /// Returns None while an install/uninstall holds the write lock.
+    pub fn try_read_lock(&self) -> Option<tokio::sync::RwLockReadGuard<'_, ()>> {
+        self.operation_lock.try_read().ok()
+    }
+
+    /// Snapshot of the current settings.
+    pub fn config(&self) -> PobConfig {
+        self.config.read().unwrap().clone()
+    }
+
+    /// Validate and persist new settings, applying them to subsequent operations.
+    pub async fn set_config(&self, mut config: PobConfig) -> Result<(), PobError> {
+        let _save = self.config_save.lock().await;
+        // Files have to move with it, so only `set_install_root` changes it
+        config.install_root = self.config().install_root;
+        config.validate()?;
+        config.save(&config_file_path(&self.data_dir())).await?;
+        set_name_pattern(config.archive_name_pattern.as_deref())?;
+        self.client.set_api_key(config.drive_api_key.clone());
+
+        let max_downloads = config.download.max_concurrent_downloads;
+        let max_handles = config.max_open_handles;
+        let previous = std::mem::replace(&mut *self.config.write().unwrap(), config);
+        if previous.download.max_concurrent_downloads != max_downloads {
+            *self.download_slots.write().unwrap() =
+                Arc::new(Semaphore::new(max_downloads as usize));
+        }
+        if previous.max_open_handles != max_handles {
+            *self.handle_slots.write().unwrap() = Arc::new(Semaphore::new(max_handles as usize));
+        }
+        Ok(())
+    }
+
+    /// Refuse new network activity until [`Self::resume_activity`].
+    /// Operations already past their network phase are unaffected.
+    pub fn quiesce(&self) {
+        self.quiesced.store(true, Ordering::SeqCst);
+        tracing::info!(operation = "quiesce", "Network activity paused");
+    }
+
+    pub fn resume_activity(&self) {
+        self.quiesced.store(false, Ordering::SeqCst);
+        tracing::info!(operation = "quiesce", "Network activity resumed");
+    }
+
+    pub fn is_quiesced(&self) -> bool {
+        self.quiesced.load(Ordering::SeqCst)
+    }
+
+    fn ensure_active(&self) -> Result<(), PobError> {
+        if self.is_quiesced() {
+            return Err(PobError::Quiesced);
+        }
+        Ok(())
+    }
+
+    /// Wait for a global download slot, giving up if `cancel_token` fires first.
+    pub(crate) async fn acquire_download_slot(
+        &self,
+        cancel_token: &CancellationToken,
+    ) -> Result<OwnedSemaphorePermit, PobError> {
+        self.ensure_active()?;
+        let slots = self.download_slots.read().unwrap().clone();
+        tokio::select! {
+            _ = cancel_token.cancelled() => Err(PobError::Cancelled),
+            permit = slots.acquire_owned() => {
+                Ok(permit.expect("download semaphore is never closed"))
+            }
+        }
+    }
+
+    /// Wait until `count` handles fit under `max_open_handles`, giving up if
+    /// `cancel_token` fires first. Fails outright if `count` exceeds the cap itself.
+    pub(crate) async fn acquire_handles(
+        &self,
+        count: u32,
+        cancel_token: &CancellationToken,
+    ) -> Result<OwnedSemaphorePermit, PobError> {
+        let cap = self.config().max_open_handles;
+        if count > cap {
+            return Err(PobError::ResourceLimit(format!(
+                "작업에 파일 핸들 {count}개가 필요하지만 설정된 최대치는 {cap}개입니다"
+            )));
+        }
+        let slots = self.handle_slots.read().unwrap().clone();
+        tokio::select! {
+            _ = cancel_token.cancelled() => Err(PobError::Cancelled),
+            permit = slots.acquire_many_owned(count) => {
+                Ok(permit.expect("handle semaphore is never closed"))
+            }
+        }
+    }
+
+    pub fn data_dir(&self) -> PathBuf {
+        self.data_dir.read().unwrap().clone()
+    }
+
+    pub fn is_portable(&self) -> bool {
+        self.portable
+    }
+
+    /// Temp folder to use instead of the system one, in portable mode.
+    pub fn portable_temp_dir(&self) -> Option<PathBuf> {
+        self.portable.then(|| portable_temp_dir(&self.data_dir()))
+    }
+
+    /// Log folder to use instead of the platform one, in portable mode.
+    pub fn portable_log_dir(&self) -> Option<PathBuf> {
+        self.portable.then(|| portable_log_dir(&self.data_dir()))
+    }
+
+    /// Folder holding the install, its swap siblings and the backup.
+    pub fn install_root(&self) -> PathBuf {
+        self.config()
+            .install_root
+            .map(PathBuf::from)
+            .unwrap_or_else(|| self.data_dir())
+    }
+
     pub fn install_path(&self) -> PathBuf {
-        self.data_dir.join("PoeCharm")
+        self.install_root().join("PoeCharm")
     }
 
     pub fn version_file_path(&self) -> PathBuf {
         self.install_path().join("pob_version.json")
     }
 
+    /// Timestamped install backups, one directory each (see [`Self::list_backups`]).
     pub fn backup_dir(&self) -> PathBuf {
-        self.data_dir.join("backup")
+        self.install_root().join("backup")
+    }
+
+    /// Manual snapshots from [`Self::create_manual_backup`], one directory each.
+    pub fn snapshots_dir(&self) -> PathBuf {
+        self.data_dir().join("snapshots")
+    }
+
+    /// Install replaced by the last successful update, kept for [`Self::revert_to_previous`].
+    pub fn previous_install_path(&self) -> PathBuf {
+        self.install_path().with_extension("previous")
+    }
+
+    /// Archives of failed installs, kept when `keep_download_on_failure` is set.
+    pub fn failed_downloads_dir(&self) -> PathBuf {
+        self.data_dir().join("failed")
+    }
+
+    /// Interrupted downloads kept for resuming; holds at most one file.
+    pub fn partial_downloads_dir(&self) -> PathBuf {
+        self.data_dir().join("partial")
+    }
+
+    /// Interrupted download of `file_id`, resumed by the next attempt.
+    pub fn partial_download_path(&self, file_id: &str) -> PathBuf {
+        self.partial_downloads_dir()
+            .join(file_id)
+            .with_extension("part")
+    }
+
+    /// Where [`Self::install`] downloads the archive `file_name` inside its temp dir.
+    pub fn download_path(temp_dir: &Path, file_name: &str) -> PathBuf {
+        temp_dir.join(file_name).with_extension("part")
     }
 
     pub fn exe_path(&self) -> PathBuf {
         self.install_path().join("PoeCharm3.exe")
     }
 
+    /// Event log of the most recent install/uninstall (see [`RecordingProgressSink`]).
+    ///
+    /// [`RecordingProgressSink`]: crate::pob::progress::RecordingProgressSink
+    pub fn event_log_path(&self) -> PathBuf {
+        self.data_dir().join("last_install.jsonl")
+    }
+
     pub fn pob_version_file_path(&self) -> PathBuf {
         self.install_path().join("pob_version.json")
     }
 
+    /// Releases whose version date falls within `from..=to` (`YYYY.MM.DD`,
+    /// inclusive, either end optional), newest first. Always fetches the folder.
+    pub async fn list_versions(
+        &self,
+        from: Option<&str>,
+        to: Option<&str>,
+    ) -> Result<Vec<GoogleDriveFileInfo>, PobError> {
+        let from = from.map(version_date).transpose()?;
+        let to = to.map(version_date).transpose()?;
+
+        self.ensure_active()?;
+        let files = self.client.fetch_all_files(&self.folder_id()).await?;
+        Ok(versions_in_range(files, from, to))
+    }
+
+    /// Probe the size and Range support of every file in `files`, a few at a time
+    /// per `metadata_prefetch_concurrency`. A failed probe leaves that file's
+    /// `metadata` empty instead of failing the list.
+    pub async fn prefetch_metadata(
+        &self,
+        files: Vec<GoogleDriveFileInfo>,
+    ) -> Result<Vec<GoogleDriveFileInfo>, PobError> {
+        self.ensure_active()?;
+        let concurrency = self.config().download.metadata_prefetch_concurrency;
+        let probes = Semaphore::new(concurrency as usize);
+        // Probes are never cancelled; the token only satisfies `acquire_handles`
+        let never = CancellationToken::new();
+
+        let probe = |mut file: GoogleDriveFileInfo| {
+            let probes = &probes;
+            let never = &never;
+            async move {
+                if file.is_folder {
+                    return file;
+                }
+                let _probe = probes
+                    .acquire()
+                    .await
+                    .expect("probe semaphore is never closed");
+                let info = match self.acquire_handles(1, never).await {
+                    Ok(_connection) => self.client.get_file_download_info(&file.id).await,
+                    Err(e) => Err(e),
+                };
+                match info {
+                    Ok(info) => {
+                        file.metadata = Some(FileMetadata {
+                            size: (info.content_length > 0).then_some(info.content_length as f64),
+                            accepts_ranges: info.accepts_ranges,
+                        });
+                    }
+                    Err(e) => {
+                        tracing::warn!(operation = "prefetch", file_id = %file.id, error = %e, "Failed to probe file metadata");
+                    }
+                }
+                file
+            }
+        };
+        Ok(futures_util::future::join_all(files.into_iter().map(probe)).await)
+    }
+
+    pub fn folder_id(&self) -> String {
+        self.folder_id.read().unwrap().clone()
+    }
+
+    /// Look up releases in `folder_id` from now on, e.g. a test folder or another
+    /// channel. Cached results of the previous folder are dropped.
+    pub async fn set_folder_id(&self, folder_id: String) -> Result<(), PobError> {
+        validate_folder_id(&folder_id)?;
+        *self.folder_id.write().unwrap() = folder_id;
+        self.cached_result.lock().await.clear();
+        tracing::info!(operation = "config", folder_id = %self.folder_id(), "Changed release folder");
+        Ok(())
+    }
+
+    /// Latest release in the configured folder.
     pub async fn fetch_latest_file(
         &self,
         force_refresh: bool,
     ) -> Result<GoogleDriveFileInfo, PobError> {
-        // Currently, hardcodeing the folder ID
-        const FOLDER_ID: &str = "1_5YhTy59gkyJpWqPuKA_z1cnobQcS8gi";
+        self.fetch_latest_file_in(&self.folder_id(), force_refresh)
+            .await
+    }
 
+    /// Latest release in `folder_id`, cached per folder for `latest_cache_ttl_secs`.
+    pub async fn fetch_latest_file_in(
+        &self,
+        folder_id: &str,
+        force_refresh: bool,
+    ) -> Result<GoogleDriveFileInfo, PobError> {
+        validate_folder_id(folder_id)?;
         if !force_refresh {
+            let ttl = Duration::from_secs(self.config().latest_cache_ttl_secs.into());
             let cache = self.cached_result.lock().await;
-            if let Some(cached) = cache.get(FOLDER_ID) {
-                return Ok(cached.clone());
+            match cache.get(folder_id) {
+                Some((fetched_at, cached)) if fetched_at.elapsed() < ttl => {
+                    return Ok(cached.clone());
+                }
+                Some(_) => {
+                    tracing::debug!(operation = "cache", folder_id = %folder_id, "Cached latest release expired");
+                }
+                None => {}
             }
         }
 
-        let latest = self.client.find_latest(FOLDER_ID).await?;
+        self.ensure_active()?;
+        let latest = self.client.find_latest(folder_id).await?;
 
-        let latest = latest.ok_or_else(|| PobError::NotFoundFromDrive(FOLDER_ID.to_string()))?;
+        let latest = latest.ok_or_else(|| PobError::NotFoundFromDrive(folder_id.to_string()))?;
 
         let mut cache = self.cached_result.lock().await;
-        cache.insert(FOLDER_ID.to_string(), latest.clone());
+        cache.insert(folder_id.to_string(), (Instant::now(), latest.clone()));
 
         Ok(latest)
     }
 
+    /// How the install compares to the latest release. Warns when the install is
+    /// newer, since offering "latest" then would be a downgrade.
+    pub async fn update_state(&self, force_refresh: bool) -> Result<UpdateState, PobError> {
+        let latest = self.fetch_latest_file(force_refresh).await?;
+        let latest = parse_from_name(&latest.name)?;
+        let installed = self.installed_version().await?;
+        let installed = installed.as_ref().map(|v| v.version.as_str());
+        let state = UpdateState::classify(installed, &latest);
+        if state == UpdateState::InstalledNewer {
+            tracing::warn!(
+                operation = "update_check",
+                installed = ?installed,
+                latest = %latest,
+                "Installed version is newer than the latest release"
+            );
+        }
+        Ok(state)
+    }
+
+    /// Installed and latest versions, and whether installing latest would update.
+    pub async fn check_for_update(&self) -> Result<UpdateStatus, PobError> {
+        let latest = self.fetch_latest_file(false).await?;
+        let latest = parse_from_name(&latest.name)?;
+        let installed = self.installed_version().await?.map(|v| v.version);
+        let update_available = matches!(
+            UpdateState::classify(installed.as_deref(), &latest),
+            UpdateState::NotInstalled | UpdateState::UpdateAvailable
+        );
+        let days_behind = installed
+            .as_deref()
+            .and_then(|installed| days_between(installed, &latest))
+            .map(|days| days as i32);
+        Ok(UpdateStatus {
+            installed,
+            latest,
+            update_available,
+            days_behind,
+        })
+    }
+
+    /// Refuse to install `file_info` over a newer install unless `allow_downgrade`.
+    pub async fn check_downgrade(
+        &self,
+        file_info: &GoogleDriveFileInfo,
+        allow_downgrade: bool,
+    ) -> Result<(), PobError> {
+        let Some(installed) = self.installed_version().await? else {
+            return Ok(());
+        };
+        let target = parse_from_name(&file_info.name)?;
+        if !installed.is_newer_than(&target) {
+            return Ok(());
+        }
+        if allow_downgrade {
+            tracing::info!(phase = "prepare", installed = %installed.version, target = %target, "Downgrading as requested");
+            return Ok(());
+        }
+        Err(PobError::Downgrade {
+            installed: installed.version,
+            target,
+        })
+    }
+
+    /// Size of the update to the latest release, or `None` if it's already installed.
+    pub async fn update_download_estimate(
+        &self,
+    ) -> Result<Option<UpdateDownloadEstimate>, PobError> {
+        let latest = self.fetch_latest_file(false).await?;
+        let version = parse_from_name(&latest.name)?;
+        let installed = self.installed_version().await?;
+        if matches!(
+            UpdateState::classify(installed.as_ref().map(|v| v.version.as_str()), &version),
+            UpdateState::UpToDate | UpdateState::InstalledNewer
+        ) {
+            return Ok(None);
+        }
+
+        self.ensure_active()?;
+        let info = self.client.get_file_download_info(&latest.id).await?;
+        let threshold = u64::from(self.config().download.large_download_mb) * 1024 * 1024;
+        let bytes = (info.content_length > 0).then_some(info.content_length);
+        tracing::info!(
+            operation = "update_estimate",
+            version = %version,
+            bytes = ?bytes,
+            threshold,
+            "Estimated update download"
+        );
+
+        Ok(Some(UpdateDownloadEstimate {
+            version,
+            bytes: bytes.map(|b| b as f64),
+            is_large: bytes.is_some_and(|b| b >= threshold),
+        }))
+    }
+
     pub async fn installed_version(
         &self,
     ) -> Result<Option<crate::pob::version::PobVersion>, PobError> {
-        let path = self.version_file_path();
-        if !path.exists() {
-            return Ok(None);
+        read_version_file(&self.version_file_path()).await
+    }
+
+    /// Version that [`Self::revert_to_previous`] would bring back, if one is retained.
+    pub async fn previous_version(&self) -> Result<Option<PobVersion>, PobError> {
+        read_version_file(&self.previous_install_path().join("pob_version.json")).await
+    }
+
+    /// Release notes shipped at the root of the installed archive, if any.
+    /// Not valid UTF-8 is decoded lossily rather than refused.
+    pub async fn changelog(&self) -> Result<Option<String>, PobError> {
+        let install_path = self.install_path();
+        for name in CHANGELOG_FILES {
+            let bytes = match tokio::fs::read(install_path.join(name)).await {
+                Ok(bytes) => bytes,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            let text = String::from_utf8(bytes)
+                .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned());
+            return Ok(Some(text.trim_start_matches('\u{feff}').to_string()));
         }
-        let data = tokio::fs::read_to_string(&path).await?;
-        let installed: crate::pob::version::PobVersion = serde_json::from_str(&data)?;
-        Ok(Some(installed))
+        Ok(None)
     }
 
-    pub fn is_pob_running(&self) -> bool {
-        use sysinfo::System;
+    /// Events recorded for the last operation, or empty if none was recorded.
+    pub async fn replay_last_install(&self) -> Result<Vec<InstallProgress>, PobError> {
+        let path = self.event_log_path();
+        let events = tokio::task::spawn_blocking(move || read_event_log(&path)).await?;
+        match events {
+            Ok(events) => Ok(events),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
 
-        let mut sys = System::new();
+    /// Whether PoB is running from this install.
+    pub fn is_pob_running(&self) -> bool {
+        let mut sys = sysinfo::System::new();
         sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+        !self.pob_processes(&sys).is_empty()
+    }
+
+    /// Processes named like the PoB exe that run from this install. Ones whose
+    /// exe path can't be read (e.g. access denied) count too, erring on the
+    /// side of blocking file operations.
+    fn pob_processes<'a>(&self, sys: &'a sysinfo::System) -> Vec<&'a sysinfo::Process> {
         let exe_path = self.exe_path();
         let exe_name = exe_path.file_name().unwrap();
+        let canonical =
+            |path: &Path| std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let exe_path = canonical(&exe_path);
+
+        sys.processes_by_exact_name(exe_name)
+            .filter(|process| process.exe().is_none_or(|exe| canonical(exe) == exe_path))
+            .collect()
+    }
+
+    /// Kill PoB processes running from this install and wait for them to exit.
+    /// Fails with [`PobError::ProcessRunning`] if one outlives the timeout.
+    pub async fn terminate_pob(&self) -> Result<(), PobError> {
+        const TERMINATE_TIMEOUT: Duration = Duration::from_secs(5);
+
+        let killed = {
+            let mut sys = sysinfo::System::new();
+            sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+            let processes = self.pob_processes(&sys);
+            processes.iter().filter(|process| process.kill()).count()
+        };
+        if killed > 0 {
+            tracing::warn!(operation = "terminate", killed, "Terminated running PoB");
+        }
 
-        sys.processes_by_exact_name(exe_name).next().is_some()
+        let deadline = Instant::now() + TERMINATE_TIMEOUT;
+        while self.is_pob_running() {
+            if Instant::now() >= deadline {
+                return Err(PobError::ProcessRunning);
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+        Ok(())
     }
 
+    /// Download `file_id` to `dst`, returning the still-open file rewound to the
+    /// start so extraction can read it without reopening.
     pub(crate) async fn download_with_progress<P: AsRef<std::path::Path>>(
         &self,
         file_id: &str,
         dst: P,
         cancel_token: CancellationToken,
         reporter: &InstallReporter,
-    ) -> Result<(), PobError> {
+    ) -> Result<std::fs::File, PobError> {
         use futures_util::StreamExt;
-        use tokio::io::{AsyncWriteExt, BufWriter};
+        use tokio::io::{AsyncSeekExt, AsyncWriteExt, BufWriter};
 
-        const DOWNLOAD_BUFFER_SIZE: usize = 64 * 1024;
+        let download_config = self.config().download;
+        let _slot = self.acquire_download_slot(&cancel_token).await?;
+        // The connection and the part file
+        let _handles = self.acquire_handles(2, &cancel_token).await?;
+
+        // Bytes left by an earlier interrupted attempt at this file, if any, and
+        // the file size they belong to. A part without its size can't be validated.
+        let partial = self.partial_download_path(file_id);
+        let partial_meta = partial_meta_path(&partial);
+        let expected_total = read_partial_meta(&partial_meta).await;
+        let partial_len = match expected_total {
+            Some(_) => self
+                .fs
+                .metadata(&partial)
+                .await
+                .map(|m| m.len())
+                .unwrap_or(0),
+            None => 0,
+        };
+
+        // Response headers can take a while; don't wait for them once cancelled
+        let request = async {
+            if partial_len > 0 {
+                match self.client.get_file_range_open(file_id, partial_len).await {
+                    // The file must not have changed, or grown shorter than the part
+                    Ok(res)
+                        if res.status() != reqwest::StatusCode::PARTIAL_CONTENT
+                            || content_range_total(res.headers()).is_some_and(|total| {
+                                partial_len < total && Some(total) == expected_total
+                            }) =>
+                    {
+                        return Ok(res);
+                    }
+                    Ok(_) => {
+                        tracing::warn!(
+                            phase = "download",
+                            "Partial download no longer matches the file, restarting"
+                        )
+                    }
+                    // e.g. 416 when the part is already as large as the file
+                    Err(e) => {
+                        tracing::warn!(phase = "download", error = %e, "Resume request failed, restarting download")
+                    }
+                }
+            }
+            self.client.get_file(file_id).await
+        };
+        let res = match until_cancelled(&cancel_token, request).await {
+            Err(PobError::Cancelled) => {
+                tracing::info!(phase = "download", "Download cancelled before start");
+                reporter.report(InstallPhase::Downloading, InstallStatus::Cancelled);
+                return Err(PobError::Cancelled);
+            }
+            res => res?,
+        };
 
-        let res = self.client.get_file(file_id).await?;
-        let total_size = res.content_length().unwrap_or(0);
+        // Only a 206 continues the part; a 200 is the whole file again
+        let (resume_from, total_size) = if res.status() == reqwest::StatusCode::PARTIAL_CONTENT {
+            let total = content_range_total(res.headers())
+                .unwrap_or_else(|| partial_len + res.content_length().unwrap_or(0));
+            (partial_len, total)
+        } else {
+            (0, res.content_length().unwrap_or(0))
+        };
+        if resume_from > 0 {
+            self.move_file(&partial, dst.as_ref()).await?;
+        } else {
+            self.fs.remove_file(&partial).await.ok();
+        }
+        self.fs.remove_file(&partial_meta).await.ok();
 
-        let f = tokio::fs::File::create(dst.as_ref()).await?;
-        if total_size > 0
+        // Readable too: extraction reads back through this same handle
+        let mut f = tokio::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(resume_from == 0)
+            .open(dst.as_ref())
+            .await?;
+        if resume_from > 0 {
+            tracing::info!(
+                phase = "download",
+                resume_from,
+                total_size,
+                "Resuming partial download"
+            );
+            f.seek(std::io::SeekFrom::Start(resume_from)).await?;
+        }
+        if download_config.preallocate
+            && total_size > 0
             && let Err(e) = f.set_len(total_size).await
         {
             tracing::warn!(
@@ -145,23 +769,27 @@ impl PobManager {
             InstallPhase::Downloading,
             InstallStatus::Started {
                 total_size: NonZeroU32::new(total_size as u32),
+                estimated_secs: None,
             },
         );
 
         let start = Instant::now();
         let mut stream = res.bytes_stream();
-        let mut writer = BufWriter::with_capacity(DOWNLOAD_BUFFER_SIZE, f);
+        let mut writer = BufWriter::with_capacity(download_config.buffer_size as usize, f);
 
-        let mut downloaded: u64 = 0;
+        let mut downloaded: u64 = resume_from;
+        let retry = *self.client.retry_policy();
+        let mut attempt = 1;
         let mut last_report = start;
+        let mut speed = SpeedTracker::new(start);
+        let mut throughput = ThroughputWindow::default();
 
         loop {
             tokio::select! {
                 _ = cancel_token.cancelled() => {
                     tracing::info!(phase = "download", "Download cancelled");
                     reporter.report(InstallPhase::Downloading, InstallStatus::Cancelled);
-                    drop(writer);
-                    tokio::fs::remove_file(dst.as_ref()).await.ok();
+                    self.stash_partial_download(writer, downloaded, total_size, dst.as_ref(), &partial).await;
                     return Err(PobError::Cancelled);
                 }
                 chunk = stream.next() => {
@@ -173,24 +801,88 @@ impl PobManager {
                             if last_report.elapsed() < Duration::from_millis(PROGRESS_REPORT_INTERVAL_MS) {
                                 continue;
                             }
-                            let percent = if total_size > 0 {
-                                downloaded as f64 / total_size as f64 * 100.0
-                            } else {
-                                0.0
-                            };
-                            reporter.report(InstallPhase::Downloading, InstallStatus::InProgress { percent });
                             last_report = Instant::now();
+                            throughput.record(downloaded, last_report);
+                            reporter.report(InstallPhase::Downloading, throughput.status(downloaded, total_size));
+                            speed.sample(downloaded - resume_from, last_report);
                         }
                         Some(Err(e)) => {
+                            // Dropped mid-body: continue from the bytes already written
+                            if total_size > 0 && attempt < retry.max_attempts && is_retryable(&e) {
+                                let delay = retry.delay(attempt);
+                                tracing::warn!(
+                                    phase = "download",
+                                    attempt,
+                                    max_attempts = retry.max_attempts,
+                                    downloaded,
+                                    delay_ms = delay.as_millis() as u64,
+                                    error = %e,
+                                    "Download interrupted, resuming"
+                                );
+                                attempt += 1;
+                                let resume = async {
+                                    tokio::time::sleep(delay).await;
+                                    self.client.get_file_range_open(file_id, downloaded).await
+                                };
+                                match until_cancelled(&cancel_token, resume).await {
+                                    Ok(res)
+                                        if res.status() == reqwest::StatusCode::PARTIAL_CONTENT
+                                            && content_range_total(res.headers()) == Some(total_size) =>
+                                    {
+                                        stream = res.bytes_stream();
+                                        continue;
+                                    }
+                                    Ok(_) => {
+                                        tracing::warn!(phase = "download", "Server did not resume the download");
+                                    }
+                                    Err(PobError::Cancelled) => {
+                                        tracing::info!(phase = "download", "Download cancelled");
+                                        reporter.report(InstallPhase::Downloading, InstallStatus::Cancelled);
+                                        self.stash_partial_download(writer, downloaded, total_size, dst.as_ref(), &partial).await;
+                                        return Err(PobError::Cancelled);
+                                    }
+                                    Err(resume_err) => {
+                                        tracing::warn!(phase = "download", error = %resume_err, "Resume request failed");
+                                    }
+                                }
+                            }
                             tracing::error!(phase = "download", error = %e, "Error while downloading");
                             reporter.report(InstallPhase::Downloading, InstallStatus::Failed { reason: e.to_string() });
+                            self.stash_partial_download(writer, downloaded, total_size, dst.as_ref(), &partial).await;
+                            // With a known size the kept part can be resumed, so say so
+                            if total_size > 0 {
+                                return Err(PobError::DownloadTruncated { expected: total_size, actual: downloaded });
+                            }
                             return Err(PobError::DownloadFailed(e.to_string()));
                         }
+                        // A close-delimited body ends without an error when the connection drops
+                        None if downloaded < total_size => {
+                            let err = PobError::DownloadTruncated { expected: total_size, actual: downloaded };
+                            tracing::error!(phase = "download", downloaded, total_size, "Download ended early");
+                            reporter.report(InstallPhase::Downloading, InstallStatus::Failed { reason: err.to_string() });
+                            self.stash_partial_download(writer, downloaded, total_size, dst.as_ref(), &partial).await;
+                            return Err(err);
+                        }
                         None => {
                             writer.flush().await?;
-                            tracing::info!(phase = "download", elapsed = ?start.elapsed(), "Download completed");
-                            reporter.report(InstallPhase::Downloading, InstallStatus::Completed);
-                            return Ok(());
+                            let mut file = writer.into_inner();
+                            file.rewind().await?;
+                            // Only this session's bytes count towards throughput, not the resumed part
+                            let transferred = downloaded - resume_from;
+                            let elapsed = start.elapsed();
+                            let speed = speed.summary(transferred, Instant::now());
+                            tracing::info!(
+                                phase = "download",
+                                mode = "single",
+                                total_bytes = transferred,
+                                elapsed_ms = elapsed.as_millis() as u64,
+                                avg_mbps = megabits_per_sec(transferred, elapsed),
+                                avg_bytes_per_sec = speed.map(|s| s.avg_bytes_per_sec),
+                                peak_bytes_per_sec = speed.map(|s| s.peak_bytes_per_sec),
+                                "Download completed"
+                            );
+                            reporter.report(InstallPhase::Downloading, InstallStatus::Completed { speed });
+                            return Ok(file.into_std().await);
                         }
                     }
                 }
@@ -198,6 +890,54 @@ impl PobManager {
         }
     }
 
+    /// Keep the first `downloaded` bytes at `dst` as `partial`, for the next
+    /// attempt at the same file to resume from, with `total_size` recorded in
+    /// its `.part.meta`. Best-effort.
+    async fn stash_partial_download(
+        &self,
+        mut writer: tokio::io::BufWriter<tokio::fs::File>,
+        downloaded: u64,
+        total_size: u64,
+        dst: &Path,
+        partial: &Path,
+    ) {
+        use tokio::io::AsyncWriteExt;
+
+        // Without a known size a resumed download couldn't be validated
+        if downloaded == 0 || total_size == 0 {
+            drop(writer);
+            self.fs.remove_file(dst).await.ok();
+            return;
+        }
+
+        let stashed = async move {
+            // Drop the preallocated tail so the part's length is exactly what was received
+            writer.flush().await?;
+            writer.into_inner().set_len(downloaded).await?;
+
+            // Only one file is ever resumed; parts of older releases are dead weight
+            let dir = self.partial_downloads_dir();
+            self.fs.remove_dir_all(&dir).await.ok();
+            self.fs.create_dir_all(&dir).await?;
+            self.move_file(dst, partial).await?;
+            let meta = serde_json::to_vec(&PartialMeta { total_size })?;
+            tokio::fs::write(partial_meta_path(partial), meta).await
+        }
+        .await;
+
+        match stashed {
+            Ok(()) => {
+                tracing::info!(phase = "download", downloaded, path = %partial.display(), "Kept partial download for resume");
+            }
+            Err(e) => {
+                tracing::warn!(phase = "download", error = %e, "Failed to keep partial download");
+                self.fs.remove_file(partial).await.ok();
+                self.fs.remove_file(&partial_meta_path(partial)).await.ok();
+                self.fs.remove_file(dst).await.ok();
+            }
+        }
+    }
+
     pub(crate) async fn extract_with_progress<P: AsRef<std::path::Path>>(
         &self,
         zip_path: P,
@@ -205,21 +945,67 @@ impl PobManager {
         cancel_token: CancellationToken,
         reporter: InstallReporter,
     ) -> Result<(), PobError> {
-        if dest_path.as_ref().exists() {
-            tokio::fs::remove_dir_all(&dest_path).await?;
+        let zip_file = tokio::fs::File::open(zip_path.as_ref())
+            .await?
+            .into_std()
+            .await;
+        let skip_user_data = self.config().skip_user_data_on_extract;
+        self.extract_file_with_progress(
+            zip_file,
+            dest_path.as_ref(),
+            skip_user_data,
+            cancel_token,
+            reporter,
+        )
+        .await
+    }
+
+    /// [`Self::extract_with_progress`] from an already-open archive, e.g. the
+    /// handle returned by [`Self::download_with_progress`]. `skip_user_data`
+    /// leaves out entries under backup targets present in the current install.
+    pub(crate) async fn extract_file_with_progress(
+        &self,
+        zip_file: std::fs::File,
+        dest_path: &Path,
+        skip_user_data: bool,
+        cancel_token: CancellationToken,
+        reporter: InstallReporter,
+    ) -> Result<(), PobError> {
+        // The archive and the entry being written
+        let _handles = self.acquire_handles(2, &cancel_token).await?;
+        if dest_path.exists() {
+            self.fs.remove_dir_all(dest_path).await?;
         }
-        tokio::fs::create_dir_all(&dest_path).await?;
+        self.fs.create_dir_all(dest_path).await?;
 
-        let zip_path = zip_path.as_ref().to_path_buf();
-        let dest_path = dest_path.as_ref().to_path_buf();
+        let dest_path = dest_path.to_path_buf();
+        let config = self.config();
+        let mtime_policy = config.mtime_policy;
+        let on_dangerous_path = config.on_dangerous_path;
+        // Only targets present in the current install get restored, so only those can be skipped
+        let user_data_targets: Vec<PathBuf> = if skip_user_data {
+            let install_path = self.install_path();
+            self.load_backup_targets()
+                .into_iter()
+                .filter(|target| install_path.join(target).exists())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        // User data changes after restore, so it never belongs in the manifest
+        let manifest_excluded = self.load_backup_targets();
+        let stats = self.stats.read().unwrap().clone();
 
-        let task = tokio::task::spawn_blocking(move || -> Result<(), PobError> {
-            let f = std::fs::File::open(&zip_path)?;
-            let mut archive = zip::ZipArchive::new(f)?;
+        let task = tokio::task::spawn_blocking(move || -> Result<Option<f64>, PobError> {
+            let mut archive = zip::ZipArchive::new(zip_file)?;
             let file_count = archive.len() as u32;
+            let total_bytes = archive
+                .decompressed_size()
+                .and_then(|size| u64::try_from(size).ok());
+            let estimated_secs = total_bytes.and_then(|bytes| stats.estimate_extract_secs(bytes));
 
             // Detect nested structure BEFORE extraction
-            let skip_prefix = detect_nested_structure(&archive)?;
+            let skip_prefix = detect_nested_structure(&mut archive)?;
             if let Some(ref prefix) = skip_prefix {
                 tracing::warn!(
                     phase = "extract",
@@ -232,9 +1018,29 @@ impl PobManager {
                 InstallPhase::Extracting,
                 InstallStatus::Started {
                     total_size: NonZeroU32::new(file_count),
+                    estimated_secs,
                 },
             );
-            let mut last_report = Instant::now();
+            // Progress follows uncompressed bytes, so one large entry doesn't stall it
+            let entry_bytes: u64 = (0..archive.len())
+                .filter_map(|i| archive.by_index_raw(i).ok().map(|file| file.size()))
+                .sum();
+            let start = Instant::now();
+            let mut last_report = start;
+            let mut report_bytes = |done: u64| {
+                if last_report.elapsed() < Duration::from_millis(PROGRESS_REPORT_INTERVAL_MS) {
+                    return;
+                }
+                reporter.report(
+                    InstallPhase::Extracting,
+                    InstallStatus::in_progress(byte_percent(done, entry_bytes)),
+                );
+                last_report = Instant::now();
+            };
+            let mut done_bytes = 0u64;
+            let mut skipped_user_data = 0u32;
+            let mut skipped_dangerous = 0u32;
+            let mut manifest = InstallManifest::default();
 
             for i in 0..file_count {
                 if cancel_token.is_cancelled() {
@@ -252,13 +1058,24 @@ impl PobManager {
                 }
 
                 let mut file = archive.by_index(i as usize)?;
+                // Skipped entries count as done right away
+                let entry_start = done_bytes;
+                done_bytes += file.size();
 
-                let Some(outpath) = file.enclosed_name() else {
-                    tracing::warn!(
-                        phase = "extract",
-                        name = file.name(),
-                        "Skipping dangerous path"
-                    );
+                let Some(outpath) = enclosed_entry_path(decode_entry_name(&file)) else {
+                    let raw_name = String::from_utf8_lossy(file.name_raw());
+                    if on_dangerous_path == DangerousPathPolicy::Abort {
+                        tracing::error!(
+                            phase = "extract",
+                            name = %raw_name,
+                            "Aborting on dangerous path"
+                        );
+                        return Err(PobError::ExtractFailed(format!(
+                            "안전하지 않은 경로의 항목이 포함되어 있습니다: {raw_name}"
+                        )));
+                    }
+                    tracing::warn!(phase = "extract", name = %raw_name, "Skipping dangerous path");
+                    skipped_dangerous += 1;
                     continue;
                 };
 
@@ -272,7 +1089,12 @@ impl PobManager {
                     outpath
                 };
 
-                let outpath = dest_path.join(final_path);
+                if is_user_data_path(&user_data_targets, &final_path) {
+                    skipped_user_data += 1;
+                    continue;
+                }
+
+                let outpath = dest_path.join(&final_path);
 
                 if file.is_dir() {
                     std::fs::create_dir_all(&outpath)?;
@@ -281,41 +1103,168 @@ impl PobManager {
                         std::fs::create_dir_all(p)?;
                     }
                     let mut outfile = std::fs::File::create(&outpath)?;
-                    std::io::copy(&mut file, &mut outfile)?;
+                    // The entry reader checks the CRC at EOF and fails with InvalidData,
+                    // as does a corrupt deflate stream; name the entry instead of a bare I/O error
+                    if let Err(e) = copy_with_progress(&mut file, &mut outfile, |written| {
+                        report_bytes(entry_start + written)
+                    }) {
+                        if e.kind() != std::io::ErrorKind::InvalidData {
+                            return Err(e.into());
+                        }
+                        tracing::error!(
+                            phase = "extract",
+                            entry = %final_path.display(),
+                            error = %e,
+                            "Corrupt archive entry"
+                        );
+                        drop(outfile);
+                        std::fs::remove_file(&outpath).ok();
+                        return Err(PobError::ExtractFailed(format!(
+                            "손상된 항목입니다: {} ({e})",
+                            final_path.display()
+                        )));
+                    }
 
-                    if let Some(last_modified) = file.last_modified()
+                    // mtime is best-effort: some filesystems reject it, and it
+                    // shouldn't abort an otherwise-good extraction
+                    if mtime_policy.should_preserve(&outpath)
+                        && let Some(last_modified) = file.last_modified()
                         && let Some(t) = datetime_to_systemtime(&last_modified)
+                        && let Err(e) = outfile.set_modified(t)
                     {
-                        outfile.set_modified(t)?;
+                        tracing::warn!(
+                            phase = "extract",
+                            path = %outpath.display(),
+                            error = %e,
+                            "Failed to preserve modification time"
+                        );
                     }
-                }
 
-                if last_report.elapsed() < Duration::from_millis(PROGRESS_REPORT_INTERVAL_MS) {
-                    continue;
+                    if !is_user_data_path(&manifest_excluded, &final_path) {
+                        let mtime = outfile.metadata().and_then(|m| m.modified()).ok();
+                        manifest.push(&final_path, file.size(), file.crc32(), mtime);
+                    }
                 }
-                let percent = (i + 1) as f64 / file_count as f64 * 100.0;
-                reporter.report(
-                    InstallPhase::Extracting,
-                    InstallStatus::InProgress { percent },
+
+                report_bytes(done_bytes);
+            }
+
+            if skipped_dangerous > 0 {
+                tracing::warn!(
+                    phase = "extract",
+                    skipped = skipped_dangerous,
+                    "Skipped entries with dangerous paths"
+                );
+            }
+            if skipped_user_data > 0 {
+                tracing::info!(
+                    phase = "extract",
+                    skipped = skipped_user_data,
+                    "Skipped user-data entries that will be restored from backup"
                 );
-                last_report = Instant::now();
             }
+            // Without a manifest verify_install just can't run; not worth failing the install
+            if let Err(e) = manifest.save(&dest_path) {
+                tracing::warn!(phase = "extract", error = %e, "Failed to write install manifest");
+            }
+            reporter.report(
+                InstallPhase::Extracting,
+                InstallStatus::Completed { speed: None },
+            );
 
-            reporter.report(InstallPhase::Extracting, InstallStatus::Completed);
-            Ok(())
+            // Measured throughput feeds the next estimate; tiny archives are too noisy
+            let elapsed = start.elapsed().as_secs_f64();
+            Ok(total_bytes
+                .filter(|bytes| *bytes >= MIN_MEASURED_EXTRACT_BYTES && elapsed > 0.0)
+                .map(|bytes| bytes as f64 / elapsed))
         });
 
-        task.await?
+        if let Some(rate) = task.await?? {
+            tracing::debug!(
+                phase = "extract",
+                bytes_per_sec = rate,
+                "Recorded extraction throughput"
+            );
+            self.update_stats(|stats| stats.extract_bytes_per_sec = Some(rate))
+                .await;
+        }
+
+        Ok(())
     }
 
-    pub(crate) async fn backup(&self, reporter: &InstallReporter) -> Result<(), PobError> {
-        tracing::info!(phase = "backup", "Starting backup");
-        reporter.report(
-            InstallPhase::BackingUp,
-            InstallStatus::Started { total_size: None },
-        );
+    /// Overall-progress weights for the next install, learned from past ones.
+    pub fn phase_weights(&self) -> PhaseWeights {
+        self.stats.read().unwrap().phase_weights()
+    }
 
-        let install_path = self.install_path();
+    /// Apply `update` to the in-memory stats and persist them. Failures only cost estimates.
+    async fn update_stats(&self, update: impl FnOnce(&mut InstallStats)) {
+        let stats = {
+            let mut stats = self.stats.write().unwrap();
+            update(&mut stats);
+            stats.clone()
+        };
+        if let Err(e) = stats.save(&stats_file_path(&self.data_dir())).await {
+            tracing::warn!(operation = "stats", error = %e, "Failed to save install stats");
+        }
+    }
+
+    /// Copy every existing [`Self::load_backup_targets`] entry under `install_path`
+    /// into `dest`, keeping relative paths. Returns how many were copied.
+    async fn copy_backup_targets(
+        &self,
+        install_path: &Path,
+        dest: &Path,
+        cancel_token: &CancellationToken,
+    ) -> Result<u32, PobError> {
+        let mut copied = 0;
+        for relative_path in self.load_backup_targets() {
+            if cancel_token.is_cancelled() {
+                return Err(PobError::Cancelled);
+            }
+            let absolute_path = install_path.join(&relative_path);
+            let metadata = match self.fs.metadata(&absolute_path).await {
+                Ok(metadata) => metadata,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    tracing::debug!(phase = "backup", path = %relative_path.display(), "Backup target does not exist, skipping");
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            let backup_target_path = dest.join(&relative_path);
+
+            if metadata.is_dir() {
+                async_copy_dir_recursive(&absolute_path, &backup_target_path, cancel_token)
+                    .await
+                    .map_err(|e| copy_error(cancel_token, e))?;
+            } else {
+                if let Some(parent) = backup_target_path.parent() {
+                    self.fs.create_dir_all(parent).await?;
+                }
+                self.fs.copy(&absolute_path, &backup_target_path).await?;
+            }
+            copied += 1;
+        }
+        Ok(copied)
+    }
+
+    /// Copy the user data into a new timestamped backup and return its path.
+    pub(crate) async fn backup(
+        &self,
+        reporter: &InstallReporter,
+        cancel_token: &CancellationToken,
+    ) -> Result<PathBuf, PobError> {
+        tracing::info!(phase = "backup", "Starting backup");
+        reporter.report(
+            InstallPhase::BackingUp,
+            InstallStatus::Started {
+                total_size: None,
+                estimated_secs: None,
+            },
+        );
+
+        let install_path = self.install_path();
         tracing::debug!(
             phase = "backup",
             install_path = %install_path.display(),
@@ -323,196 +1272,1218 @@ impl PobManager {
             "Backup source path"
         );
 
-        // write to `<backup_dir>/backup.new`
-        let existing_backup = self.backup_dir();
-        let backup_path = self.backup_dir().with_extension("new");
+        // write to `<backup_dir>/<stamp>.new`, then rename into place
+        let backup_dir = self.backup_dir();
+        let stamp = chrono::Local::now().format(STAMP_FORMAT).to_string();
+        let name = next_backup_name(&backup_dir, &stamp);
+        let backup_path = backup_dir.join(&name);
+        let staging = backup_path.with_extension("new");
         tracing::debug!(
             phase = "backup",
-            backup_new = %backup_path.display(),
-            existing_backup = %existing_backup.display(),
+            staging = %staging.display(),
+            backup = %backup_path.display(),
             "Backup paths determined"
         );
 
-        // Ensure backup.new directory exists (especially for first install)
-        if backup_path.exists() {
-            tokio::fs::remove_dir_all(&backup_path).await?;
+        // Ensure the staging directory exists (especially for first install)
+        if staging.exists() {
+            self.fs.remove_dir_all(&staging).await?;
         }
-        tokio::fs::create_dir_all(&backup_path).await?;
-        tracing::debug!(phase = "backup", path = %backup_path.display(), "Created backup.new directory");
+        self.fs.create_dir_all(&staging).await?;
+        tracing::debug!(phase = "backup", path = %staging.display(), "Created backup staging directory");
 
-        for relative_path in self.backup_targets() {
-            let absolute_path = install_path.join(&relative_path);
-            if !absolute_path.exists() {
-                tracing::debug!(phase = "backup", path = %relative_path.display(), "Backup target does not exist, skipping");
-                continue;
+        if let Err(e) = self
+            .copy_backup_targets(&install_path, &staging, cancel_token)
+            .await
+        {
+            if let PobError::Cancelled = e {
+                tracing::info!(phase = "backup", "Backup cancelled, removing partial copy");
+                self.fs.remove_dir_all(&staging).await.ok();
+                reporter.report(InstallPhase::BackingUp, InstallStatus::Cancelled);
             }
+            return Err(e);
+        }
+        tracing::info!(phase = "backup", "Backup copy completed");
+        reporter.report(
+            InstallPhase::BackingUp,
+            InstallStatus::Completed { speed: None },
+        );
 
-            let backup_target_path = backup_path.join(&relative_path);
+        self.fs.rename(&staging, &backup_path).await?;
+        self.prune_backups().await;
+        tracing::info!(phase = "backup", name = %name, "Backup finalized");
 
-            if absolute_path.is_dir() {
-                async_copy_dir_recursive(&absolute_path, &backup_target_path).await?;
-            } else {
-                if let Some(parent) = backup_target_path.parent() {
-                    tokio::fs::create_dir_all(parent).await?;
+        Ok(backup_path)
+    }
+
+    /// Delete the oldest install backups beyond `max_backups`. Best-effort.
+    async fn prune_backups(&self) {
+        let backup_dir = self.backup_dir();
+        let keep = self.config().max_backups as usize;
+        let names = backups_newest_first(&backup_dir);
+        for name in names.iter().skip(keep) {
+            let path = backup_dir.join(name);
+            tracing::debug!(phase = "backup", path = %path.display(), "Pruning old backup");
+            if let Err(e) = self.fs.remove_dir_all(&path).await {
+                tracing::warn!(phase = "backup", path = %path.display(), error = %e, "Failed to prune old backup");
+            }
+        }
+    }
+
+    /// Newest install backup, which `check_backup` uses.
+    pub fn latest_backup(&self) -> Option<PathBuf> {
+        let backup_dir = self.backup_dir();
+        backups_newest_first(&backup_dir)
+            .into_iter()
+            .next()
+            .map(|name| backup_dir.join(name))
+    }
+
+    /// Install backups, newest first.
+    pub async fn list_backups(&self) -> Result<Vec<BackupEntry>, PobError> {
+        let backup_dir = self.backup_dir();
+        let entries = tokio::task::spawn_blocking(move || {
+            backups_newest_first(&backup_dir)
+                .into_iter()
+                .map(|name| backup_entry(&backup_dir, name))
+                .collect()
+        })
+        .await?;
+        Ok(entries)
+    }
+
+    /// Repair the backup directory after a crash mid-backup.
+    ///
+    /// Prefers an existing `backup`, otherwise promotes `backup.old` left by the
+    /// single-backup layout's finalize swap. An orphaned `backup.new` or
+    /// `backup/<name>.new` is always discarded since it may be an incomplete copy.
+    /// Called once from app setup, before any operation can run.
+    pub fn recover_backup_swap(&self) -> BackupRecovery {
+        let backup = self.backup_dir();
+        let new = backup.with_extension("new");
+        let old = backup.with_extension("old");
+
+        let mut action = BackupRecovery::Intact;
+
+        if !backup.exists() && old.exists() {
+            match std::fs::rename(&old, &backup) {
+                Ok(()) => {
+                    tracing::warn!(
+                        operation = "recovery",
+                        from = %old.display(),
+                        to = %backup.display(),
+                        "Promoted backup.old after interrupted backup swap"
+                    );
+                    action = BackupRecovery::PromotedOld;
+                }
+                Err(e) => {
+                    tracing::error!(
+                        operation = "recovery",
+                        path = %old.display(),
+                        error = %e,
+                        "Failed to promote backup.old, leaving it in place"
+                    );
+                    return BackupRecovery::Failed;
                 }
-                tokio::fs::copy(&absolute_path, &backup_target_path).await?;
             }
         }
-        tracing::info!(phase = "backup", "Backup copy completed");
-        reporter.report(InstallPhase::BackingUp, InstallStatus::Completed);
 
-        // finalize: swap backup.new -> backup (with .old staging if exists)
-        let old = existing_backup.with_extension("old");
-        tracing::debug!(
-            phase = "backup",
-            backup_new = %backup_path.display(),
-            existing = %existing_backup.display(),
-            existing_exists = %existing_backup.exists(),
-            old = %old.display(),
-            "Finalizing backup swap"
-        );
+        for leftover in [new, old].into_iter().chain(staged_backups(&backup)) {
+            if !leftover.exists() {
+                continue;
+            }
+            tracing::warn!(
+                operation = "recovery",
+                path = %leftover.display(),
+                "Removing leftover directory from interrupted backup"
+            );
+            if let Err(e) = std::fs::remove_dir_all(&leftover) {
+                tracing::warn!(
+                    operation = "recovery",
+                    path = %leftover.display(),
+                    error = %e,
+                    "Failed to remove leftover backup directory"
+                );
+            }
+            if action == BackupRecovery::Intact {
+                action = BackupRecovery::DiscardedLeftovers;
+            }
+        }
+
+        action
+    }
+
+    /// Deal with an install the app died in the middle of, from the leftover
+    /// `.new`/`.old` dirs and the recorded [`InstallState`]. A missing install
+    /// with `.old` present is rolled back and a stray `.new` removed; a swapped
+    /// install that may lack its user data, or one interrupted before the swap,
+    /// is left for the user to decide via [`Self::take_recovery_action`].
+    /// Called once from app setup, before any operation can run.
+    pub fn recover_incomplete_install(&self) -> Result<RecoveryAction, PobError> {
+        let state_path = install_state_path(&self.data_dir());
+        let state = InstallState::load(&state_path);
+        let install = self.install_path();
+        let old = install.with_extension("old");
+        let new = install.with_extension("new");
+        let (old_exists, new_exists) = (old.exists(), new.exists());
+
+        let action = if !install.exists() && old_exists {
+            tracing::warn!(operation = "recovery", path = %old.display(), "Install missing after interrupted swap, moving .old back");
+            std::fs::rename(&old, &install).map_err(|e| PobError::from_locked_io(e, &old))?;
+            RecoveryAction::RolledBack
+        } else {
+            match &state {
+                Some(state) if state.swapped(old_exists, new_exists) => {
+                    if state.backed_up {
+                        RecoveryAction::RestoreUserData {
+                            file_name: state.file_name.clone(),
+                        }
+                    } else {
+                        RecoveryAction::None
+                    }
+                }
+                Some(state) => RecoveryAction::ResumeInstall {
+                    file_id: state.file_id.clone(),
+                    file_name: state.file_name.clone(),
+                },
+                None if new_exists => RecoveryAction::CleanedUp,
+                None => RecoveryAction::None,
+            }
+        };
 
-        if existing_backup.exists() {
-            tracing::debug!(phase = "backup", "Moving existing backup to .old");
-            fs::rename(&existing_backup, &old).await?;
+        // Anything still in `.new` is a half-finished extraction, or one kept
+        // after a failed backup that no retry is coming for any more
+        std::fs::remove_file(staged_release_path(&new)).ok();
+        if new.exists() {
+            tracing::warn!(operation = "recovery", path = %new.display(), "Removing leftover extraction");
+            if let Err(e) = std::fs::remove_dir_all(&new) {
+                tracing::warn!(operation = "recovery", error = %e, "Failed to remove leftover extraction");
+            }
         }
-        tracing::debug!(phase = "backup", "Moving backup.new to backup");
-        fs::rename(&backup_path, &existing_backup).await?;
-        if old.exists() {
-            tracing::debug!(phase = "backup", "Cleaning up backup.old");
-            fs::remove_dir_all(&old).await.ok(); // best-effort cleanup
+        if state.is_some()
+            && let Err(e) = std::fs::remove_file(&state_path)
+        {
+            tracing::warn!(operation = "recovery", error = %e, "Failed to remove install state");
         }
-        tracing::info!(phase = "backup", "Backup finalized");
 
-        Ok(())
+        if action != RecoveryAction::None {
+            tracing::info!(
+                operation = "recovery",
+                ?action,
+                "Recovered from interrupted install"
+            );
+        }
+        *self.pending_recovery.write().unwrap() = action.needs_prompt().then(|| action.clone());
+        Ok(action)
     }
 
-    pub(crate) fn backup_targets(&self) -> Vec<PathBuf> {
-        const TARGETS: &[&str] = &[
-            "POE1 POB/Builds",
-            "POE2 POB/Builds",
-            "POE1 POB/Settings.xml",
-            "POE2 POB/Settings.xml",
-            "Data/Fonts",
-        ];
+    /// Recommendation from [`Self::recover_incomplete_install`] that needs the
+    /// user, returned once.
+    pub fn take_recovery_action(&self) -> Option<RecoveryAction> {
+        self.pending_recovery.write().unwrap().take()
+    }
 
-        TARGETS.iter().map(PathBuf::from).collect()
+    /// Record the stage `file_info`'s install reached. Best-effort: without it
+    /// recovery still works from the leftover directories alone.
+    async fn record_install_state(
+        &self,
+        file_info: &GoogleDriveFileInfo,
+        phase: InstallPhase,
+        backed_up: bool,
+    ) {
+        let state = InstallState::new(file_info, phase, backed_up);
+        if let Err(e) = state.save(&install_state_path(&self.data_dir())).await {
+            tracing::warn!(operation = "recovery", error = %e, "Failed to record install state");
+        }
     }
 
-    pub(crate) async fn restore(&self, reporter: &InstallReporter) -> Result<(), PobError> {
-        tracing::info!(phase = "restore", "Starting restore from backup");
-        reporter.report(
-            InstallPhase::Restoring,
-            InstallStatus::Started { total_size: None },
-        );
+    /// Move a backup from the single-backup layout, where user data sat directly
+    /// in `backup/`, into a timestamped `backup/<name>/` so it keeps rotating.
+    /// Called once from app setup after [`Self::recover_backup_swap`].
+    pub fn migrate_legacy_backup(&self) {
+        let backup = self.backup_dir();
+        let migrating = backup.with_extension("migrating");
 
-        let install_path = self.install_path();
-        let backup_path = self.backup_dir();
+        if !migrating.exists() {
+            // Only a backup target's top folder (e.g. `POE1 POB`) marks the old
+            // layout; stray files like desktop.ini or Thumbs.db don't
+            let legacy = self.load_backup_targets().iter().any(|target| {
+                target
+                    .components()
+                    .next()
+                    .is_some_and(|root| backup.join(root).exists())
+            });
+            if !legacy {
+                return;
+            }
+            if let Err(e) = std::fs::rename(&backup, &migrating) {
+                tracing::warn!(operation = "recovery", error = %e, "Failed to migrate legacy backup");
+                return;
+            }
+        }
 
-        if !backup_path.exists() {
-            tracing::warn!(
-                phase = "restore",
-                "No backup directory found, skipping restore (likely first install)"
+        // Resumes here if a previous migration was interrupted
+        let stamp = std::fs::metadata(&migrating)
+            .and_then(|m| m.modified())
+            .map(chrono::DateTime::<chrono::Local>::from)
+            .unwrap_or_else(|_| chrono::Local::now())
+            .format(STAMP_FORMAT)
+            .to_string();
+        let (name, target) = unique_snapshot_dir(&backup, &stamp);
+        let result =
+            std::fs::create_dir_all(&backup).and_then(|()| std::fs::rename(&migrating, &target));
+        match result {
+            Ok(()) => {
+                tracing::info!(operation = "recovery", name = %name, "Migrated legacy backup")
+            }
+            Err(e) => {
+                tracing::warn!(operation = "recovery", error = %e, "Failed to migrate legacy backup")
+            }
+        }
+    }
+
+    /// Move the install, backups and settings under `new_dir`, and leave a
+    /// redirect in `app_dir` so later launches use it.
+    ///
+    /// Transactional: the source is only removed once every copied file matches
+    /// its original's size. Any earlier failure deletes the partial copy and keeps
+    /// the current data dir. An install under a custom install root stays put.
+    /// Caller must hold the write lock.
+    pub async fn relocate_data_dir(
+        &self,
+        new_dir: PathBuf,
+        app_dir: &Path,
+        reporter: &InstallReporter,
+    ) -> Result<(), PobError> {
+        if self.portable {
+            return Err(PobError::InvalidDataDir(
+                "포터블 모드에서는 데이터 폴더를 이동할 수 없습니다".into(),
+            ));
+        }
+        let old_dir = self.data_dir();
+        if !new_dir.is_absolute() {
+            return Err(PobError::InvalidDataDir(format!(
+                "절대 경로가 아닙니다: {}",
+                new_dir.display()
+            )));
+        }
+        let old_canonical = std::fs::canonicalize(&old_dir).unwrap_or_else(|_| old_dir.clone());
+        let new_canonical = std::fs::canonicalize(&new_dir).unwrap_or_else(|_| new_dir.clone());
+        if new_canonical.starts_with(&old_canonical) {
+            return Err(PobError::InvalidDataDir(format!(
+                "현재 데이터 폴더와 같거나 그 하위 폴더입니다: {}",
+                new_dir.display()
+            )));
+        }
+
+        let entries: Vec<PathBuf> = [
+            self.install_path(),
+            self.previous_install_path(),
+            self.backup_dir(),
+            self.snapshots_dir(),
+            self.failed_downloads_dir(),
+            self.partial_downloads_dir(),
+            config_file_path(&old_dir),
+            stats_file_path(&old_dir),
+            backup_manifest_path(&old_dir),
+            install_state_path(&old_dir),
+            self.event_log_path(),
+        ]
+        .into_iter()
+        .filter(|path| path.exists())
+        .filter_map(|path| path.strip_prefix(&old_dir).ok().map(Path::to_path_buf))
+        .collect();
+
+        if let Some(existing) = entries.iter().find(|entry| new_dir.join(entry).exists()) {
+            return Err(PobError::InvalidDataDir(format!(
+                "대상 폴더에 이미 존재합니다: {}",
+                new_dir.join(existing).display()
+            )));
+        }
+
+        tracing::info!(
+            operation = "relocate",
+            from = %old_dir.display(),
+            to = %new_dir.display(),
+            entries = entries.len(),
+            "Relocating data dir"
+        );
+
+        let copied = {
+            let (src, dst, entries) = (old_dir.clone(), new_dir.clone(), entries.clone());
+            let reporter = reporter.clone();
+            tokio::task::spawn_blocking(move || {
+                copy_entries_verified(&src, &dst, &entries, InstallPhase::Relocating, &reporter)
+            })
+            .await?
+        };
+        let result = match copied {
+            Ok(()) => save_data_dir_redirect(app_dir, &new_dir).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            tracing::error!(operation = "relocate", error = %e, "Relocation failed, removing partial copy");
+            for entry in &entries {
+                remove_path(&new_dir.join(entry)).await;
+            }
+            reporter.report(
+                InstallPhase::Relocating,
+                InstallStatus::Failed {
+                    reason: e.to_string(),
+                },
             );
-            reporter.report(InstallPhase::Restoring, InstallStatus::Completed);
-            return Ok(());
+            return Err(e);
         }
 
-        let target_paths: Vec<PathBuf> = self.backup_targets();
+        *self.data_dir.write().unwrap() = new_dir;
 
-        for relative_path in target_paths {
-            let backup_target_path = backup_path.join(&relative_path);
-            if !backup_target_path.exists() {
-                tracing::debug!(phase = "restore", path = %relative_path.display(), "Backup target does not exist, skipping");
-                continue;
+        // The copy is already live; leftovers only cost disk space
+        for entry in &entries {
+            remove_path(&old_dir.join(entry)).await;
+        }
+
+        tracing::info!(operation = "relocate", "Data dir relocated");
+        reporter.report(
+            InstallPhase::Relocating,
+            InstallStatus::Completed { speed: None },
+        );
+        Ok(())
+    }
+
+    /// Move the install, the retained previous install and the backup under
+    /// `root`, e.g. onto a drive with more space, and keep them there from now
+    /// on. Passing the data dir restores the default. `root` must accept
+    /// writes. Anything moved is moved back if a later step fails. Not available
+    /// in portable mode. Caller must hold the write lock.
+    pub async fn set_install_root(
+        &self,
+        root: PathBuf,
+        reporter: &InstallReporter,
+    ) -> Result<(), PobError> {
+        if self.portable {
+            return Err(PobError::InvalidConfig(
+                "포터블 모드에서는 설치 폴더를 바꿀 수 없습니다".into(),
+            ));
+        }
+        if !root.is_absolute() {
+            return Err(PobError::InvalidConfig(format!(
+                "절대 경로가 아닙니다: {}",
+                root.display()
+            )));
+        }
+        let old_root = self.install_root();
+        if root == old_root {
+            return Ok(());
+        }
+        if self.is_pob_running() {
+            return Err(PobError::ProcessRunning);
+        }
+
+        let entries: Vec<PathBuf> = [
+            self.install_path(),
+            self.previous_install_path(),
+            self.backup_dir(),
+        ]
+        .into_iter()
+        .filter(|path| path.exists())
+        .filter_map(|path| path.strip_prefix(&old_root).ok().map(Path::to_path_buf))
+        .collect();
+        if let Some(existing) = entries.iter().find(|entry| root.join(entry).exists()) {
+            return Err(PobError::InvalidConfig(format!(
+                "대상 폴더에 이미 존재합니다: {}",
+                root.join(existing).display()
+            )));
+        }
+        let created = !root.exists();
+        self.fs.create_dir_all(&root).await?;
+        if let Err(e) = ensure_writable(&*self.fs, &root).await {
+            if created {
+                self.fs.remove_dir_all(&root).await.ok();
             }
+            return Err(e);
+        }
 
-            let restore_target_path = install_path.join(&relative_path);
+        tracing::info!(
+            operation = "install_root",
+            from = %old_root.display(),
+            to = %root.display(),
+            entries = entries.len(),
+            "Moving install root"
+        );
+        reporter.report(
+            InstallPhase::Relocating,
+            InstallStatus::Started {
+                total_size: None,
+                estimated_secs: None,
+            },
+        );
 
-            if backup_target_path.is_dir() {
-                async_copy_dir_recursive(&backup_target_path, &restore_target_path).await?;
-            } else {
-                if let Some(parent) = restore_target_path.parent() {
-                    tokio::fs::create_dir_all(parent).await?;
+        let mut moved = Vec::new();
+        let mut result = Ok(());
+        for entry in &entries {
+            let from = old_root.join(entry);
+            let to = root.join(entry);
+            if let Err(e) = self
+                .move_dir(&from, &to, InstallPhase::Relocating, reporter)
+                .await
+            {
+                result = Err(e);
+                break;
+            }
+            moved.push(entry);
+        }
+
+        // Re-read under the lock: settings saved while the files moved are kept
+        let _save = self.config_save.lock().await;
+        let mut config = self.config();
+        config.install_root =
+            (root != self.data_dir()).then(|| root.to_string_lossy().into_owned());
+        if result.is_ok() {
+            result = config.save(&config_file_path(&self.data_dir())).await;
+        }
+        if let Err(e) = result {
+            tracing::error!(operation = "install_root", error = %e, "Moving install root failed, moving back");
+            for entry in moved.into_iter().rev() {
+                let (from, to) = (root.join(entry), old_root.join(entry));
+                if let Err(e) = self
+                    .move_dir(&from, &to, InstallPhase::Relocating, reporter)
+                    .await
+                {
+                    tracing::error!(
+                        operation = "install_root",
+                        path = %root.join(entry).display(),
+                        error = %e,
+                        "Failed to move back, manual intervention required"
+                    );
                 }
-                tokio::fs::copy(&backup_target_path, &restore_target_path).await?;
             }
+            reporter.report(
+                InstallPhase::Relocating,
+                InstallStatus::Failed {
+                    reason: e.to_string(),
+                },
+            );
+            return Err(e);
         }
-        tracing::info!(phase = "restore", "Restore completed");
-        reporter.report(InstallPhase::Restoring, InstallStatus::Completed);
 
+        *self.config.write().unwrap() = config;
+        tracing::info!(operation = "install_root", "Install root moved");
+        reporter.report(
+            InstallPhase::Relocating,
+            InstallStatus::Completed { speed: None },
+        );
         Ok(())
     }
 
-    pub(crate) async fn save_version_info(&self, version: &PobVersion) -> Result<(), PobError> {
-        let path = self.pob_version_file_path();
-        let data = serde_json::to_string_pretty(version)?;
-        tokio::fs::write(&path, data).await?;
+    /// Install-relative user data paths preserved across installs, from
+    /// `backup_manifest.json` if present, else [`DEFAULT_BACKUP_TARGETS`].
+    pub fn load_backup_targets(&self) -> Vec<PathBuf> {
+        match BackupManifest::load(&backup_manifest_path(&self.data_dir())) {
+            Some(manifest) => manifest.paths(),
+            None => DEFAULT_BACKUP_TARGETS.iter().map(PathBuf::from).collect(),
+        }
+    }
+
+    /// Replace the backup targets. Takes effect from the next backup or restore.
+    pub async fn save_backup_targets(&self, targets: Vec<String>) -> Result<(), PobError> {
+        let manifest = BackupManifest::new(targets)?;
+        manifest
+            .save(&backup_manifest_path(&self.data_dir()))
+            .await?;
+        tracing::info!(operation = "backup_manifest", targets = ?manifest.targets, "Backup targets saved");
         Ok(())
     }
 
-    pub(crate) async fn rename(
+    /// Restore user data from the backup `name` instead of the newest one.
+    /// Files the backup doesn't have are left as they are.
+    pub async fn restore_from_backup(
         &self,
-        extracted: &Path,
-        install_dir: &Path,
+        name: &str,
         reporter: &InstallReporter,
     ) -> Result<(), PobError> {
-        tracing::info!(
-            phase = "rename",
-            from = %extracted.display(),
-            to = %install_dir.display(),
-            "rename() called"
-        );
+        if self.is_pob_running() {
+            return Err(PobError::ProcessRunning);
+        }
+        if !self.install_path().is_dir() {
+            return Err(PobError::NotInstalled);
+        }
+        // Only listed names, so `name` can't point outside the backup dir
+        let backup_dir = self.backup_dir();
+        if !backups_newest_first(&backup_dir).iter().any(|n| n == name) {
+            return Err(PobError::NoBackup);
+        }
+        self.restore_from(&backup_dir.join(name), reporter, &CancellationToken::new())
+            .await
+    }
 
+    /// Copy the backup at `backup_path` over the install. A cancel stops it
+    /// between entries, leaving whatever was already restored.
+    pub(crate) async fn restore_from(
+        &self,
+        backup_path: &Path,
+        reporter: &InstallReporter,
+        cancel_token: &CancellationToken,
+    ) -> Result<(), PobError> {
+        tracing::info!(phase = "restore", path = %backup_path.display(), "Starting restore from backup");
         reporter.report(
-            InstallPhase::Moving,
-            InstallStatus::Started { total_size: None },
-        );
-
-        // move existing to .old
-        let old = install_dir.with_extension("old");
-        tracing::debug!(
-            phase = "rename",
-            install_dir = %install_dir.display(),
-            exists = %install_dir.exists(),
-            old = %old.display(),
-            "Checking if install_dir exists"
+            InstallPhase::Restoring,
+            InstallStatus::Started {
+                total_size: None,
+                estimated_secs: None,
+            },
         );
 
-        if install_dir.exists() {
-            tracing::info!(phase = "rename", "Moving existing install to .old");
+        let install_path = self.install_path();
+        let target_paths: Vec<PathBuf> = self.load_backup_targets();
 
-            // Remove orphaned .old directory from previous failed installation
-            if old.exists() {
-                tracing::warn!(
-                    phase = "rename",
-                    path = %old.display(),
-                    "Removing orphaned .old directory from previous failed installation"
-                );
-                tokio::fs::remove_dir_all(&old).await?;
+        for relative_path in target_paths {
+            if cancel_token.is_cancelled() {
+                reporter.report(InstallPhase::Restoring, InstallStatus::Cancelled);
+                return Err(PobError::Cancelled);
             }
+            let backup_target_path = backup_path.join(&relative_path);
+            let metadata = match self.fs.metadata(&backup_target_path).await {
+                Ok(metadata) => metadata,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    tracing::debug!(phase = "restore", path = %relative_path.display(), "Backup target does not exist, skipping");
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
 
-            tokio::fs::rename(install_dir, &old).await?;
-            tracing::info!(phase = "rename", "Existing install moved to .old");
-        } else {
-            tracing::info!(
-                phase = "rename",
-                "No existing install, skipping .old rename"
-            );
-        }
+            let restore_target_path = install_path.join(&relative_path);
 
-        // move new in place
-        tracing::debug!(
-            phase = "rename",
-            from = %extracted.display(),
-            to = %install_dir.display(),
-            from_exists = %extracted.exists(),
-            "Attempting to rename extracted to install_dir"
+            if metadata.is_dir() {
+                let copied = async_copy_dir_recursive(
+                    &backup_target_path,
+                    &restore_target_path,
+                    cancel_token,
+                )
+                .await;
+                if let Err(e) = copied {
+                    let e = copy_error(cancel_token, e);
+                    if let PobError::Cancelled = e {
+                        reporter.report(InstallPhase::Restoring, InstallStatus::Cancelled);
+                    }
+                    return Err(e);
+                }
+            } else {
+                if let Some(parent) = restore_target_path.parent() {
+                    self.fs.create_dir_all(parent).await?;
+                }
+                self.fs
+                    .copy(&backup_target_path, &restore_target_path)
+                    .await?;
+            }
+        }
+        tracing::info!(phase = "restore", "Restore completed");
+        reporter.report(
+            InstallPhase::Restoring,
+            InstallStatus::Completed { speed: None },
         );
 
-        // NOTE: Cross-device fallback은 현재 불필요 (모두 app_local_data_dir 내부)
-        // 향후 커스텀 설치 경로 지원 시 async_copy_dir_recursive fallback 추가 필요
-        tokio::fs::rename(extracted, install_dir).await?;
+        Ok(())
+    }
+
+    /// Compare the latest release against the installed game files. Only the
+    /// archive's central directory is fetched when the server honours Range
+    /// requests; otherwise the release is downloaded into `temp_dir`.
+    /// User-data paths are excluded on both sides.
+    pub async fn diff_against_latest(
+        &self,
+        temp_dir: &Path,
+        cancel_token: CancellationToken,
+        reporter: &InstallReporter,
+    ) -> Result<InstallDiff, PobError> {
+        let install_path = self.install_path();
+        if !install_path.is_dir() {
+            return Err(PobError::NotInstalled);
+        }
+
+        let file_info = self.fetch_latest_file(false).await?;
+        tracing::info!(operation = "diff", file = %file_info.name, "Diffing install against latest release");
+
+        // The central directory is enough to diff, so try fetching only that first
+        let directory =
+            until_cancelled(&cancel_token, self.fetch_central_directory(&file_info.id)).await;
+        match directory {
+            Ok(Some(entries)) => {
+                let targets = self.load_backup_targets();
+                let reporter = reporter.clone();
+                return tokio::task::spawn_blocking(move || {
+                    diff_entries(&entries, &install_path, &targets, &cancel_token, &reporter)
+                })
+                .await?;
+            }
+            Ok(None) => {
+                tracing::info!(
+                    operation = "diff",
+                    "Central directory unavailable by range, downloading archive"
+                );
+            }
+            Err(PobError::Cancelled) => return Err(PobError::Cancelled),
+            Err(e) => {
+                tracing::warn!(operation = "diff", error = %e, "Failed to fetch central directory, downloading archive");
+            }
+        }
+
+        let zip_path = temp_dir.join(&file_info.name).with_extension("zip");
+
+        let result = async {
+            self.download_with_progress(&file_info.id, &zip_path, cancel_token.clone(), reporter)
+                .await?;
+
+            let zip_path = zip_path.clone();
+            let targets = self.load_backup_targets();
+            let reporter = reporter.clone();
+            tokio::task::spawn_blocking(move || {
+                diff_install(&zip_path, &install_path, &targets, &cancel_token, &reporter)
+            })
+            .await?
+        }
+        .await;
+
+        self.fs.remove_file(&zip_path).await.ok();
+        result
+    }
+
+    /// Fetch and parse just the central directory at the end of `file_id` with
+    /// Range requests. `None` when ranges aren't honoured or the archive needs ZIP64.
+    async fn fetch_central_directory(
+        &self,
+        file_id: &str,
+    ) -> Result<Option<Vec<ArchiveEntry>>, PobError> {
+        // One connection; the caller's `until_cancelled` abandons the wait on cancel
+        let _handles = self.acquire_handles(1, &CancellationToken::new()).await?;
+        let info = self.client.get_file_download_info(file_id).await?;
+        let len = info.content_length;
+        if !info.accepts_ranges || len == 0 {
+            return Ok(None);
+        }
+
+        let tail_start = len.saturating_sub(EOCD_SEARCH_BYTES);
+        let Some(tail) = self.fetch_range(file_id, tail_start, len - 1).await? else {
+            return Ok(None);
+        };
+        let Some((offset, size)) = find_central_directory(&tail) else {
+            return Ok(None);
+        };
+        if offset + size > len {
+            return Ok(None);
+        }
+
+        // Usually the whole directory already came with the tail
+        let directory = if offset >= tail_start {
+            let start = (offset - tail_start) as usize;
+            tail[start..start + size as usize].to_vec()
+        } else {
+            match self.fetch_range(file_id, offset, offset + size - 1).await? {
+                Some(directory) => directory,
+                None => return Ok(None),
+            }
+        };
+        tracing::debug!(
+            operation = "diff",
+            archive_bytes = len,
+            directory_bytes = size,
+            "Fetched central directory"
+        );
+        Ok(parse_central_directory(&directory))
+    }
+
+    /// Bytes `start..=end` of `file_id`, `None` if the server ignored the range.
+    async fn fetch_range(
+        &self,
+        file_id: &str,
+        start: u64,
+        end: u64,
+    ) -> Result<Option<Vec<u8>>, PobError> {
+        let res = self.client.get_file_range(file_id, start, end).await?;
+        // A 200 would be the whole archive; don't read it
+        if res.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+            return Ok(None);
+        }
+        let body = res.bytes().await?;
+        Ok((body.len() as u64 == end - start + 1).then(|| body.to_vec()))
+    }
+
+    /// Check the install against the manifest recorded at extraction, without
+    /// downloading anything. See [`verify_manifest`] for quick vs `full`.
+    pub async fn verify_install(
+        &self,
+        full: bool,
+        cancel_token: CancellationToken,
+        reporter: &InstallReporter,
+    ) -> Result<InstallDiff, PobError> {
+        let install_path = self.install_path();
+        if !install_path.is_dir() {
+            return Err(PobError::NotInstalled);
+        }
+
+        let targets = self.load_backup_targets();
+        let reporter = reporter.clone();
+        tokio::task::spawn_blocking(move || {
+            let manifest =
+                InstallManifest::load(&install_path)?.ok_or(PobError::ManifestMissing)?;
+            verify_manifest(
+                &install_path,
+                &manifest,
+                full,
+                &targets,
+                &cancel_token,
+                &reporter,
+            )
+        })
+        .await?
+    }
+
+    /// Check that the install has its exe, a readable version file and the
+    /// folders every release ships, without reading file contents.
+    pub async fn check_integrity(&self) -> Result<IntegrityReport, PobError> {
+        let install_path = self.install_path();
+        if !install_path.is_dir() {
+            return Err(PobError::NotInstalled);
+        }
+
+        let version = self.installed_version().await.unwrap_or_else(|e| {
+            tracing::warn!(operation = "integrity", error = %e, "Version file is unreadable");
+            None
+        });
+        let missing_dirs = REQUIRED_DIRS
+            .iter()
+            .filter(|dir| !install_path.join(dir).is_dir())
+            .map(|dir| dir.to_string())
+            .collect();
+        Ok(IntegrityReport {
+            exe_exists: self.exe_path().is_file(),
+            version_file_valid: version.is_some(),
+            missing_dirs,
+            file_id: version
+                .map(|v| v.file_id)
+                .filter(|file_id| !file_id.is_empty()),
+        })
+    }
+
+    /// Release to reinstall when repairing: the one recorded in the version
+    /// file, looked up in the release folder.
+    pub async fn repair_target(&self) -> Result<GoogleDriveFileInfo, PobError> {
+        let report = self.check_integrity().await?;
+        let file_id = report.file_id.ok_or(PobError::UnknownInstalledRelease)?;
+
+        self.ensure_active()?;
+        let files = self.client.fetch_all_files(&self.folder_id()).await?;
+        files
+            .into_iter()
+            .find(|file| file.id == file_id)
+            .ok_or(PobError::NotFoundFromDrive(file_id))
+    }
+
+    /// Free at least `target_bytes` by pruning, cheapest to lose first: task temp
+    /// dirs in `temp_dir`, interrupted backup copies, all but the newest log in
+    /// `log_dir`, older backups, then the retained previous install. The current
+    /// install and newest backup are never touched. Caller must hold the write lock.
+    pub async fn reclaim_space(
+        &self,
+        target_bytes: u64,
+        temp_dir: PathBuf,
+        log_dir: Option<PathBuf>,
+    ) -> Result<ReclaimReport, PobError> {
+        let backup = self.backup_dir();
+        let previous = self.previous_install_path();
+        let partial = self.partial_downloads_dir();
+
+        let report = tokio::task::spawn_blocking(move || {
+            let mut candidates = task_temp_dirs(&temp_dir);
+            candidates.push(partial);
+            candidates.push(backup.with_extension("new"));
+            candidates.push(backup.with_extension("old"));
+            candidates.extend(staged_backups(&backup));
+            if let Some(log_dir) = log_dir {
+                candidates.extend(surplus_logs(&log_dir));
+            }
+            let older_backups = backups_newest_first(&backup).into_iter().skip(1).rev();
+            candidates.extend(older_backups.map(|name| backup.join(name)));
+            if !holds_unbacked_data(&previous) {
+                candidates.push(previous);
+            }
+            reclaim(&candidates, target_bytes)
+        })
+        .await?;
+
+        tracing::info!(
+            operation = "reclaim",
+            target_bytes,
+            freed_bytes = report.freed_bytes,
+            removed = report.removed.len(),
+            "Disk space reclaim finished"
+        );
+        Ok(report)
+    }
+
+    /// Rename the directory `from` to `to`, copying then removing it when they
+    /// are on different volumes (e.g. a custom install root on another drive).
+    /// The copy reports byte progress as `phase`; a rename is instant and doesn't.
+    async fn move_dir(
+        &self,
+        from: &Path,
+        to: &Path,
+        phase: InstallPhase,
+        reporter: &InstallReporter,
+    ) -> Result<(), PobError> {
+        match self.fs.rename(from, to).await {
+            Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+                tracing::info!(
+                    from = %from.display(),
+                    to = %to.display(),
+                    "Cross-device move, copying instead"
+                );
+                let copied = {
+                    let (src, dst) = (from.to_path_buf(), to.to_path_buf());
+                    let reporter = reporter.clone();
+                    // The empty entry is `from` itself
+                    tokio::task::spawn_blocking(move || {
+                        copy_entries_verified(&src, &dst, &[PathBuf::new()], phase, &reporter)
+                    })
+                    .await?
+                };
+                if let Err(e) = copied {
+                    self.fs.remove_dir_all(to).await.ok();
+                    return Err(e);
+                }
+                self.fs
+                    .remove_dir_all(from)
+                    .await
+                    .map_err(|e| PobError::from_locked_io(e, from))
+            }
+            result => result.map_err(|e| PobError::from_locked_io(e, from)),
+        }
+    }
+
+    /// Move a file between the temp dir and the data dir, which may sit on
+    /// different volumes where rename fails.
+    async fn move_file(&self, from: &Path, to: &Path) -> std::io::Result<()> {
+        if self.fs.rename(from, to).await.is_ok() {
+            return Ok(());
+        }
+        if let Err(e) = self.fs.copy(from, to).await {
+            self.fs.remove_file(to).await.ok();
+            return Err(e);
+        }
+        self.fs.remove_file(from).await
+    }
+
+    /// Move a fully downloaded archive left in `temp_dir` by a failed
+    /// [`Self::install`] to `failed/<timestamp>.zip`, if `keep_download_on_failure`
+    /// is on. Only the newest few are kept. Returns where it was moved.
+    pub async fn keep_failed_download(&self, temp_dir: &Path, file_name: &str) -> Option<PathBuf> {
+        if !self.config().keep_download_on_failure {
+            return None;
+        }
+        // Failed downloads remove their partial file, so anything left here is complete
+        let source = Self::download_path(temp_dir, file_name);
+        if !source.is_file() {
+            return None;
+        }
+
+        let failed_dir = self.failed_downloads_dir();
+        if let Err(e) = self.fs.create_dir_all(&failed_dir).await {
+            tracing::warn!(operation = "keep_download", error = %e, "Failed to create failed downloads directory");
+            return None;
+        }
+        let kept = failed_dir.join(format!(
+            "{}.zip",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ));
+
+        if let Err(e) = self.move_file(&source, &kept).await {
+            tracing::warn!(operation = "keep_download", error = %e, "Failed to keep downloaded archive");
+            return None;
+        }
+        tracing::info!(operation = "keep_download", path = %kept.display(), "Kept downloaded archive of failed install");
+
+        let stale = tokio::task::spawn_blocking(move || {
+            logs_newest_first(&failed_dir)
+                .into_iter()
+                .skip(KEPT_FAILED_DOWNLOADS)
+                .collect::<Vec<_>>()
+        })
+        .await
+        .unwrap_or_default();
+        for path in stale {
+            tracing::debug!(operation = "keep_download", path = %path.display(), "Removing older kept archive");
+            self.fs.remove_file(&path).await.ok();
+        }
+
+        Some(kept)
+    }
+
+    /// Read the newest backup back without restoring it, so users can tell
+    /// whether it is usable before they need it.
+    pub async fn check_backup(
+        &self,
+        cancel_token: CancellationToken,
+        reporter: &InstallReporter,
+    ) -> Result<BackupCheck, PobError> {
+        let backup_path = self.latest_backup().ok_or(PobError::NoBackup)?;
+
+        let reporter = reporter.clone();
+        tokio::task::spawn_blocking(move || check_backup(&backup_path, &cancel_token, &reporter))
+            .await?
+    }
+
+    /// Copy the live install's user data into a new labeled snapshot, leaving
+    /// the install and the rolling backup untouched.
+    pub async fn create_manual_backup(
+        &self,
+        label: Option<String>,
+        reporter: &InstallReporter,
+    ) -> Result<BackupSnapshot, PobError> {
+        let install_path = self.install_path();
+        if !install_path.is_dir() {
+            return Err(PobError::NotInstalled);
+        }
+
+        reporter.report(
+            InstallPhase::BackingUp,
+            InstallStatus::Started {
+                total_size: None,
+                estimated_secs: None,
+            },
+        );
+
+        let snapshots_dir = self.snapshots_dir();
+        let stamp = chrono::Local::now().format(STAMP_FORMAT).to_string();
+        let (id, snapshot_path) = unique_snapshot_dir(&snapshots_dir, &stamp);
+        // Copy into `<id>.new` so a failed snapshot never shows up as complete
+        let staging = snapshot_path.with_extension("new");
+
+        let result = async {
+            if staging.exists() {
+                self.fs.remove_dir_all(&staging).await?;
+            }
+            self.fs.create_dir_all(&staging).await?;
+            let entries = self
+                .copy_backup_targets(&install_path, &staging, &CancellationToken::new())
+                .await?;
+
+            let snapshot = BackupSnapshot {
+                id,
+                label: normalize_label(label),
+                version: self.installed_version().await?.map(|v| v.version),
+                created_at: chrono::Utc::now().to_rfc3339(),
+                entries,
+            };
+            snapshot.save(&staging).await?;
+            self.fs.rename(&staging, &snapshot_path).await?;
+            Ok::<_, PobError>(snapshot)
+        }
+        .await;
+
+        match &result {
+            Ok(snapshot) => {
+                tracing::info!(operation = "snapshot", id = %snapshot.id, entries = snapshot.entries, "Created manual backup");
+                reporter.report(
+                    InstallPhase::BackingUp,
+                    InstallStatus::Completed { speed: None },
+                );
+            }
+            Err(e) => {
+                tracing::error!(operation = "snapshot", error = %e, "Manual backup failed");
+                if let Err(cleanup) = self.fs.remove_dir_all(&staging).await {
+                    tracing::warn!(operation = "snapshot", error = %cleanup, "Failed to remove partial snapshot");
+                }
+                reporter.report(
+                    InstallPhase::BackingUp,
+                    InstallStatus::Failed {
+                        reason: e.to_string(),
+                    },
+                );
+            }
+        }
+        result
+    }
+
+    /// Manual snapshots from [`Self::create_manual_backup`], newest first.
+    pub async fn list_snapshots(&self) -> Result<Vec<BackupSnapshot>, PobError> {
+        let snapshots_dir = self.snapshots_dir();
+        Ok(tokio::task::spawn_blocking(move || snapshots_newest_first(&snapshots_dir)).await?)
+    }
+
+    /// Restore user data from the manual snapshot `id`. Files the snapshot
+    /// doesn't have are left as they are.
+    pub async fn restore_snapshot(
+        &self,
+        id: &str,
+        reporter: &InstallReporter,
+    ) -> Result<(), PobError> {
+        if self.is_pob_running() {
+            return Err(PobError::ProcessRunning);
+        }
+        if !self.install_path().is_dir() {
+            return Err(PobError::NotInstalled);
+        }
+        // Only listed ids, so `id` can't point outside the snapshots dir
+        let snapshots = self.list_snapshots().await?;
+        if !snapshots.iter().any(|snapshot| snapshot.id == id) {
+            return Err(PobError::NoBackup);
+        }
+        self.restore_from(
+            &self.snapshots_dir().join(id),
+            reporter,
+            &CancellationToken::new(),
+        )
+        .await
+    }
+
+    /// Check the structure and safety of any ZIP, e.g. a mirror download, the
+    /// way extraction would but without writing anything.
+    pub async fn inspect_archive(&self, zip_path: PathBuf) -> Result<ArchiveReport, PobError> {
+        // Inspection is never cancelled; the token only satisfies `acquire_handles`
+        let _handle = self.acquire_handles(1, &CancellationToken::new()).await?;
+        tokio::task::spawn_blocking(move || inspect_archive(&zip_path)).await?
+    }
+
+    /// Package settings, version info, the last operation's event log and the
+    /// newest logs from `log_dir` into a ZIP at `dest` for bug reports.
+    pub async fn export_diagnostics(
+        &self,
+        dest: PathBuf,
+        log_dir: Option<PathBuf>,
+    ) -> Result<PathBuf, PobError> {
+        let data_dir = self.data_dir();
+        let files = vec![
+            DiagnosticFile::new("config.json", config_file_path(&data_dir)),
+            DiagnosticFile::new("install_stats.json", stats_file_path(&data_dir)),
+            DiagnosticFile::new("backup_manifest.json", backup_manifest_path(&data_dir)),
+            DiagnosticFile::new("pob_version.json", self.version_file_path()),
+            DiagnosticFile::new("last_install.jsonl", self.event_log_path()),
+        ];
+
+        let bundle = dest.clone();
+        tokio::task::spawn_blocking(move || write_bundle(&bundle, &files, log_dir.as_deref()))
+            .await??;
+        tracing::info!(operation = "diagnostics", path = %dest.display(), "Exported diagnostics");
+        Ok(dest)
+    }
+
+    /// Recreate `pob_version.json` for an intact install whose record was lost.
+    ///
+    /// `installed_at` becomes now. `file_id` is taken from `latest` only when that
+    /// release carries the same version, and is left empty otherwise.
+    pub async fn rebuild_version_info(
+        &self,
+        version: &str,
+        latest: Option<&GoogleDriveFileInfo>,
+    ) -> Result<PobVersion, PobError> {
+        let version = validate_version(version)?;
+        if !self.exe_path().is_file() {
+            return Err(PobError::NotInstalled);
+        }
+
+        let file_id = latest
+            .filter(|info| parse_from_name(&info.name).is_ok_and(|v| v == version))
+            .map(|info| info.id.clone())
+            .unwrap_or_default();
+        let record = PobVersion {
+            version,
+            installed_at: chrono::Utc::now().to_rfc3339(),
+            file_id,
+        };
+
+        self.save_version_info(&record).await?;
+        tracing::info!(
+            operation = "rebuild_version",
+            version = %record.version,
+            file_id = %record.file_id,
+            "Rebuilt version info"
+        );
+        Ok(record)
+    }
+
+    /// Write the version file via a temp file and rename, so concurrent
+    /// [`Self::installed_version`] calls never see it half-written.
+    pub(crate) async fn save_version_info(&self, version: &PobVersion) -> Result<(), PobError> {
+        let path = self.pob_version_file_path();
+        let tmp = path.with_extension("json.tmp");
+        let data = serde_json::to_string_pretty(version)?;
+        tokio::fs::write(&tmp, data).await?;
+        if let Err(e) = self.fs.rename(&tmp, &path).await {
+            self.fs.remove_file(&tmp).await.ok();
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn rename(
+        &self,
+        extracted: &Path,
+        install_dir: &Path,
+        reporter: &InstallReporter,
+    ) -> Result<(), PobError> {
+        tracing::info!(
+            phase = "rename",
+            from = %extracted.display(),
+            to = %install_dir.display(),
+            "rename() called"
+        );
+
+        reporter.report(
+            InstallPhase::Moving,
+            InstallStatus::Started {
+                total_size: None,
+                estimated_secs: None,
+            },
+        );
+
+        // move existing to .old
+        let old = install_dir.with_extension("old");
+        tracing::debug!(
+            phase = "rename",
+            install_dir = %install_dir.display(),
+            exists = %install_dir.exists(),
+            old = %old.display(),
+            "Checking if install_dir exists"
+        );
+
+        if install_dir.exists() {
+            tracing::info!(phase = "rename", "Moving existing install to .old");
+
+            // Remove orphaned .old directory from previous failed installation
+            if old.exists() {
+                tracing::warn!(
+                    phase = "rename",
+                    path = %old.display(),
+                    "Removing orphaned .old directory from previous failed installation"
+                );
+                self.fs
+                    .remove_dir_all(&old)
+                    .await
+                    .map_err(|e| PobError::from_locked_io(e, &old))?;
+            }
+
+            self.fs
+                .rename(install_dir, &old)
+                .await
+                .map_err(|e| PobError::from_locked_io(e, install_dir))?;
+            tracing::info!(phase = "rename", "Existing install moved to .old");
+        } else {
+            tracing::info!(
+                phase = "rename",
+                "No existing install, skipping .old rename"
+            );
+        }
+
+        // move new in place
+        tracing::debug!(
+            phase = "rename",
+            from = %extracted.display(),
+            to = %install_dir.display(),
+            from_exists = %extracted.exists(),
+            "Attempting to rename extracted to install_dir"
+        );
+
+        self.move_dir(extracted, install_dir, InstallPhase::Moving, reporter)
+            .await?;
         tracing::info!(
             phase = "rename",
             install_dir = %install_dir.display(),
@@ -520,307 +2491,3101 @@ impl PobManager {
             "Rename completed"
         );
 
-        reporter.report(InstallPhase::Moving, InstallStatus::Completed);
+        reporter.report(
+            InstallPhase::Moving,
+            InstallStatus::Completed { speed: None },
+        );
+
+        Ok(())
+    }
+}
+
+async fn read_version_file(path: &Path) -> Result<Option<PobVersion>, PobError> {
+    // Read directly rather than checking first: the file can vanish in between (e.g. mid-swap)
+    let data = match tokio::fs::read_to_string(path).await {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    Ok(Some(serde_json::from_str(&data)?))
+}
+
+/// Free bytes on the volume holding `path`, `None` if no mounted disk contains it.
+fn available_space(path: &Path) -> Option<u64> {
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
+/// Fail with [`PobError::InsufficientSpace`] unless `required` bytes fit on
+/// the volume holding `path`. Passes when the free space can't be determined.
+fn check_free_space(path: &Path, required: u64) -> Result<(), PobError> {
+    match available_space(path) {
+        Some(available) if available < required => {
+            tracing::error!(
+                path = %path.display(),
+                required,
+                available,
+                "Not enough free space"
+            );
+            Err(PobError::InsufficientSpace {
+                required,
+                available,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Whether `install` is a retained install carrying the only copy of its user data.
+fn holds_unbacked_data(install: &Path) -> bool {
+    install.join(UNBACKED_MARKER).exists()
+}
+
+/// File created and removed in a folder to check that it accepts writes
+const WRITE_PROBE: &str = ".exile_write_test";
+
+/// Fail with [`PobError::DirNotWritable`] unless a file can be written in `dir`,
+/// so a read-only target is rejected before anything is moved there.
+async fn ensure_writable(fs: &dyn Fs, dir: &Path) -> Result<(), PobError> {
+    let probe = dir.join(WRITE_PROBE);
+    if let Err(e) = fs.write(&probe, b"").await {
+        tracing::warn!(path = %dir.display(), error = %e, "Folder is not writable");
+        return Err(PobError::DirNotWritable(dir.display().to_string()));
+    }
+    fs.remove_file(&probe).await.ok();
+    Ok(())
+}
+
+/// Sidecar of a `.new` extract kept after a failed backup, holding the Drive id
+/// of its release so a retry of that release can skip download and extraction.
+fn staged_release_path(extract_dir: &Path) -> PathBuf {
+    extract_dir.with_extension("new.release")
+}
+
+/// Sidecar of a partial download: the size of the file it is a prefix of.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PartialMeta {
+    total_size: u64,
+}
+
+fn partial_meta_path(partial: &Path) -> PathBuf {
+    partial.with_extension("part.meta")
+}
+
+/// Recorded file size of a partial download, if it has a readable sidecar.
+async fn read_partial_meta(path: &Path) -> Option<u64> {
+    let data = tokio::fs::read(path).await.ok()?;
+    serde_json::from_slice::<PartialMeta>(&data)
+        .ok()
+        .map(|meta| meta.total_size)
+}
+
+/// Await a step that can't observe `cancel_token` itself, giving up as soon as it fires.
+pub(crate) async fn until_cancelled<T>(
+    cancel_token: &CancellationToken,
+    step: impl Future<Output = Result<T, PobError>>,
+) -> Result<T, PobError> {
+    tokio::select! {
+        biased;
+        _ = cancel_token.cancelled() => Err(PobError::Cancelled),
+        result = step => result,
+    }
+}
+
+/// Drive IDs are URL-safe base64-ish; anything else would be spliced into the folder URL.
+fn validate_folder_id(folder_id: &str) -> Result<(), PobError> {
+    if folder_id.is_empty()
+        || !folder_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(PobError::InvalidConfig(format!(
+            "Google Drive 폴더 ID가 올바르지 않습니다: {folder_id}"
+        )));
+    }
+    Ok(())
+}
+
+/// Percent of `total` uncompressed bytes extracted. An archive of only empty
+/// entries has nothing to wait on, so it counts as done.
+fn byte_percent(done: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 100.0;
+    }
+    (done as f64 / total as f64 * 100.0).min(100.0)
+}
+
+/// Average rate of `bytes` over `elapsed` in megabits per second, for logs.
+fn megabits_per_sec(bytes: u64, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64();
+    if secs == 0.0 {
+        return 0.0;
+    }
+    bytes as f64 * 8.0 / secs / 1_000_000.0
+}
+
+/// `io::copy` that calls `on_progress` with the bytes written so far after each chunk.
+fn copy_with_progress(
+    reader: &mut impl std::io::Read,
+    writer: &mut impl std::io::Write,
+    mut on_progress: impl FnMut(u64),
+) -> std::io::Result<u64> {
+    let mut buf = vec![0u8; EXTRACT_CHUNK_SIZE];
+    let mut written = 0u64;
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => return Ok(written),
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        writer.write_all(&buf[..n])?;
+        written += n as u64;
+        on_progress(written);
+    }
+}
+
+/// Whether an install-relative path is (or lives under) one of the user-data `targets`.
+pub(crate) fn is_user_data_path(targets: &[PathBuf], path: &Path) -> bool {
+    targets.iter().any(|target| path.starts_with(target))
+}
+
+/// Copy `entries` (relative to `src_root`) into `dst_root` with byte-based
+/// progress as `phase`, then check each copy's size against its source.
+fn copy_entries_verified(
+    src_root: &Path,
+    dst_root: &Path,
+    entries: &[PathBuf],
+    phase: InstallPhase,
+    reporter: &InstallReporter,
+) -> Result<(), PobError> {
+    let mut dirs = Vec::new();
+    let mut files = Vec::new();
+    for entry in entries {
+        collect_relocation_entries(src_root, entry, &mut dirs, &mut files)?;
+    }
+    let total_bytes: u64 = files.iter().map(|(_, len)| len).sum();
+
+    reporter.report(
+        phase,
+        InstallStatus::Started {
+            total_size: NonZeroU32::new(total_bytes as u32),
+            estimated_secs: None,
+        },
+    );
+
+    for dir in &dirs {
+        std::fs::create_dir_all(dst_root.join(dir))?;
+    }
+
+    let mut copied = 0u64;
+    let mut last_report = Instant::now();
+    for (file, len) in &files {
+        let dst = dst_root.join(file);
+        if let Some(parent) = dst.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(src_root.join(file), &dst)?;
+        copied += len;
+
+        if total_bytes > 0
+            && last_report.elapsed() >= Duration::from_millis(PROGRESS_REPORT_INTERVAL_MS)
+        {
+            let percent = copied as f64 / total_bytes as f64 * 100.0;
+            reporter.report(phase, InstallStatus::in_progress(percent));
+            last_report = Instant::now();
+        }
+    }
+
+    for (file, len) in &files {
+        let copied_len = std::fs::metadata(dst_root.join(file))?.len();
+        if copied_len != *len {
+            return Err(PobError::RelocateFailed(format!(
+                "복사본 크기가 일치하지 않습니다: {} ({} != {})",
+                file.display(),
+                copied_len,
+                len
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Walk `entry` (relative to `root`), collecting directories and files with sizes.
+/// Directory symlinks/junctions are skipped, matching [`async_copy_dir_recursive`].
+fn collect_relocation_entries(
+    root: &Path,
+    entry: &Path,
+    dirs: &mut Vec<PathBuf>,
+    files: &mut Vec<(PathBuf, u64)>,
+) -> Result<(), PobError> {
+    let path = root.join(entry);
+    let mut metadata = std::fs::symlink_metadata(&path)?;
+    if metadata.is_symlink() {
+        match std::fs::metadata(&path) {
+            Ok(target) if target.is_file() => metadata = target,
+            _ => {
+                tracing::warn!(
+                    operation = "relocate",
+                    path = %path.display(),
+                    "Skipping directory or dangling symlink"
+                );
+                return Ok(());
+            }
+        }
+    }
+
+    if metadata.is_dir() {
+        dirs.push(entry.to_path_buf());
+        for child in std::fs::read_dir(&path)? {
+            let child = child?;
+            collect_relocation_entries(root, &entry.join(child.file_name()), dirs, files)?;
+        }
+    } else {
+        files.push((entry.to_path_buf(), metadata.len()));
+    }
+    Ok(())
+}
+
+/// Best-effort removal of a file or directory tree.
+async fn remove_path(path: &Path) {
+    let result = if path.is_dir() {
+        tokio::fs::remove_dir_all(path).await
+    } else {
+        tokio::fs::remove_file(path).await
+    };
+    if let Err(e) = result
+        && e.kind() != std::io::ErrorKind::NotFound
+    {
+        tracing::warn!(
+            operation = "relocate",
+            path = %path.display(),
+            error = %e,
+            "Failed to remove path"
+        );
+    }
+}
+
+/// `Cancelled` for a copy that `cancel_token` interrupted, otherwise `e` as is.
+fn copy_error(cancel_token: &CancellationToken, e: std::io::Error) -> PobError {
+    if cancel_token.is_cancelled() && e.kind() == std::io::ErrorKind::Interrupted {
+        PobError::Cancelled
+    } else {
+        e.into()
+    }
+}
+
+/// Outcome of [`PobManager::recover_backup_swap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackupRecovery {
+    /// Nothing to do
+    Intact,
+    /// Stale `backup.new`/`backup.old` or staged `backup/<name>.new` were removed;
+    /// `backup` was already valid
+    DiscardedLeftovers,
+    /// `backup` was missing and `backup.old` was promoted in its place
+    PromotedOld,
+    /// Promotion failed; directories were left untouched for manual recovery
+    Failed,
+}
+
+/// Decode a ZIP entry name, preferring UTF-8 over the CP437 fallback.
+///
+/// `zip` decodes entries without the UTF-8 flag (bit 11) as CP437, which is
+/// right for legacy packers but turns UTF-8 names from packers that omit the
+/// flag into mojibake. Raw bytes that are valid UTF-8 are taken as-is; CP437
+/// bytes above 0x7F practically never form valid UTF-8 sequences.
+pub(crate) fn decode_entry_name<'a, R: std::io::Read + ?Sized>(
+    file: &'a zip::read::ZipFile<'_, R>,
+) -> &'a str {
+    std::str::from_utf8(file.name_raw()).unwrap_or(file.name())
+}
+
+/// Characters of CP437 bytes 0x80-0xFF; the lower half is ASCII.
+const CP437_HIGH: &str = "ÇüéâäàåçêëèïîìÄÅÉæÆôöòûùÿÖÜ¢£¥₧ƒáíóúñÑªº¿⌐¬½¼¡«»░▒▓│┤╡╢╖╕╣║╗╝╜╛┐└┴┬├─┼╞╟╚╔╩╦╠═╬╧╨╤╥╙╘╒╓╫╪┘┌█▄▌▐▀αßΓπΣσµτΦΘΩδ∞φε∩≡±≥≤⌠⌡÷≈°∙·√ⁿ²■\u{a0}";
+
+/// Decode raw entry name bytes read without `zip`, like [`decode_entry_name`]:
+/// UTF-8 when valid, else CP437.
+pub(crate) fn decode_raw_entry_name(raw: &[u8]) -> String {
+    match std::str::from_utf8(raw) {
+        Ok(name) => name.to_string(),
+        Err(_) => raw
+            .iter()
+            .map(|&b| match b {
+                0..=0x7F => char::from(b),
+                _ => CP437_HIGH.chars().nth(usize::from(b - 0x80)).unwrap(),
+            })
+            .collect(),
+    }
+}
+
+/// Turn a decoded entry name into a relative path that stays inside the
+/// extraction root, mirroring [`zip::read::ZipFile::enclosed_name`].
+pub(crate) fn enclosed_entry_path(name: &str) -> Option<PathBuf> {
+    if name.contains('\0') {
+        return None;
+    }
+    let mut parts: Vec<&str> = Vec::new();
+    for part in name.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            // Drive prefixes and NTFS alternate streams
+            _ if part.contains(':') => return None,
+            _ => parts.push(part),
+        }
+    }
+    Some(parts.iter().collect())
+}
+
+/// Detect if ZIP has nested directory structure (e.g., PoeCharm/POE1 POB/...)
+/// Returns the prefix to skip, or None if structure is flat
+pub(crate) fn detect_nested_structure(
+    archive: &mut zip::ZipArchive<std::fs::File>,
+) -> Result<Option<PathBuf>, PobError> {
+    let mut names = Vec::with_capacity(archive.len());
+    for i in 0..archive.len() {
+        names.push(decode_entry_name(&archive.by_index_raw(i)?).to_string());
+    }
+    nested_prefix(names.iter().map(String::as_str))
+}
+
+/// [`detect_nested_structure`] over already-decoded entry names.
+pub(crate) fn nested_prefix<'a>(
+    names: impl IntoIterator<Item = &'a str>,
+) -> Result<Option<PathBuf>, PobError> {
+    const REQUIRED: &[&str] = &["POE1 POB/", "POE2 POB/", "Data/"];
+
+    // Check first occurrence of any required folder
+    for name in names {
+        for &required_folder in REQUIRED {
+            if let Some(pos) = name.find(required_folder) {
+                if pos == 0 {
+                    // "POE1 POB/..." - top level, OK
+                    tracing::info!(
+                        phase = "extract",
+                        "ZIP structure validated: top-level folders found"
+                    );
+                    return Ok(None);
+                } else {
+                    // "PoeCharm/POE1 POB/..." - nested structure
+                    let prefix = &name[..pos];
+                    let prefix = prefix.trim_end_matches('/');
+                    tracing::warn!(
+                        phase = "extract",
+                        prefix = %prefix,
+                        example_file = %name,
+                        "Detected nested directory structure in ZIP"
+                    );
+                    return Ok(Some(PathBuf::from(prefix)));
+                }
+            }
+        }
+    }
+
+    Err(PobError::ExtractFailed(
+        "ZIP does not contain required folders (POE1 POB, POE2 POB, Data)".into(),
+    ))
+}
+
+// ============================================================================
+// Install Context & Workflow
+// ============================================================================
+
+/// Tracks which stages of installation have been completed for rollback purposes
+#[derive(Debug, Default)]
+struct InstallContext {
+    temp_zip_path: Option<PathBuf>,
+    extract_dir: Option<PathBuf>,
+    install_path: PathBuf,
+    /// Backup taken by this install, which the restore reads from
+    backup_path: Option<PathBuf>,
+    swapped: bool,
+}
+
+impl PobManager {
+    /// Resolve the release to install, fetching the latest when `file_data` is `None`.
+    pub async fn prepare_install(
+        &self,
+        file_data: Option<GoogleDriveFileInfo>,
+        cancel_token: &CancellationToken,
+        reporter: &InstallReporter,
+    ) -> Result<GoogleDriveFileInfo, PobError> {
+        let result = match file_data {
+            Some(data) if !cancel_token.is_cancelled() => Ok(data),
+            Some(_) => Err(PobError::Cancelled),
+            None => until_cancelled(cancel_token, self.fetch_latest_file(false)).await,
+        };
+        if let Err(PobError::Cancelled) = result {
+            tracing::info!(phase = "prepare", "Install cancelled while preparing");
+            reporter.report(InstallPhase::Preparing, InstallStatus::Cancelled);
+        }
+        result
+    }
+
+    /// Main installation workflow - transactional update with rollback support.
+    ///
+    /// Stages: download → extract → backup → swap → restore → save version
+    pub async fn install(
+        &self,
+        file_info: GoogleDriveFileInfo,
+        temp_dir: PathBuf,
+        on_backup_failure: BackupFailurePolicy,
+        cancel_token: CancellationToken,
+        reporter: InstallReporter,
+    ) -> Result<(), PobError> {
+        let result = self
+            .run_install(
+                file_info,
+                temp_dir,
+                on_backup_failure,
+                cancel_token,
+                reporter,
+            )
+            .await;
+        // Every return leaves the install consistent (done, rolled back or never
+        // touched), so only a crash should leave the state behind
+        if let Err(e) = self
+            .fs
+            .remove_file(&install_state_path(&self.data_dir()))
+            .await
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            tracing::warn!(operation = "recovery", error = %e, "Failed to remove install state");
+        }
+        result
+    }
+
+    async fn run_install(
+        &self,
+        file_info: GoogleDriveFileInfo,
+        temp_dir: PathBuf,
+        on_backup_failure: BackupFailurePolicy,
+        cancel_token: CancellationToken,
+        reporter: InstallReporter,
+    ) -> Result<(), PobError> {
+        if cancel_token.is_cancelled() {
+            tracing::info!(phase = "prepare", "Install cancelled before download");
+            reporter.report(InstallPhase::Preparing, InstallStatus::Cancelled);
+            return Err(PobError::Cancelled);
+        }
+        if self.is_pob_running() {
+            return Err(PobError::ProcessRunning);
+        }
+
+        tracing::info!("=== INSTALL START ===");
+
+        let install_path = self.install_path();
+        let extract_dir = install_path.with_extension("new");
+        // Extraction reads the downloaded handle directly, so the `.part` file is never renamed
+        let temp_zip_path = Self::download_path(&temp_dir, &file_info.name);
+
+        let mut ctx = InstallContext {
+            temp_zip_path: None,
+            extract_dir: Some(extract_dir.clone()),
+            install_path: install_path.clone(),
+            backup_path: None,
+            swapped: false,
+        };
+
+        tracing::info!(phase = "init", path = %install_path.display(), "Install path determined");
+        let mut clock = PhaseClock::start();
+
+        // A `.new` kept after a failed backup of this same release is used as is
+        let staged_path = staged_release_path(&extract_dir);
+        let staged = tokio::fs::read_to_string(&staged_path).await.ok();
+        self.fs.remove_file(&staged_path).await.ok();
+        if staged.as_deref() == Some(file_info.id.as_str()) && extract_dir.is_dir() {
+            tracing::info!(
+                phase = "extract",
+                path = %extract_dir.display(),
+                "Reusing extract kept from an earlier attempt"
+            );
+        } else {
+            // 1. Download
+            self.record_install_state(&file_info, InstallPhase::Downloading, false)
+                .await;
+            let download_result = self
+                .download_with_progress(
+                    &file_info.id,
+                    &temp_zip_path,
+                    cancel_token.clone(),
+                    &reporter,
+                )
+                .await;
+
+            let zip_file = match download_result {
+                Ok(file) => file,
+                Err(e) => {
+                    tracing::error!(
+                        phase = "download",
+                        error = %e,
+                        "Failed to download POB file from Google Drive"
+                    );
+                    self.fs.remove_file(&temp_zip_path).await.ok();
+                    return Err(e);
+                }
+            };
+
+            clock.lap(InstallPhase::Downloading);
+            ctx.temp_zip_path = Some(temp_zip_path.clone());
+
+            // 2. Extract
+            tracing::info!(
+                phase = "extract",
+                from = %temp_zip_path.display(),
+                to = %extract_dir.display(),
+                "Extracting to .new directory"
+            );
+
+            // On failure the archive stays in the temp dir, for `keep_failed_download`
+            self.record_install_state(&file_info, InstallPhase::Extracting, false)
+                .await;
+            self.extract_file_with_progress(
+                zip_file,
+                &extract_dir,
+                self.config().skip_user_data_on_extract,
+                cancel_token.clone(),
+                reporter.clone(),
+            )
+            .await?;
+
+            tracing::info!(phase = "extract", path = %extract_dir.display(), "Extract completed");
+            clock.lap(InstallPhase::Extracting);
+        }
+
+        // 3. Backup existing user data
+        tracing::info!(phase = "backup", "Starting backup phase");
+        self.record_install_state(&file_info, InstallPhase::BackingUp, false)
+            .await;
+        match self.backup(&reporter, &cancel_token).await {
+            Ok(backup_path) => {
+                ctx.backup_path = Some(backup_path);
+                tracing::info!(phase = "backup", "Backup completed");
+            }
+            // Nothing swapped yet; the install is left as it was
+            Err(PobError::Cancelled) => {
+                self.fs.remove_dir_all(&extract_dir).await.ok();
+                return Err(PobError::Cancelled);
+            }
+            Err(e) => {
+                self.on_backup_failure(e, on_backup_failure, &file_info, &extract_dir, &reporter)
+                    .await?
+            }
+        }
+        clock.lap(InstallPhase::BackingUp);
+
+        // 4-6: Atomic operations with rollback on failure
+        self.record_install_state(&file_info, InstallPhase::Moving, ctx.backup_path.is_some())
+            .await;
+        self.finish_or_rollback(&ctx, &file_info, &mut clock, &reporter, &cancel_token)
+            .await?;
+        ctx.swapped = true;
+
+        // Success: cleanup
+        self.cleanup_success(&ctx, &temp_zip_path).await;
+        self.update_stats(|stats| stats.record_phase_secs(clock.laps()))
+            .await;
+
+        tracing::info!("=== INSTALL SUCCESS ===");
+        Ok(())
+    }
+
+    /// Download and extract `file_info` under `temp_dir` like an install, then
+    /// check the result and the free space at the install root. Nothing is
+    /// backed up, swapped or recorded, and the extracted files are removed again.
+    pub async fn dry_run_install(
+        &self,
+        file_info: GoogleDriveFileInfo,
+        temp_dir: PathBuf,
+        cancel_token: CancellationToken,
+        reporter: InstallReporter,
+    ) -> Result<DryRunReport, PobError> {
+        if cancel_token.is_cancelled() {
+            reporter.report(InstallPhase::Preparing, InstallStatus::Cancelled);
+            return Err(PobError::Cancelled);
+        }
+        tracing::info!(operation = "dry_run", file = %file_info.name, "=== DRY RUN START ===");
+
+        let temp_zip_path = Self::download_path(&temp_dir, &file_info.name);
+        let extract_dir = temp_dir.join("dry_run");
+        let result = self
+            .run_dry_run(
+                &file_info,
+                &temp_zip_path,
+                &extract_dir,
+                &cancel_token,
+                &reporter,
+            )
+            .await;
+
+        self.fs.remove_dir_all(&extract_dir).await.ok();
+        // On failure the archive stays in the temp dir, for `keep_failed_download`
+        if let Ok(report) = &result {
+            self.fs.remove_file(&temp_zip_path).await.ok();
+            tracing::info!(
+                operation = "dry_run",
+                file_count = report.file_count,
+                total_bytes = report.total_bytes,
+                prefix = ?report.detected_prefix,
+                "=== DRY RUN SUCCESS ==="
+            );
+        }
+        result
+    }
+
+    async fn run_dry_run(
+        &self,
+        file_info: &GoogleDriveFileInfo,
+        temp_zip_path: &Path,
+        extract_dir: &Path,
+        cancel_token: &CancellationToken,
+        reporter: &InstallReporter,
+    ) -> Result<DryRunReport, PobError> {
+        let zip_file = self
+            .download_with_progress(&file_info.id, temp_zip_path, cancel_token.clone(), reporter)
+            .await?;
+
+        // Read the layout up front, so a full disk is reported before extracting
+        let archive_path = temp_zip_path.to_path_buf();
+        let (detected_prefix, file_count, total_bytes) =
+            tokio::task::spawn_blocking(move || -> Result<_, PobError> {
+                let mut archive = zip::ZipArchive::new(std::fs::File::open(&archive_path)?)?;
+                let prefix = detect_nested_structure(&mut archive)?;
+                let total_bytes: u64 = (0..archive.len())
+                    .filter_map(|i| archive.by_index_raw(i).ok().map(|file| file.size()))
+                    .sum();
+                Ok((prefix, archive.len() as u32, total_bytes))
+            })
+            .await??;
+        check_free_space(&self.install_root(), total_bytes)?;
+
+        self.extract_file_with_progress(
+            zip_file,
+            extract_dir,
+            false,
+            cancel_token.clone(),
+            reporter.clone(),
+        )
+        .await?;
+
+        reporter.report(
+            InstallPhase::Verifying,
+            InstallStatus::Started {
+                total_size: None,
+                estimated_secs: None,
+            },
+        );
+        let missing_dirs: Vec<&str> = REQUIRED_DIRS
+            .iter()
+            .copied()
+            .filter(|dir| !extract_dir.join(dir).is_dir())
+            .collect();
+        if !missing_dirs.is_empty() {
+            let err = PobError::ExtractFailed(format!(
+                "필수 폴더가 없습니다: {}",
+                missing_dirs.join(", ")
+            ));
+            reporter.report(
+                InstallPhase::Verifying,
+                InstallStatus::Failed {
+                    reason: err.to_string(),
+                },
+            );
+            return Err(err);
+        }
+        reporter.report(
+            InstallPhase::Verifying,
+            InstallStatus::Completed { speed: None },
+        );
+
+        Ok(DryRunReport {
+            detected_prefix: detected_prefix.map(|prefix| prefix.to_string_lossy().into_owned()),
+            file_count,
+            total_bytes: total_bytes as f64,
+        })
+    }
+
+    /// Re-download the installed release and swap it in over a damaged install.
+    /// User data is copied straight from the current install instead of going
+    /// through a backup, and the damaged install isn't kept as the previous version.
+    pub async fn repair(
+        &self,
+        temp_dir: PathBuf,
+        cancel_token: CancellationToken,
+        reporter: InstallReporter,
+    ) -> Result<(), PobError> {
+        if self.is_pob_running() {
+            return Err(PobError::ProcessRunning);
+        }
+        let file_info = until_cancelled(&cancel_token, self.repair_target()).await?;
+        tracing::info!(operation = "repair", file = %file_info.name, "=== REPAIR START ===");
+
+        let install_path = self.install_path();
+        let extract_dir = install_path.with_extension("new");
+        // Extracted over below, so an extract kept for a retry no longer matches
+        self.fs
+            .remove_file(&staged_release_path(&extract_dir))
+            .await
+            .ok();
+        let temp_zip_path = Self::download_path(&temp_dir, &file_info.name);
+        let ctx = InstallContext {
+            temp_zip_path: Some(temp_zip_path.clone()),
+            extract_dir: Some(extract_dir.clone()),
+            install_path: install_path.clone(),
+            backup_path: None,
+            swapped: false,
+        };
+        let mut clock = PhaseClock::start();
+
+        let zip_file = match self
+            .download_with_progress(
+                &file_info.id,
+                &temp_zip_path,
+                cancel_token.clone(),
+                &reporter,
+            )
+            .await
+        {
+            Ok(file) => file,
+            Err(e) => {
+                self.fs.remove_file(&temp_zip_path).await.ok();
+                return Err(e);
+            }
+        };
+        clock.lap(InstallPhase::Downloading);
+
+        // User data is carried over below, so the archive's copies are never needed
+        self.extract_file_with_progress(
+            zip_file,
+            &extract_dir,
+            true,
+            cancel_token.clone(),
+            reporter.clone(),
+        )
+        .await?;
+        clock.lap(InstallPhase::Extracting);
+
+        reporter.report(
+            InstallPhase::BackingUp,
+            InstallStatus::Started {
+                total_size: None,
+                estimated_secs: None,
+            },
+        );
+        if let Err(e) = self
+            .copy_backup_targets(&install_path, &extract_dir, &cancel_token)
+            .await
+        {
+            tracing::error!(operation = "repair", error = %e, "Failed to carry over user data");
+            self.fs.remove_dir_all(&extract_dir).await.ok();
+            let status = match e {
+                PobError::Cancelled => InstallStatus::Cancelled,
+                ref e => InstallStatus::Failed {
+                    reason: e.to_string(),
+                },
+            };
+            reporter.report(InstallPhase::BackingUp, status);
+            return Err(e);
+        }
+        reporter.report(
+            InstallPhase::BackingUp,
+            InstallStatus::Completed { speed: None },
+        );
+
+        self.finish_or_rollback(&ctx, &file_info, &mut clock, &reporter, &cancel_token)
+            .await?;
+
+        // The replaced install is the damaged one, not worth reverting to
+        let old_path = install_path.with_extension("old");
+        if let Err(e) = self.fs.remove_dir_all(&old_path).await
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            tracing::warn!(operation = "repair", error = %e, "Failed to remove replaced install");
+        }
+        self.fs.remove_file(&temp_zip_path).await.ok();
+
+        tracing::info!(operation = "repair", "=== REPAIR SUCCESS ===");
+        Ok(())
+    }
+
+    /// Run [`Self::finish_install`] and [`Self::rollback`] if it fails.
+    ///
+    /// Only the restore observes `cancel_token`: once the swap starts, a cancel
+    /// is otherwise deferred until the install dir is consistent again (fully new
+    /// or fully old), so repeated cancels can't interleave with the rollback's
+    /// renames. A cancelled restore keeps the new install.
+    async fn finish_or_rollback(
+        &self,
+        ctx: &InstallContext,
+        file_info: &GoogleDriveFileInfo,
+        clock: &mut PhaseClock,
+        reporter: &InstallReporter,
+        cancel_token: &CancellationToken,
+    ) -> Result<(), PobError> {
+        let extract_dir = ctx
+            .extract_dir
+            .as_deref()
+            .expect("extract dir is set before the swap");
+        let result = self
+            .finish_install(
+                extract_dir,
+                &ctx.install_path,
+                file_info,
+                ctx.backup_path.as_deref(),
+                clock,
+                reporter,
+                cancel_token,
+            )
+            .await;
+
+        if let Err(e) = result {
+            tracing::error!(phase = "rollback", error = %e, "Installation failed, attempting rollback");
+            self.rollback(ctx, reporter).await;
+            if cancel_token.is_cancelled() {
+                tracing::info!(
+                    phase = "rollback",
+                    "Cancel requested during rollback, deferred until rollback finished"
+                );
+            }
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Apply `policy` after [`Self::backup`] failed with `err`.
+    /// `Ok` means continue the install without restoring user data.
+    async fn on_backup_failure(
+        &self,
+        err: PobError,
+        policy: BackupFailurePolicy,
+        file_info: &GoogleDriveFileInfo,
+        extract_dir: &Path,
+        reporter: &InstallReporter,
+    ) -> Result<(), PobError> {
+        tracing::error!(phase = "backup", error = %err, ?policy, "Failed to back up user data");
+
+        if policy == BackupFailurePolicy::Continue {
+            reporter.report(
+                InstallPhase::BackingUp,
+                InstallStatus::Failed {
+                    reason: format!(
+                        "백업 실패: {err}. 백업 없이 설치를 계속합니다. 사용자 데이터는 이전 버전 폴더에 남아 있습니다."
+                    ),
+                },
+            );
+            return Ok(());
+        }
+
+        // Nothing has been swapped yet, so the install is untouched. The extract is
+        // kept for a retry of the same release, e.g. once the user picked `Continue`
+        for partial_backup in staged_backups(&self.backup_dir()) {
+            self.fs.remove_dir_all(&partial_backup).await.ok();
+        }
+        if let Err(e) = self
+            .fs
+            .write(&staged_release_path(extract_dir), file_info.id.as_bytes())
+            .await
+        {
+            tracing::warn!(phase = "backup", error = %e, "Failed to keep extract for a retry");
+            self.fs.remove_dir_all(extract_dir).await.ok();
+        }
+
+        if policy == BackupFailurePolicy::SkipUpdate {
+            tracing::warn!(
+                phase = "backup",
+                "Skipping update, existing install left untouched"
+            );
+            reporter.report(
+                InstallPhase::BackingUp,
+                InstallStatus::Failed {
+                    reason: format!("백업 실패: {err}. 이번 업데이트를 건너뜁니다."),
+                },
+            );
+            return Err(PobError::UpdateSkipped(err.to_string()));
+        }
+        Err(PobError::BackupFailed(err.to_string()))
+    }
+
+    /// Finish installation: swap → restore → save version.
+    /// Restore reads `backup`, and is skipped when no backup was taken for this install.
+    async fn finish_install(
+        &self,
+        extract_dir: &Path,
+        install_path: &Path,
+        file_info: &GoogleDriveFileInfo,
+        backup: Option<&Path>,
+        clock: &mut PhaseClock,
+        reporter: &InstallReporter,
+        cancel_token: &CancellationToken,
+    ) -> Result<(), PobError> {
+        // 4. Swap (rename .new to install_path)
+        tracing::info!(
+            phase = "rename",
+            from = %extract_dir.display(),
+            to = %install_path.display(),
+            "Starting rename phase"
+        );
+        self.rename(extract_dir, install_path, reporter).await?;
+        tracing::info!(phase = "rename", "Rename completed");
+        clock.lap(InstallPhase::Moving);
+        if backup.is_some() {
+            // A crash from here on leaves user data only in the backup
+            self.record_install_state(file_info, InstallPhase::Restoring, true)
+                .await;
+        }
+
+        // 5. Restore user data, unless this install went ahead without a backup
+        if let Some(backup) = backup {
+            tracing::info!(phase = "restore", "Starting restore phase");
+            match self.restore_from(backup, reporter, cancel_token).await {
+                Ok(()) => {
+                    tracing::info!(phase = "restore", "Restore completed successfully");
+                }
+                Err(PobError::Cancelled) => {
+                    tracing::warn!(
+                        phase = "restore",
+                        "Restore cancelled after swap, remaining user data is only in the backup"
+                    );
+                }
+                Err(e) => {
+                    tracing::error!(phase = "restore", error = %e, "Failed to restore user data after swap");
+                    reporter.report(
+                        InstallPhase::Restoring,
+                        InstallStatus::Failed {
+                            reason: format!("백업 복원 실패: {}. 백업 폴더를 확인해주세요.", e),
+                        },
+                    );
+                }
+            }
+        } else {
+            tracing::warn!(
+                phase = "restore",
+                "No backup taken for this install, skipping restore"
+            );
+        }
+        clock.lap(InstallPhase::Restoring);
+
+        // 6. Save version info
+        tracing::info!(phase = "finalize", "Saving version info");
+        let version = PobVersion::try_from(file_info)?;
+        self.save_version_info(&version).await?;
+        tracing::info!(phase = "finalize", "Version info saved");
+
+        // The next "latest" lookup should be compared against what was just installed
+        self.invalidate_latest_cache().await;
+
+        Ok(())
+    }
+
+    async fn invalidate_latest_cache(&self) {
+        let folder_id = self.folder_id();
+        if self.cached_result.lock().await.remove(&folder_id).is_some() {
+            tracing::debug!(operation = "cache", "Invalidated cached latest release");
+        }
+    }
+
+    /// Rollback on failure - restore from .old if available
+    async fn rollback(&self, ctx: &InstallContext, reporter: &InstallReporter) {
+        reporter.report(
+            InstallPhase::RollingBack,
+            InstallStatus::Started {
+                total_size: None,
+                estimated_secs: None,
+            },
+        );
+
+        let old_path = ctx.install_path.with_extension("old");
+        let mut failure = None;
+
+        if old_path.exists() {
+            tracing::info!(phase = "rollback", path = %old_path.display(), "Restoring from .old");
+
+            // Remove partial installation
+            if ctx.install_path.exists() {
+                tracing::warn!(phase = "rollback", "Removing partial installation");
+                self.fs.remove_dir_all(&ctx.install_path).await.ok();
+            }
+
+            // Restore from .old
+            if let Err(e) = self.fs.rename(&old_path, &ctx.install_path).await {
+                tracing::error!(
+                    phase = "rollback",
+                    error = %e,
+                    old = %old_path.display(),
+                    target = %ctx.install_path.display(),
+                    "CRITICAL: Failed to rollback from .old, manual intervention required"
+                );
+                failure = Some(format!(
+                    "이전 버전 복구 실패: {}. {} 폴더를 확인해주세요.",
+                    e,
+                    old_path.display()
+                ));
+            } else {
+                tracing::info!(phase = "rollback", "Successfully restored from .old");
+            }
+        } else {
+            tracing::warn!(phase = "rollback", "No .old directory to rollback from");
+        }
+
+        // Cleanup: remove .new if exists
+        if let Some(ref extract_dir) = ctx.extract_dir
+            && extract_dir.exists()
+        {
+            tracing::info!(operation = "cleanup", path = %extract_dir.display(), "Cleaning up .new directory");
+            self.fs.remove_dir_all(extract_dir).await.ok();
+        }
+
+        match failure {
+            Some(reason) => {
+                reporter.report(InstallPhase::RollingBack, InstallStatus::Failed { reason })
+            }
+            None => reporter.report(
+                InstallPhase::RollingBack,
+                InstallStatus::Completed { speed: None },
+            ),
+        }
+    }
+
+    /// Cleanup after successful installation
+    async fn cleanup_success(&self, ctx: &InstallContext, temp_zip_path: &Path) {
+        tracing::info!(
+            operation = "cleanup",
+            "Installation successful, cleaning up temporary directories"
+        );
+
+        // Remove temp ZIP
+        if temp_zip_path.exists() {
+            tracing::debug!(operation = "cleanup", path = %temp_zip_path.display(), "Removing temp ZIP");
+            self.fs.remove_file(temp_zip_path).await.ok();
+        }
+
+        let old_path = ctx.install_path.with_extension("old");
+        if old_path.exists() {
+            self.retain_previous(&old_path, ctx.backup_path.is_some())
+                .await;
+        }
+
+        // Remove .new (should not exist at this point, but just in case)
+        if let Some(ref extract_dir) = ctx.extract_dir
+            && extract_dir.exists()
+        {
+            tracing::debug!(operation = "cleanup", path = %extract_dir.display(), "Removing .new");
+            self.fs.remove_dir_all(extract_dir).await.ok();
+        }
+    }
+
+    /// Keep the replaced install `old_path` as the previous version for
+    /// revert_to_previous, replacing the one before it. Without a backup it holds
+    /// the only copy of the user data, so it is marked and never removed.
+    async fn retain_previous(&self, old_path: &Path, backed_up: bool) {
+        let previous = self.previous_install_path();
+        if !backed_up {
+            tokio::fs::write(old_path.join(UNBACKED_MARKER), b"")
+                .await
+                .ok();
+        }
+
+        if holds_unbacked_data(&previous) {
+            tracing::warn!(operation = "cleanup", path = %previous.display(), "Previous version holds unbacked user data, not replacing it");
+            if backed_up {
+                self.fs.remove_dir_all(old_path).await.ok();
+            }
+            return;
+        }
+
+        tracing::debug!(operation = "cleanup", from = %old_path.display(), to = %previous.display(), "Retaining .old as previous version");
+        if previous.exists() {
+            self.fs.remove_dir_all(&previous).await.ok();
+        }
+        if let Err(e) = self.fs.rename(old_path, &previous).await {
+            if backed_up {
+                tracing::warn!(operation = "cleanup", error = %e, "Failed to retain previous version, removing .old");
+                self.fs.remove_dir_all(old_path).await.ok();
+            } else {
+                tracing::warn!(operation = "cleanup", error = %e, path = %old_path.display(), "Failed to retain previous version, leaving unbacked .old in place");
+            }
+        }
+    }
+
+    /// Undo the last update: swap the retained previous install back in, carrying
+    /// over the current user data. The replaced install becomes the new previous
+    /// one, so reverting twice returns to where you started. A previous install
+    /// holding unbacked user data keeps its own, since the current install never
+    /// got it restored.
+    pub async fn revert_to_previous(
+        &self,
+        reporter: &InstallReporter,
+    ) -> Result<Option<PobVersion>, PobError> {
+        if self.is_pob_running() {
+            return Err(PobError::ProcessRunning);
+        }
+
+        let install_path = self.install_path();
+        let previous = self.previous_install_path();
+        if !previous.is_dir() {
+            return Err(PobError::NoPreviousVersion);
+        }
+        tracing::info!(operation = "revert", from = %previous.display(), "Reverting to previous version");
+        let unbacked = holds_unbacked_data(&previous);
+
+        let backup_path = self.backup(reporter, &CancellationToken::new()).await?;
+
+        // Current install -> .old, previous -> install
+        if let Err(e) = self.rename(&previous, &install_path, reporter).await {
+            tracing::error!(operation = "revert", error = %e, "Revert swap failed, attempting rollback");
+            let ctx = InstallContext {
+                install_path: install_path.clone(),
+                ..Default::default()
+            };
+            self.rollback(&ctx, reporter).await;
+            return Err(e);
+        }
+
+        let old_path = install_path.with_extension("old");
+        if let Err(e) = self.fs.rename(&old_path, &previous).await {
+            tracing::warn!(
+                operation = "revert",
+                path = %old_path.display(),
+                error = %e,
+                "Failed to retain replaced install as previous version"
+            );
+        }
+
+        if unbacked {
+            // The fresh backup only holds the defaults the update came with
+            tracing::info!(
+                operation = "revert",
+                "Keeping the previous install's unbacked user data, skipping restore"
+            );
+            self.fs
+                .remove_file(&install_path.join(UNBACKED_MARKER))
+                .await
+                .ok();
+        } else if let Err(e) = self
+            .restore_from(&backup_path, reporter, &CancellationToken::new())
+            .await
+        {
+            tracing::error!(operation = "revert", error = %e, "Failed to restore user data after revert");
+            reporter.report(
+                InstallPhase::Restoring,
+                InstallStatus::Failed {
+                    reason: format!("백업 복원 실패: {}. 백업 폴더를 확인해주세요.", e),
+                },
+            );
+        }
+
+        tracing::info!(operation = "revert", "Reverted to previous version");
+        self.installed_version().await
+    }
+
+    /// Uninstall PoB - removes the installation directory
+    pub async fn uninstall(&self, reporter: &InstallReporter) -> Result<(), PobError> {
+        if self.is_pob_running() {
+            return Err(PobError::ProcessRunning);
+        }
+
+        let path = self.install_path();
+
+        if path.exists() {
+            tracing::info!(phase = "uninstall", path = %path.display(), "Starting uninstall");
+            reporter.report(
+                InstallPhase::Uninstalling,
+                InstallStatus::Started {
+                    total_size: None,
+                    estimated_secs: None,
+                },
+            );
+
+            self.fs
+                .remove_dir_all(&path)
+                .await
+                .map_err(|e| PobError::from_locked_io(e, &path))?;
+            let previous = self.previous_install_path();
+            if previous.exists() {
+                self.fs.remove_dir_all(&previous).await.ok();
+            }
+
+            reporter.report(
+                InstallPhase::Uninstalling,
+                InstallStatus::Completed { speed: None },
+            );
+            tracing::info!(phase = "uninstall", "Uninstall completed");
+        } else {
+            tracing::debug!(phase = "uninstall", "No installation found, skipping");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pob::{
+        fs::tests::{FaultyFs, FsOp},
+        google_drive::RetryPolicy,
+        progress::tests::MockProgressSink,
+        snapshot::SNAPSHOT_META,
+    };
+
+    fn test_manager(data_dir: &Path) -> PobManager {
+        PobManager::new(
+            GoogleDriveClient::new(reqwest::Client::new()),
+            data_dir.to_path_buf(),
+            DEFAULT_FOLDER_ID.to_string(),
+        )
+    }
+
+    fn write_marker(dir: &Path, content: &str) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join("marker.txt"), content).unwrap();
+    }
+
+    fn read_marker(dir: &Path) -> String {
+        std::fs::read_to_string(dir.join("marker.txt")).unwrap()
+    }
+
+    /// Build a ZIP of empty stored entries with raw name bytes and general-purpose flags,
+    /// bypassing `ZipWriter` which always marks non-ASCII names as UTF-8.
+    fn raw_zip(entries: &[(&[u8], u16)]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut central = Vec::new();
+        for &(name, flags) in entries {
+            let offset = out.len() as u32;
+            out.extend_from_slice(&0x04034b50u32.to_le_bytes());
+            out.extend_from_slice(&20u16.to_le_bytes());
+            out.extend_from_slice(&flags.to_le_bytes());
+            out.extend_from_slice(&[0; 18]); // method, time, date, crc, sizes
+            out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            out.extend_from_slice(&0u16.to_le_bytes());
+            out.extend_from_slice(name);
+
+            central.extend_from_slice(&0x02014b50u32.to_le_bytes());
+            central.extend_from_slice(&20u16.to_le_bytes());
+            central.extend_from_slice(&20u16.to_le_bytes());
+            central.extend_from_slice(&flags.to_le_bytes());
+            central.extend_from_slice(&[0; 18]);
+            central.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            central.extend_from_slice(&[0; 12]); // extra, comment, disk, attributes
+            central.extend_from_slice(&offset.to_le_bytes());
+            central.extend_from_slice(name);
+        }
+        let central_offset = out.len() as u32;
+        out.extend_from_slice(&central);
+        out.extend_from_slice(&0x06054b50u32.to_le_bytes());
+        out.extend_from_slice(&[0; 4]);
+        out.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(central.len() as u32).to_le_bytes());
+        out.extend_from_slice(&central_offset.to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes());
+        out
+    }
+
+    #[test]
+    fn test_decode_entry_name_encodings() {
+        const UTF8_FLAG: u16 = 1 << 11;
+        let utf8_name = "Data/Fonts/한글.ttf".as_bytes();
+        let data = raw_zip(&[
+            // 0x82 is 'é' in CP437 and never valid UTF-8 on its own
+            (b"Data/caf\x82.txt", 0),
+            (utf8_name, 0),
+            (utf8_name, UTF8_FLAG),
+        ]);
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(data.clone())).unwrap();
+
+        let names: Vec<String> = (0..archive.len())
+            .map(|i| decode_entry_name(&archive.by_index(i).unwrap()).to_string())
+            .collect();
+
+        // Central directory parsed by hand decodes the same way
+        let (offset, size) = find_central_directory(&data).unwrap();
+        let parsed: Vec<String> =
+            parse_central_directory(&data[offset as usize..(offset + size) as usize])
+                .unwrap()
+                .into_iter()
+                .map(|entry| entry.name)
+                .collect();
+        assert_eq!(parsed, names);
+        assert_eq!(
+            names,
+            [
+                "Data/café.txt",
+                "Data/Fonts/한글.ttf",
+                "Data/Fonts/한글.ttf"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_enclosed_entry_path() {
+        assert_eq!(
+            enclosed_entry_path("POE1 POB/./Launch.lua"),
+            Some(PathBuf::from("POE1 POB").join("Launch.lua"))
+        );
+        assert_eq!(
+            enclosed_entry_path("/Data\\한글/../font.ttf"),
+            Some(PathBuf::from("Data").join("font.ttf"))
+        );
+        assert_eq!(enclosed_entry_path("../outside.txt"), None);
+        assert_eq!(enclosed_entry_path("Data/../../outside.txt"), None);
+        assert_eq!(enclosed_entry_path("C:/Windows/evil.dll"), None);
+        assert_eq!(enclosed_entry_path("Data/a\0b"), None);
+    }
+
+    #[tokio::test]
+    async fn test_download_slots_respect_configured_cap() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let token = CancellationToken::new();
+
+        let mut config = manager.config();
+        config.download.max_concurrent_downloads = 1;
+        manager.set_config(config).await.unwrap();
+
+        let first = manager.acquire_download_slot(&token).await.unwrap();
+        let second = tokio::time::timeout(
+            Duration::from_millis(50),
+            manager.acquire_download_slot(&token),
+        )
+        .await;
+        assert!(second.is_err(), "second download should wait for a slot");
+
+        drop(first);
+        assert!(manager.acquire_download_slot(&token).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_handle_cap_holds_across_mixed_operations() {
+        use std::sync::atomic::AtomicU32;
+
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let token = CancellationToken::new();
+
+        let mut config = manager.config();
+        config.max_open_handles = 4;
+        manager.set_config(config).await.unwrap();
+
+        let in_use = AtomicU32::new(0);
+        let peak = AtomicU32::new(0);
+        // Downloads/extractions take 2 handles, range fetches 1
+        let operations = (0..24).map(|i| {
+            let (manager, token, in_use, peak) = (&manager, &token, &in_use, &peak);
+            async move {
+                let count = if i % 3 == 0 { 1 } else { 2 };
+                let _handles = manager.acquire_handles(count, token).await.unwrap();
+                let now = in_use.fetch_add(count, Ordering::SeqCst) + count;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(2)).await;
+                in_use.fetch_sub(count, Ordering::SeqCst);
+            }
+        });
+        futures_util::future::join_all(operations).await;
+
+        let peak = peak.load(Ordering::SeqCst);
+        assert!(peak <= 4, "peak {peak} exceeded the cap");
+        assert!(peak >= 3, "operations should overlap up to the cap");
+
+        assert!(matches!(
+            manager.acquire_handles(5, &token).await,
+            Err(PobError::ResourceLimit(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_download_slot_wait_is_cancellable() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let token = CancellationToken::new();
+
+        let mut config = manager.config();
+        config.download.max_concurrent_downloads = 1;
+        manager.set_config(config).await.unwrap();
+
+        let _held = manager.acquire_download_slot(&token).await.unwrap();
+        token.cancel();
+        assert!(matches!(
+            manager.acquire_download_slot(&token).await,
+            Err(PobError::Cancelled)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_prepare_install_cancelled_before_fetch() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let sink = Arc::new(MockProgressSink::new());
+        let reporter = InstallReporter::new("prepare", sink.clone());
+        let token = CancellationToken::new();
+        token.cancel();
+
+        // Biased towards the token, so the folder fetch is never polled
+        let result = manager.prepare_install(None, &token, &reporter).await;
+        assert!(matches!(result, Err(PobError::Cancelled)));
+
+        let events = sink.get_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].phase, InstallPhase::Preparing);
+        assert!(matches!(events[0].status, InstallStatus::Cancelled));
+    }
+
+    #[tokio::test]
+    async fn test_set_folder_id_keys_cache_by_folder() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let release = |id: &str| GoogleDriveFileInfo {
+            name: "PoeCharm3(3.25.1).zip".to_string(),
+            ..GoogleDriveFileInfo::test_release(id)
+        };
+        manager.cached_result.lock().await.insert(
+            DEFAULT_FOLDER_ID.to_string(),
+            (Instant::now(), release("official")),
+        );
+        manager
+            .cached_result
+            .lock()
+            .await
+            .insert("testFolder".to_string(), (Instant::now(), release("test")));
+
+        assert_eq!(
+            manager.fetch_latest_file(false).await.unwrap().id,
+            "official"
+        );
+        assert_eq!(
+            manager
+                .fetch_latest_file_in("testFolder", false)
+                .await
+                .unwrap()
+                .id,
+            "test"
+        );
+
+        assert!(matches!(
+            manager.set_folder_id("../folders".to_string()).await,
+            Err(PobError::InvalidConfig(_))
+        ));
+        assert_eq!(manager.folder_id(), DEFAULT_FOLDER_ID);
+
+        manager
+            .set_folder_id("testFolder".to_string())
+            .await
+            .unwrap();
+        assert_eq!(manager.folder_id(), "testFolder");
+        assert!(manager.cached_result.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_latest_cache_expires_after_ttl() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let release = GoogleDriveFileInfo::test_release("cached");
+        manager
+            .cached_result
+            .lock()
+            .await
+            .insert(manager.folder_id(), (Instant::now(), release));
+        // Any fetch that reaches the network fails fast
+        manager.quiesce();
+
+        assert_eq!(manager.fetch_latest_file(false).await.unwrap().id, "cached");
+        assert!(matches!(
+            manager.fetch_latest_file(true).await,
+            Err(PobError::Quiesced)
+        ));
+
+        let mut config = manager.config();
+        config.latest_cache_ttl_secs = 0;
+        manager.set_config(config).await.unwrap();
+        assert!(matches!(
+            manager.fetch_latest_file(false).await,
+            Err(PobError::Quiesced)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_quiesce_blocks_downloads_until_resumed() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let token = CancellationToken::new();
+
+        manager.quiesce();
+        assert!(manager.is_quiesced());
+        assert!(matches!(
+            manager.acquire_download_slot(&token).await,
+            Err(PobError::Quiesced)
+        ));
+        assert!(matches!(
+            manager.fetch_latest_file(true).await,
+            Err(PobError::Quiesced)
+        ));
+
+        manager.resume_activity();
+        assert!(manager.acquire_download_slot(&token).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_backup_targets_follow_manifest() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        assert_eq!(
+            manager.load_backup_targets().len(),
+            DEFAULT_BACKUP_TARGETS.len()
+        );
+
+        manager
+            .save_backup_targets(vec!["POE2 POB/Builds".into(), "POE2 POB/Scripts".into()])
+            .await
+            .unwrap();
+        assert_eq!(
+            manager.load_backup_targets(),
+            [
+                PathBuf::from("POE2 POB/Builds"),
+                PathBuf::from("POE2 POB/Scripts")
+            ]
+        );
+
+        // A rejected list leaves the saved one in place
+        assert!(matches!(
+            manager
+                .save_backup_targets(vec!["../../Documents".into()])
+                .await,
+            Err(PobError::InvalidConfig(_))
+        ));
+        assert_eq!(manager.load_backup_targets().len(), 2);
+    }
+
+    #[test]
+    fn test_is_user_data_path() {
+        let temp = tempfile::tempdir().unwrap();
+        let targets = test_manager(temp.path()).load_backup_targets();
+
+        assert!(is_user_data_path(
+            &targets,
+            Path::new("POE1 POB/Builds/my build.xml")
+        ));
+        assert!(is_user_data_path(
+            &targets,
+            Path::new("POE2 POB/Settings.xml")
+        ));
+        assert!(is_user_data_path(&targets, Path::new("Data/Fonts")));
+        assert!(is_user_data_path(&targets, Path::new("Data/Fonts/a.ttf")));
+
+        assert!(!is_user_data_path(
+            &targets,
+            Path::new("POE1 POB/Launch.lua")
+        ));
+        assert!(!is_user_data_path(
+            &targets,
+            Path::new("POE1 POB/BuildsExtra/x")
+        ));
+        assert!(!is_user_data_path(&targets, Path::new("Data/Other.dat")));
+        assert!(!is_user_data_path(&[], Path::new("POE1 POB/Builds/a.xml")));
+    }
+
+    #[test]
+    fn test_recover_backup_swap_intact() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        write_marker(&manager.backup_dir(), "current");
+
+        assert_eq!(manager.recover_backup_swap(), BackupRecovery::Intact);
+        assert_eq!(read_marker(&manager.backup_dir()), "current");
+    }
+
+    #[test]
+    fn test_recover_backup_swap_prefers_existing_backup() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let backup = manager.backup_dir();
+        write_marker(&backup, "current");
+        write_marker(&backup.with_extension("new"), "partial");
+        write_marker(&backup.with_extension("old"), "previous");
+        write_marker(&backup.join("20260101-120000.new"), "staged");
+
+        assert_eq!(
+            manager.recover_backup_swap(),
+            BackupRecovery::DiscardedLeftovers
+        );
+        assert_eq!(read_marker(&backup), "current");
+        assert!(!backup.join("20260101-120000.new").exists());
+        assert!(!backup.with_extension("new").exists());
+        assert!(!backup.with_extension("old").exists());
+    }
+
+    #[test]
+    fn test_recover_backup_swap_promotes_old() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let backup = manager.backup_dir();
+        write_marker(&backup.with_extension("new"), "partial");
+        write_marker(&backup.with_extension("old"), "previous");
+
+        assert_eq!(manager.recover_backup_swap(), BackupRecovery::PromotedOld);
+        assert_eq!(read_marker(&backup), "previous");
+        assert!(!backup.with_extension("new").exists());
+        assert!(!backup.with_extension("old").exists());
+    }
+
+    #[test]
+    fn test_recover_backup_swap_discards_incomplete_new() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let backup = manager.backup_dir();
+        write_marker(&backup.with_extension("new"), "partial");
+
+        assert_eq!(
+            manager.recover_backup_swap(),
+            BackupRecovery::DiscardedLeftovers
+        );
+        assert!(!backup.exists());
+        assert!(!backup.with_extension("new").exists());
+    }
+
+    #[tokio::test]
+    async fn test_relocate_data_dir_moves_everything() {
+        let app_dir = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        let new_dir = target.path().join("exile-rs");
+        let manager = test_manager(app_dir.path());
+        write_marker(&manager.install_path().join("POE1 POB"), "install");
+        write_marker(&manager.backup_dir(), "backup");
+        write_marker(&manager.snapshots_dir().join("20260101-120000"), "snapshot");
+        write_marker(&manager.failed_downloads_dir(), "failed");
+        manager.set_config(PobConfig::default()).await.unwrap();
+
+        let sink = Arc::new(MockProgressSink::new());
+        let reporter = InstallReporter::new("relocate", sink.clone());
+        manager
+            .relocate_data_dir(new_dir.clone(), app_dir.path(), &reporter)
+            .await
+            .unwrap();
+
+        assert_eq!(manager.data_dir(), new_dir);
+        assert_eq!(
+            read_marker(&manager.install_path().join("POE1 POB")),
+            "install"
+        );
+        assert_eq!(read_marker(&manager.backup_dir()), "backup");
+        assert_eq!(
+            read_marker(&manager.snapshots_dir().join("20260101-120000")),
+            "snapshot"
+        );
+        assert_eq!(read_marker(&manager.failed_downloads_dir()), "failed");
+        assert!(config_file_path(&new_dir).exists());
+        assert!(!app_dir.path().join("PoeCharm").exists());
+        assert!(!app_dir.path().join("backup").exists());
+        assert!(!app_dir.path().join("snapshots").exists());
+        assert!(!config_file_path(app_dir.path()).exists());
+        assert_eq!(
+            crate::pob::config::resolve_data_dir(app_dir.path()),
+            new_dir
+        );
+
+        let events = sink.get_events();
+        assert!(matches!(
+            events.last().unwrap().status,
+            InstallStatus::Completed { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_relocate_data_dir_rejects_occupied_target() {
+        let app_dir = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        let manager = test_manager(app_dir.path());
+        write_marker(&manager.install_path(), "current");
+        write_marker(&target.path().join("PoeCharm"), "someone else's");
+
+        let reporter = InstallReporter::new("relocate", Arc::new(MockProgressSink::new()));
+        let result = manager
+            .relocate_data_dir(target.path().to_path_buf(), app_dir.path(), &reporter)
+            .await;
+
+        assert!(matches!(result, Err(PobError::InvalidDataDir(_))));
+        assert_eq!(manager.data_dir(), app_dir.path());
+        assert_eq!(read_marker(&manager.install_path()), "current");
+        assert_eq!(
+            read_marker(&target.path().join("PoeCharm")),
+            "someone else's"
+        );
+
+        let nested = app_dir.path().join("nested");
+        let result = manager
+            .relocate_data_dir(nested, app_dir.path(), &reporter)
+            .await;
+        assert!(matches!(result, Err(PobError::InvalidDataDir(_))));
+    }
+
+    #[tokio::test]
+    async fn test_portable_layout_rebases_paths() {
+        let exe_dir = tempfile::tempdir().unwrap();
+        std::fs::write(exe_dir.path().join("portable.txt"), "").unwrap();
+        let root = crate::pob::config::portable_root(exe_dir.path()).unwrap();
+        let manager = PobManager::new_portable(
+            GoogleDriveClient::new(reqwest::Client::new()),
+            root.clone(),
+            DEFAULT_FOLDER_ID.to_string(),
+        );
+        manager.set_config(PobConfig::default()).await.unwrap();
+
+        assert!(manager.is_portable());
+        for path in [
+            manager.install_path(),
+            manager.exe_path(),
+            manager.version_file_path(),
+            manager.backup_dir(),
+            manager.event_log_path(),
+            manager.portable_temp_dir().unwrap(),
+            manager.portable_log_dir().unwrap(),
+        ] {
+            assert!(path.starts_with(&root), "{} escapes root", path.display());
+        }
+        assert!(config_file_path(&root).exists());
+
+        let reporter = InstallReporter::new("relocate", Arc::new(MockProgressSink::new()));
+        let target = tempfile::tempdir().unwrap();
+        let result = manager
+            .relocate_data_dir(target.path().to_path_buf(), exe_dir.path(), &reporter)
+            .await;
+        assert!(matches!(result, Err(PobError::InvalidDataDir(_))));
+        let result = manager
+            .set_install_root(target.path().to_path_buf(), &reporter)
+            .await;
+        assert!(matches!(result, Err(PobError::InvalidConfig(_))));
+
+        let installed = test_manager(exe_dir.path());
+        assert!(installed.portable_temp_dir().is_none());
+        assert!(installed.portable_log_dir().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_check_integrity() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        assert!(matches!(
+            manager.check_integrity().await,
+            Err(PobError::NotInstalled)
+        ));
+
+        // Crashed mid-extract: only part of the tree, no version file
+        let install = manager.install_path();
+        std::fs::create_dir_all(install.join("POE1 POB")).unwrap();
+        let report = manager.check_integrity().await.unwrap();
+        assert!(!report.is_healthy());
+        assert!(!report.exe_exists);
+        assert!(!report.version_file_valid);
+        assert_eq!(report.missing_dirs, ["POE2 POB", "Data"]);
+        assert!(matches!(
+            manager.repair_target().await,
+            Err(PobError::UnknownInstalledRelease)
+        ));
+
+        std::fs::write(manager.version_file_path(), "{ truncated").unwrap();
+        assert!(!manager.check_integrity().await.unwrap().version_file_valid);
+
+        for dir in ["POE2 POB", "Data"] {
+            std::fs::create_dir_all(install.join(dir)).unwrap();
+        }
+        std::fs::write(manager.exe_path(), b"").unwrap();
+        std::fs::write(
+            manager.version_file_path(),
+            r#"{"version":"2024.05.20","installedAt":"","fileId":"drive_id"}"#,
+        )
+        .unwrap();
+        let report = manager.check_integrity().await.unwrap();
+        assert!(report.is_healthy());
+        assert_eq!(report.file_id.as_deref(), Some("drive_id"));
+    }
+
+    #[tokio::test]
+    async fn test_repair_needs_recorded_release() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let reporter = InstallReporter::new("repair", Arc::new(MockProgressSink::new()));
+        assert!(matches!(
+            manager
+                .repair(
+                    temp.path().join("tmp"),
+                    CancellationToken::new(),
+                    reporter.clone()
+                )
+                .await,
+            Err(PobError::NotInstalled)
+        ));
+
+        // Nothing is downloaded or touched without a file_id to fetch
+        write_marker(&manager.install_path(), "damaged");
+        assert!(matches!(
+            manager
+                .repair(temp.path().join("tmp"), CancellationToken::new(), reporter)
+                .await,
+            Err(PobError::UnknownInstalledRelease)
+        ));
+        assert_eq!(read_marker(&manager.install_path()), "damaged");
+        assert!(!manager.install_path().with_extension("new").exists());
+    }
+
+    #[tokio::test]
+    async fn test_changelog() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let install = manager.install_path();
+        std::fs::create_dir_all(&install).unwrap();
+        assert_eq!(manager.changelog().await.unwrap(), None);
+
+        std::fs::write(
+            install.join("변경사항.txt"),
+            b"\xEF\xBB\xBF- \xEC\x88\x98\xEC\xA0\x95\xFF",
+        )
+        .unwrap();
+        assert_eq!(
+            manager.changelog().await.unwrap().as_deref(),
+            Some("- 수정\u{fffd}")
+        );
+
+        std::fs::write(install.join("changelog.txt"), "- fix").unwrap();
+        assert_eq!(manager.changelog().await.unwrap().as_deref(), Some("- fix"));
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_version_info() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+
+        assert!(matches!(
+            manager.rebuild_version_info("2024.05.20", None).await,
+            Err(PobError::NotInstalled)
+        ));
+
+        std::fs::create_dir_all(manager.install_path()).unwrap();
+        std::fs::write(manager.exe_path(), b"").unwrap();
+        assert!(matches!(
+            manager.rebuild_version_info("latest", None).await,
+            Err(PobError::VersionParseError(_))
+        ));
+
+        let latest = GoogleDriveFileInfo::test_release("drive_id");
+        let rebuilt = manager
+            .rebuild_version_info("2024.05.20", Some(&latest))
+            .await
+            .unwrap();
+        assert_eq!(rebuilt.file_id, "drive_id");
+
+        let rebuilt = manager
+            .rebuild_version_info("2024.01.01", Some(&latest))
+            .await
+            .unwrap();
+        assert!(rebuilt.file_id.is_empty());
+
+        let installed = manager.installed_version().await.unwrap().unwrap();
+        assert_eq!(installed.version, "2024.01.01");
+    }
+
+    #[tokio::test]
+    async fn test_extract_dangerous_path_policy() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let zip_path = temp.path().join("archive.zip");
+        let dest = temp.path().join("extracted");
+        std::fs::write(
+            &zip_path,
+            raw_zip(&[(b"POE1 POB/Launch.lua", 0), (b"../escaped.txt", 0)]),
+        )
+        .unwrap();
+        let reporter = InstallReporter::new("extract", Arc::new(MockProgressSink::new()));
+
+        manager
+            .extract_with_progress(&zip_path, &dest, CancellationToken::new(), reporter.clone())
+            .await
+            .unwrap();
+        assert!(dest.join("POE1 POB/Launch.lua").exists());
+        assert!(!temp.path().join("escaped.txt").exists());
+
+        manager
+            .set_config(PobConfig {
+                on_dangerous_path: DangerousPathPolicy::Abort,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let result = manager
+            .extract_with_progress(&zip_path, &dest, CancellationToken::new(), reporter)
+            .await;
+        match result {
+            Err(PobError::ExtractFailed(msg)) => assert!(msg.contains("../escaped.txt")),
+            other => panic!("Expected ExtractFailed, got {other:?}"),
+        }
+        assert!(!temp.path().join("escaped.txt").exists());
+    }
+
+    #[test]
+    fn test_copy_with_progress_reports_bytes() {
+        let data = vec![7u8; EXTRACT_CHUNK_SIZE * 2 + 10];
+        let mut out = Vec::new();
+        let mut seen = Vec::new();
+        let written = copy_with_progress(&mut data.as_slice(), &mut out, |n| seen.push(n)).unwrap();
+
+        assert_eq!(written, data.len() as u64);
+        assert_eq!(out, data);
+        assert_eq!(seen.last(), Some(&(data.len() as u64)));
+        assert!(seen.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_byte_percent() {
+        // One 400 MB entry among many tiny ones: halfway through it is ~half done
+        assert_eq!(byte_percent(200, 400), 50.0);
+        assert_eq!(byte_percent(0, 0), 100.0);
+        assert_eq!(byte_percent(500, 400), 100.0);
+    }
+
+    #[test]
+    fn test_megabits_per_sec() {
+        assert_eq!(megabits_per_sec(12_500_000, Duration::from_secs(10)), 10.0);
+        assert_eq!(megabits_per_sec(1_000, Duration::ZERO), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_extract_rejects_crc_mismatch() {
+        use std::io::Write;
+
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let zip_path = temp.path().join("archive.zip");
+        let dest = temp.path().join("extracted");
+
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored);
+        writer.start_file("Data/payload.txt", options).unwrap();
+        writer.write_all(b"intact payload").unwrap();
+        let mut bytes = writer.finish().unwrap().into_inner();
+
+        // Flip one byte of stored data; the central directory still parses
+        let at = bytes
+            .windows(14)
+            .position(|w| w == b"intact payload")
+            .unwrap();
+        bytes[at] ^= 0x20;
+        std::fs::write(&zip_path, bytes).unwrap();
+
+        let reporter = InstallReporter::new("extract", Arc::new(MockProgressSink::new()));
+        let result = manager
+            .extract_with_progress(&zip_path, &dest, CancellationToken::new(), reporter)
+            .await;
+        match result {
+            Err(PobError::ExtractFailed(msg)) => assert!(msg.contains("payload.txt")),
+            other => panic!("Expected ExtractFailed, got {other:?}"),
+        }
+        assert!(!dest.join("Data/payload.txt").exists());
+    }
+
+    /// Install dir holding the current version and a fully extracted `.new`.
+    fn stage_swap(manager: &PobManager) -> (PathBuf, PathBuf) {
+        let install = manager.install_path();
+        let extract = install.with_extension("new");
+        write_marker(&install, "old");
+        write_marker(&extract, "new");
+        (install, extract)
+    }
+
+    #[tokio::test]
+    async fn test_finish_install_invalidates_latest_cache() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let reporter = InstallReporter::new("finish", Arc::new(MockProgressSink::new()));
+        let file_info = GoogleDriveFileInfo::test_release("drive_id");
+        manager
+            .cached_result
+            .lock()
+            .await
+            .insert(manager.folder_id(), (Instant::now(), file_info.clone()));
+
+        let (install, extract) = stage_swap(&manager);
+        manager
+            .finish_install(
+                &extract,
+                &install,
+                &file_info,
+                None,
+                &mut PhaseClock::start(),
+                &reporter,
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(read_marker(&install), "new");
+        assert!(manager.cached_result.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_set_install_root_moves_install_across_devices() {
+        let temp = tempfile::tempdir().unwrap();
+        let data_dir = temp.path().join("data");
+        let other_drive = temp.path().join("games");
+        let fs = FaultyFs::new();
+        let manager = test_manager(&data_dir).with_fs(fs.clone());
+        let sink = Arc::new(MockProgressSink::new());
+        let reporter = InstallReporter::new("install_root", sink.clone());
+        write_marker(&manager.install_path(), "install");
+        write_marker(&manager.backup_dir(), "backup");
+
+        // The install pretends to live on another volume, so rename can't be used
+        fs.fail_with(
+            FsOp::Rename,
+            other_drive.join("PoeCharm"),
+            std::io::ErrorKind::CrossesDevices,
+        );
+        manager
+            .set_install_root(other_drive.clone(), &reporter)
+            .await
+            .unwrap();
+        // The copy is sized in bytes: just the install's marker.txt
+        assert!(sink.get_events().iter().any(|e| matches!(
+            e.status,
+            InstallStatus::Started { total_size, .. } if total_size == NonZeroU32::new(7)
+        )));
+
+        assert_eq!(manager.install_path(), other_drive.join("PoeCharm"));
+        assert_eq!(read_marker(&manager.install_path()), "install");
+        assert_eq!(read_marker(&manager.backup_dir()), "backup");
+        assert!(!data_dir.join("PoeCharm").exists());
+        assert!(!data_dir.join("backup").exists());
+        let saved = PobConfig::load(&config_file_path(&data_dir));
+        assert_eq!(
+            saved.install_root,
+            Some(other_drive.to_string_lossy().into_owned())
+        );
+
+        // set_config can't move it behind the files' back
+        let mut config = manager.config();
+        config.install_root = None;
+        manager.set_config(config).await.unwrap();
+        assert_eq!(manager.install_root(), other_drive);
+
+        // Back to the default location
+        manager
+            .set_install_root(data_dir.clone(), &reporter)
+            .await
+            .unwrap();
+        assert_eq!(read_marker(&data_dir.join("PoeCharm")), "install");
+        assert!(manager.config().install_root.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_set_install_root_moves_back_on_failure() {
+        let temp = tempfile::tempdir().unwrap();
+        let data_dir = temp.path().join("data");
+        let target = temp.path().join("games");
+        let fs = FaultyFs::new();
+        let manager = test_manager(&data_dir).with_fs(fs.clone());
+        write_marker(&manager.install_path(), "install");
+        write_marker(&manager.backup_dir(), "backup");
+
+        let reporter = InstallReporter::new("install_root", Arc::new(MockProgressSink::new()));
+        fs.fail(FsOp::Rename, target.join("backup"));
+        assert!(
+            manager
+                .set_install_root(target.clone(), &reporter)
+                .await
+                .is_err()
+        );
+
+        assert_eq!(manager.install_root(), data_dir);
+        assert_eq!(read_marker(&manager.install_path()), "install");
+        assert_eq!(read_marker(&manager.backup_dir()), "backup");
+        assert!(!target.join("PoeCharm").exists());
+    }
+
+    #[tokio::test]
+    async fn test_set_install_root_rejects_unwritable_folder() {
+        let temp = tempfile::tempdir().unwrap();
+        let data_dir = temp.path().join("data");
+        let target = temp.path().join("games");
+        let manager = test_manager(&data_dir);
+        write_marker(&manager.install_path(), "install");
+
+        // A directory in the probe's place makes the write fail like a read-only folder
+        std::fs::create_dir_all(target.join(WRITE_PROBE)).unwrap();
+        let reporter = InstallReporter::new("install_root", Arc::new(MockProgressSink::new()));
+        let result = manager.set_install_root(target.clone(), &reporter).await;
+
+        assert!(matches!(result, Err(PobError::DirNotWritable(_))));
+        assert_eq!(manager.install_root(), data_dir);
+        assert_eq!(read_marker(&manager.install_path()), "install");
+    }
+
+    #[tokio::test]
+    async fn test_set_install_root_removes_rejected_new_folder() {
+        let temp = tempfile::tempdir().unwrap();
+        let data_dir = temp.path().join("data");
+        let target = temp.path().join("games");
+        let fs = FaultyFs::new();
+        let manager = test_manager(&data_dir).with_fs(fs.clone());
+        write_marker(&manager.install_path(), "install");
+
+        fs.fail(FsOp::Write, target.join(WRITE_PROBE));
+        let reporter = InstallReporter::new("install_root", Arc::new(MockProgressSink::new()));
+        let result = manager.set_install_root(target.clone(), &reporter).await;
+
+        assert!(matches!(result, Err(PobError::DirNotWritable(_))));
+        assert!(!target.exists());
+        assert_eq!(read_marker(&manager.install_path()), "install");
+    }
+
+    #[tokio::test]
+    async fn test_create_manual_backup() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let reporter = InstallReporter::new("snapshot", Arc::new(MockProgressSink::new()));
+
+        assert!(matches!(
+            manager.create_manual_backup(None, &reporter).await,
+            Err(PobError::NotInstalled)
+        ));
+
+        let install = manager.install_path();
+        write_marker(&install.join("POE1 POB/Builds"), "build");
+        std::fs::write(install.join("POE1 POB/Settings.xml"), "<Settings/>").unwrap();
+
+        let snapshot = manager
+            .create_manual_backup(Some("  before league ".into()), &reporter)
+            .await
+            .unwrap();
+        assert_eq!(snapshot.label.as_deref(), Some("before league"));
+        assert_eq!(snapshot.entries, 2);
+
+        let dir = manager.snapshots_dir().join(&snapshot.id);
+        assert_eq!(read_marker(&dir.join("POE1 POB/Builds")), "build");
+        assert!(dir.join("POE1 POB/Settings.xml").is_file());
+        assert!(dir.join(SNAPSHOT_META).is_file());
+        assert!(!dir.with_extension("new").exists());
+        // The install and the rolling backup are left alone
+        assert_eq!(read_marker(&install.join("POE1 POB/Builds")), "build");
+        assert!(!manager.backup_dir().exists());
+
+        let second = manager.create_manual_backup(None, &reporter).await.unwrap();
+        assert_ne!(second.id, snapshot.id);
+
+        let ids: Vec<_> = manager
+            .list_snapshots()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|snapshot| snapshot.id)
+            .collect();
+        assert_eq!(ids, [second.id.clone(), snapshot.id.clone()]);
+
+        write_marker(&install.join("POE1 POB/Builds"), "changed");
+        manager
+            .restore_snapshot(&snapshot.id, &reporter)
+            .await
+            .unwrap();
+        assert_eq!(read_marker(&install.join("POE1 POB/Builds")), "build");
+        assert!(matches!(
+            manager.restore_snapshot("../backup", &reporter).await,
+            Err(PobError::NoBackup)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_backups_rotate_and_restore_by_name() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let mut config = manager.config();
+        config.max_backups = 2;
+        manager.set_config(config).await.unwrap();
+        let reporter = InstallReporter::new("backup", Arc::new(MockProgressSink::new()));
+
+        let builds = manager.install_path().join("POE1 POB/Builds");
+        let mut newest = PathBuf::new();
+        for content in ["first", "second", "third"] {
+            write_marker(&builds, content);
+            newest = manager
+                .backup(&reporter, &CancellationToken::new())
+                .await
+                .unwrap();
+        }
+
+        let backups = manager.list_backups().await.unwrap();
+        assert_eq!(backups.len(), 2);
+        let backup_builds = |name: &str| manager.backup_dir().join(name).join("POE1 POB/Builds");
+        assert_eq!(read_marker(&backup_builds(&backups[0].name)), "third");
+        assert_eq!(read_marker(&backup_builds(&backups[1].name)), "second");
+        assert!(backups[0].size_bytes > 0.0);
+
+        manager
+            .restore_from_backup(&backups[1].name, &reporter)
+            .await
+            .unwrap();
+        assert_eq!(read_marker(&builds), "second");
+        manager
+            .restore_from(&newest, &reporter, &CancellationToken::new())
+            .await
+            .unwrap();
+        assert_eq!(read_marker(&builds), "third");
+
+        assert!(matches!(
+            manager.restore_from_backup("../PoeCharm", &reporter).await,
+            Err(PobError::NoBackup)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_backup_and_restore_stop_on_cancel() {
+        let temp = tempfile::tempdir().unwrap();
+        let fs = FaultyFs::new();
+        let manager = test_manager(temp.path()).with_fs(fs.clone());
+        let builds = manager.install_path().join("POE1 POB/Builds");
+        write_marker(&builds, "build");
+
+        // Cancel lands while the Builds folder is being copied
+        let sink = Arc::new(MockProgressSink::new());
+        let reporter = InstallReporter::new("backup", sink.clone());
+        let cancel = CancellationToken::new();
+        fs.cancel_on(FsOp::Metadata, builds.clone(), cancel.clone());
+        assert!(matches!(
+            manager.backup(&reporter, &cancel).await,
+            Err(PobError::Cancelled)
+        ));
+        assert!(manager.list_backups().await.unwrap().is_empty());
+        assert!(staged_backups(&manager.backup_dir()).is_empty());
+        assert!(matches!(
+            sink.get_events().last().unwrap().status,
+            InstallStatus::Cancelled
+        ));
+
+        let backup = manager
+            .backup(&reporter, &CancellationToken::new())
+            .await
+            .unwrap();
+        write_marker(&builds, "changed");
+        let cancel = CancellationToken::new();
+        fs.cancel_on(
+            FsOp::Metadata,
+            backup.join("POE1 POB/Builds"),
+            cancel.clone(),
+        );
+        assert!(matches!(
+            manager.restore_from(&backup, &reporter, &cancel).await,
+            Err(PobError::Cancelled)
+        ));
+        assert_eq!(read_marker(&builds), "changed");
+        assert!(matches!(
+            sink.get_events().last().unwrap().status,
+            InstallStatus::Cancelled
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_recover_incomplete_install() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let install = manager.install_path();
+        let state_path = install_state_path(&manager.data_dir());
+        let file_info = GoogleDriveFileInfo::test_release("drive_id");
+        assert_eq!(
+            manager.recover_incomplete_install().unwrap(),
+            RecoveryAction::None
+        );
+
+        // Died between moving the install aside and moving the new one in
+        write_marker(&install.with_extension("old"), "previous");
+        write_marker(&install.with_extension("new"), "extracted");
+        manager
+            .record_install_state(&file_info, InstallPhase::Moving, true)
+            .await;
+        assert_eq!(
+            manager.recover_incomplete_install().unwrap(),
+            RecoveryAction::RolledBack
+        );
+        assert_eq!(read_marker(&install), "previous");
+        assert!(!install.with_extension("new").exists());
+        assert!(!state_path.exists());
+        assert!(manager.take_recovery_action().is_none());
+
+        // Died after the swap, before restoring user data
+        manager
+            .record_install_state(&file_info, InstallPhase::Restoring, true)
+            .await;
+        let action = manager.recover_incomplete_install().unwrap();
+        assert!(matches!(action, RecoveryAction::RestoreUserData { .. }));
+        assert_eq!(manager.take_recovery_action(), Some(action));
+        assert!(manager.take_recovery_action().is_none());
+
+        // Died mid-extract: the install is untouched, the download can resume
+        write_marker(&install.with_extension("new"), "partial");
+        manager
+            .record_install_state(&file_info, InstallPhase::Extracting, false)
+            .await;
+        assert_eq!(
+            manager.recover_incomplete_install().unwrap(),
+            RecoveryAction::ResumeInstall {
+                file_id: "drive_id".to_string(),
+                file_name: file_info.name.clone(),
+            }
+        );
+        assert!(!install.with_extension("new").exists());
+        assert_eq!(read_marker(&install), "previous");
+    }
+
+    #[test]
+    fn test_migrate_legacy_backup() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        manager.migrate_legacy_backup();
+        assert!(!manager.backup_dir().exists());
+
+        write_marker(&manager.backup_dir().join("POE1 POB/Builds"), "legacy");
+        manager.migrate_legacy_backup();
+
+        let latest = manager.latest_backup().unwrap();
+        assert_eq!(read_marker(&latest.join("POE1 POB/Builds")), "legacy");
+        assert!(!manager.backup_dir().join("POE1 POB").exists());
+        assert!(!manager.backup_dir().with_extension("migrating").exists());
+
+        // Already in the rotating layout; Explorer's stray files don't count as legacy
+        std::fs::write(manager.backup_dir().join("desktop.ini"), "").unwrap();
+        std::fs::write(manager.backup_dir().join("Thumbs.db"), "").unwrap();
+        manager.migrate_legacy_backup();
+        assert_eq!(manager.latest_backup(), Some(latest));
+        assert!(manager.backup_dir().join("desktop.ini").exists());
+    }
+
+    #[tokio::test]
+    async fn test_on_backup_failure_policies() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let reporter = InstallReporter::new("backup", Arc::new(MockProgressSink::new()));
+        let file_info = GoogleDriveFileInfo::test_release("drive_id");
+        let extract = manager.install_path().with_extension("new");
+        let staged = staged_release_path(&extract);
+        let failure = || PobError::Io(std::io::Error::other("disk full"));
+        let apply =
+            |policy| manager.on_backup_failure(failure(), policy, &file_info, &extract, &reporter);
+
+        write_marker(&extract, "new");
+        assert!(apply(BackupFailurePolicy::Continue).await.is_ok());
+        assert_eq!(read_marker(&extract), "new");
+        assert!(!staged.exists());
+
+        assert!(matches!(
+            apply(BackupFailurePolicy::SkipUpdate).await,
+            Err(PobError::UpdateSkipped(reason)) if reason.contains("disk full")
+        ));
+        assert_eq!(read_marker(&extract), "new");
+        assert_eq!(std::fs::read_to_string(&staged).unwrap(), "drive_id");
+
+        std::fs::remove_file(&staged).unwrap();
+        match apply(BackupFailurePolicy::Abort).await {
+            Err(PobError::BackupFailed(reason)) => assert!(reason.contains("disk full")),
+            other => panic!("Expected BackupFailed, got {other:?}"),
+        }
+        // Kept for the retry once the user picked a policy
+        assert_eq!(read_marker(&extract), "new");
+        assert_eq!(std::fs::read_to_string(&staged).unwrap(), "drive_id");
+    }
+
+    #[tokio::test]
+    async fn test_install_reuses_extract_kept_after_backup_failure() {
+        let temp = tempfile::tempdir().unwrap();
+        // Nothing listens here, so any download attempt fails
+        let manager = fake_server_manager(temp.path(), "http://127.0.0.1:1".into());
+        let reporter = InstallReporter::new("install", Arc::new(MockProgressSink::new()));
+        let file_info = GoogleDriveFileInfo::test_release("drive_id");
+        let extract = manager.install_path().with_extension("new");
+        write_marker(&extract.join("POE1 POB"), "staged");
+        let failure = PobError::Io(std::io::Error::other("disk full"));
+        let result = manager
+            .on_backup_failure(
+                failure,
+                BackupFailurePolicy::Abort,
+                &file_info,
+                &extract,
+                &reporter,
+            )
+            .await;
+        assert!(matches!(result, Err(PobError::BackupFailed(_))));
+
+        let temp_dir = temp.path().join("task");
+        std::fs::create_dir_all(&temp_dir).unwrap();
+        manager
+            .install(
+                file_info,
+                temp_dir,
+                BackupFailurePolicy::Abort,
+                CancellationToken::new(),
+                reporter,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            read_marker(&manager.install_path().join("POE1 POB")),
+            "staged"
+        );
+        assert!(!extract.exists());
+        assert!(!staged_release_path(&extract).exists());
+    }
+
+    #[tokio::test]
+    async fn test_unbacked_user_data_survives_cleanup_and_reclaim() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let install_path = manager.install_path();
+        let old_path = install_path.with_extension("old");
+        let previous = manager.previous_install_path();
+        let temp_zip = temp.path().join("pob.zip");
+
+        // Continue after a failed backup: .old holds the only copy of the builds
+        write_marker(&previous.join("POE1 POB/Builds"), "older");
+        write_marker(&old_path.join("POE1 POB/Builds"), "user");
+        let mut ctx = InstallContext {
+            install_path: install_path.clone(),
+            backup_path: None,
+            ..Default::default()
+        };
+        manager.cleanup_success(&ctx, &temp_zip).await;
+        assert_eq!(read_marker(&previous.join("POE1 POB/Builds")), "user");
+
+        let report = manager
+            .reclaim_space(u64::MAX, temp.path().join("tmp"), None)
+            .await
+            .unwrap();
+        assert!(!report.removed.contains(&previous.display().to_string()));
+        assert_eq!(read_marker(&previous.join("POE1 POB/Builds")), "user");
+
+        // A later, backed-up update doesn't replace it either
+        write_marker(&old_path.join("POE1 POB/Builds"), "next");
+        ctx.backup_path = Some(manager.backup_dir().join("20240520-120000"));
+        manager.cleanup_success(&ctx, &temp_zip).await;
+        assert_eq!(read_marker(&previous.join("POE1 POB/Builds")), "user");
+        assert!(!old_path.exists());
+    }
 
-        Ok(())
+    /// Answer one HTTP request on a local port with the raw `response`, then
+    /// close the connection. Returns the base URL.
+    fn serve_once(response: Vec<u8>) -> String {
+        serve_sequence(vec![response]).0
     }
-}
 
-/// Detect if ZIP has nested directory structure (e.g., PoeCharm/POE1 POB/...)
-/// Returns the prefix to skip, or None if structure is flat
-fn detect_nested_structure(
-    archive: &zip::ZipArchive<std::fs::File>,
-) -> Result<Option<PathBuf>, PobError> {
-    const REQUIRED: &[&str] = &["POE1 POB/", "POE2 POB/", "Data/"];
+    /// Like `serve_once`, but answers consecutive connections with `responses`
+    /// in order. The receiver yields each request head as it arrives.
+    fn serve_sequence(responses: Vec<Vec<u8>>) -> (String, std::sync::mpsc::Receiver<String>) {
+        use std::io::{Read, Write};
 
-    // Check first occurrence of any required folder
-    for name in archive.file_names() {
-        for &required_folder in REQUIRED {
-            if let Some(pos) = name.find(required_folder) {
-                if pos == 0 {
-                    // "POE1 POB/..." - top level, OK
-                    tracing::info!(
-                        phase = "extract",
-                        "ZIP structure validated: top-level folders found"
-                    );
-                    return Ok(None);
-                } else {
-                    // "PoeCharm/POE1 POB/..." - nested structure
-                    let prefix = &name[..pos];
-                    let prefix = prefix.trim_end_matches('/');
-                    tracing::warn!(
-                        phase = "extract",
-                        prefix = %prefix,
-                        example_file = %name,
-                        "Detected nested directory structure in ZIP"
-                    );
-                    return Ok(Some(PathBuf::from(prefix)));
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    match stream.read(&mut buf) {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
                 }
+                tx.send(String::from_utf8_lossy(&request).into_owned()).ok();
+                stream.write_all(&response).ok();
             }
-        }
+        });
+        (format!("http://{addr}"), rx)
     }
 
-    Err(PobError::ExtractFailed(
-        "ZIP does not contain required folders (POE1 POB, POE2 POB, Data)".into(),
-    ))
-}
+    fn fake_server_manager(data_dir: &Path, host: String) -> PobManager {
+        let client = GoogleDriveClient::new(reqwest::Client::new())
+            .with_download_host(host)
+            .with_retry_policy(RetryPolicy::none());
+        PobManager::new(
+            client,
+            data_dir.to_path_buf(),
+            DEFAULT_FOLDER_ID.to_string(),
+        )
+    }
 
-// ============================================================================
-// Install Context & Workflow
-// ============================================================================
+    #[tokio::test]
+    async fn test_download_truncated_by_early_close() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut response =
+            b"HTTP/1.1 200 OK\r\nContent-Length: 100\r\nConnection: close\r\n\r\n".to_vec();
+        response.extend_from_slice(&[7; 40]);
+        let manager = fake_server_manager(&temp.path().join("data"), serve_once(response));
+        let reporter = InstallReporter::new("download", Arc::new(MockProgressSink::new()));
 
-/// Tracks which stages of installation have been completed for rollback purposes
-#[derive(Debug, Default)]
-struct InstallContext {
-    temp_zip_path: Option<PathBuf>,
-    extract_dir: Option<PathBuf>,
-    install_path: PathBuf,
-    backed_up: bool,
-    swapped: bool,
-}
+        let result = manager
+            .download_with_progress(
+                "drive_id",
+                temp.path().join("pob.part"),
+                CancellationToken::new(),
+                &reporter,
+            )
+            .await;
 
-impl PobManager {
-    /// Main installation workflow - transactional update with rollback support.
-    ///
-    /// Stages: download → extract → backup → swap → restore → save version
-    pub async fn install(
-        &self,
-        file_info: GoogleDriveFileInfo,
-        temp_dir: PathBuf,
-        cancel_token: CancellationToken,
-        reporter: InstallReporter,
-    ) -> Result<(), PobError> {
-        if self.is_pob_running() {
-            return Err(PobError::ProcessRunning);
+        match result {
+            Err(PobError::DownloadTruncated { expected, actual }) => {
+                assert_eq!((expected, actual), (100, 40));
+            }
+            other => panic!("Expected DownloadTruncated, got {other:?}"),
         }
+        // Kept for the retry to resume from
+        let partial = manager.partial_download_path("drive_id");
+        assert_eq!(std::fs::read(&partial).unwrap().len(), 40);
+    }
 
-        tracing::info!("=== INSTALL START ===");
+    #[tokio::test]
+    async fn test_download_resumes_after_dropped_connection() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut first =
+            b"HTTP/1.1 200 OK\r\nContent-Length: 100\r\nConnection: close\r\n\r\n".to_vec();
+        first.extend_from_slice(&[7; 40]);
+        let mut second = b"HTTP/1.1 206 Partial Content\r\nContent-Range: bytes 40-99/100\r\nContent-Length: 60\r\nConnection: close\r\n\r\n".to_vec();
+        second.extend_from_slice(&[8; 60]);
+        let (host, requests) = serve_sequence(vec![first, second]);
+        let client = GoogleDriveClient::new(reqwest::Client::new())
+            .with_download_host(host)
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 2,
+                base_delay: Duration::from_millis(1),
+                ..RetryPolicy::default()
+            });
+        let manager = PobManager::new(
+            client,
+            temp.path().join("data"),
+            DEFAULT_FOLDER_ID.to_string(),
+        );
+        let reporter = InstallReporter::new("download", Arc::new(MockProgressSink::new()));
+        let dst = temp.path().join("pob.part");
 
-        let install_path = self.install_path();
-        let extract_dir = install_path.with_extension("new");
-        let mut temp_zip_path = temp_dir.join(&file_info.name).with_extension("part");
+        manager
+            .download_with_progress("drive_id", &dst, CancellationToken::new(), &reporter)
+            .await
+            .unwrap();
 
-        let mut ctx = InstallContext {
-            temp_zip_path: None,
-            extract_dir: Some(extract_dir.clone()),
-            install_path: install_path.clone(),
-            backed_up: false,
-            swapped: false,
-        };
+        let mut expected = vec![7; 40];
+        expected.extend_from_slice(&[8; 60]);
+        assert_eq!(std::fs::read(&dst).unwrap(), expected);
+        let resumed = requests.iter().nth(1).unwrap().to_ascii_lowercase();
+        assert!(resumed.contains("range: bytes=40-"), "{resumed}");
+        assert!(!manager.partial_download_path("drive_id").exists());
+    }
 
-        tracing::info!(phase = "init", path = %install_path.display(), "Install path determined");
+    #[tokio::test]
+    async fn test_download_rejects_unconfirmable_html_page() {
+        let temp = tempfile::tempdir().unwrap();
+        let body = "<html><body>Too many users have viewed or downloaded this file recently.</body></html>";
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let manager =
+            fake_server_manager(&temp.path().join("data"), serve_once(response.into_bytes()));
+        let reporter = InstallReporter::new("download", Arc::new(MockProgressSink::new()));
 
-        // 1. Download
-        let download_result = self
+        let result = manager
             .download_with_progress(
-                &file_info.id,
-                &temp_zip_path,
-                cancel_token.clone(),
+                "drive_id",
+                temp.path().join("pob.part"),
+                CancellationToken::new(),
                 &reporter,
             )
             .await;
 
-        if let Err(e) = download_result {
-            tracing::error!(
-                phase = "download",
-                error = %e,
-                "Failed to download POB file from Google Drive"
-            );
-            tokio::fs::remove_file(&temp_zip_path).await.ok();
-            return Err(e);
+        assert!(matches!(result, Err(PobError::DriveConfirmationRequired)));
+        // The page must not be saved as if it were the zip
+        assert!(!temp.path().join("pob.part").exists());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_install_leaves_install_untouched() {
+        use std::io::Write;
+
+        let temp = tempfile::tempdir().unwrap();
+        let data_dir = temp.path().join("data");
+        let temp_dir = temp.path().join("task");
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        for (name, contents) in [
+            ("PoeCharm/POE1 POB/Launch.lua", "poe1"),
+            ("PoeCharm/POE2 POB/Launch.lua", "poe2"),
+            ("PoeCharm/Data/fonts.txt", "font"),
+        ] {
+            writer.start_file(name, options).unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
         }
+        let zip = writer.finish().unwrap().into_inner();
+        let mut response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            zip.len()
+        )
+        .into_bytes();
+        response.extend_from_slice(&zip);
 
-        // Rename .part to .zip
-        let zip_path = temp_zip_path.with_extension("zip");
-        tokio::fs::rename(&temp_zip_path, &zip_path).await?;
-        temp_zip_path = zip_path;
-        ctx.temp_zip_path = Some(temp_zip_path.clone());
+        let manager = fake_server_manager(&data_dir, serve_once(response));
+        write_marker(&manager.install_path(), "installed");
+        let sink = Arc::new(MockProgressSink::new());
+        let reporter = InstallReporter::new("dry_run", sink.clone());
+        let file_info = GoogleDriveFileInfo::test_release("drive_id");
 
-        // 2. Extract
-        tracing::info!(
-            phase = "extract",
-            from = %temp_zip_path.display(),
-            to = %extract_dir.display(),
-            "Extracting to .new directory"
-        );
+        let report = manager
+            .dry_run_install(
+                file_info,
+                temp_dir.clone(),
+                CancellationToken::new(),
+                reporter,
+            )
+            .await
+            .unwrap();
 
-        let extract_result = self
-            .extract_with_progress(
-                &temp_zip_path,
-                &extract_dir,
-                cancel_token.clone(),
-                reporter.clone(),
+        assert_eq!(report.detected_prefix.as_deref(), Some("PoeCharm"));
+        assert_eq!(report.file_count, 3);
+        assert_eq!(report.total_bytes, 12.0);
+        assert_eq!(read_marker(&manager.install_path()), "installed");
+        assert!(!manager.install_path().with_extension("new").exists());
+        assert!(!manager.backup_dir().exists());
+        assert_eq!(std::fs::read_dir(&temp_dir).unwrap().count(), 0);
+
+        let phases: Vec<InstallPhase> = sink.get_events().iter().map(|e| e.phase).collect();
+        for phase in [
+            InstallPhase::Downloading,
+            InstallPhase::Extracting,
+            InstallPhase::Verifying,
+        ] {
+            assert!(phases.contains(&phase), "{phase:?} not reported");
+        }
+        assert!(!phases.contains(&InstallPhase::BackingUp));
+    }
+
+    #[tokio::test]
+    async fn test_download_truncated_when_close_delimited_body_ends_early() {
+        let temp = tempfile::tempdir().unwrap();
+        // Resumed at byte 10; the body has no length and just stops at 30
+        let mut response = b"HTTP/1.1 206 Partial Content\r\nContent-Range: bytes 10-99/100\r\nConnection: close\r\n\r\n".to_vec();
+        response.extend_from_slice(&[7; 20]);
+        let manager = fake_server_manager(&temp.path().join("data"), serve_once(response));
+        let partial = manager.partial_download_path("drive_id");
+        std::fs::create_dir_all(partial.parent().unwrap()).unwrap();
+        std::fs::write(&partial, [1; 10]).unwrap();
+        let meta = serde_json::to_vec(&PartialMeta { total_size: 100 }).unwrap();
+        std::fs::write(partial_meta_path(&partial), meta).unwrap();
+        let reporter = InstallReporter::new("download", Arc::new(MockProgressSink::new()));
+
+        let result = manager
+            .download_with_progress(
+                "drive_id",
+                temp.path().join("pob.part"),
+                CancellationToken::new(),
+                &reporter,
             )
             .await;
 
-        if let Err(e) = extract_result {
-            tracing::info!(operation = "cleanup", path = %temp_zip_path.display(), "Cleaning up temp ZIP file after extract failure");
-            tokio::fs::remove_file(&temp_zip_path).await.ok();
-            return Err(e);
+        match result {
+            Err(PobError::DownloadTruncated { expected, actual }) => {
+                assert_eq!((expected, actual), (100, 30));
+            }
+            other => panic!("Expected DownloadTruncated, got {other:?}"),
         }
+        assert_eq!(std::fs::read(&partial).unwrap().len(), 30);
+    }
 
-        tracing::info!(phase = "extract", path = %extract_dir.display(), "Extract completed");
+    #[tokio::test]
+    async fn test_prefetch_metadata_isolates_failed_probes() {
+        let temp = tempfile::tempdir().unwrap();
+        let response = b"HTTP/1.1 206 Partial Content\r\nContent-Range: bytes 0-0/1234\r\nContent-Length: 1\r\nConnection: close\r\n\r\nx".to_vec();
+        let manager = fake_server_manager(&temp.path().join("data"), serve_once(response));
+        let mut config = manager.config();
+        // One at a time, so the first probe is the one the server answers
+        config.download.metadata_prefetch_concurrency = 1;
+        manager.set_config(config).await.unwrap();
 
-        // 3. Backup existing user data
-        tracing::info!(phase = "backup", "Starting backup phase");
-        self.backup(&reporter).await?;
-        ctx.backed_up = true;
-        tracing::info!(phase = "backup", "Backup completed");
+        let file = |id: &str, is_folder| GoogleDriveFileInfo {
+            name: format!("{id}.zip"),
+            is_folder,
+            ..GoogleDriveFileInfo::test_release(id)
+        };
+        let files = manager
+            .prefetch_metadata(vec![
+                file("served", false),
+                file("refused", false),
+                file("folder", true),
+            ])
+            .await
+            .unwrap();
 
-        // 4-6: Atomic operations with rollback on failure
-        let result = self
-            .finish_install(&extract_dir, &install_path, &file_info, &reporter)
+        let ids: Vec<_> = files.iter().map(|f| f.id.as_str()).collect();
+        assert_eq!(ids, ["served", "refused", "folder"]);
+        let served = files[0].metadata.as_ref().unwrap();
+        assert_eq!(served.size, Some(1234.0));
+        assert!(served.accepts_ranges);
+        assert!(files[1].metadata.is_none());
+        assert!(files[2].metadata.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stash_partial_download_trims_preallocation() {
+        use tokio::io::AsyncWriteExt;
+
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(&temp.path().join("data"));
+        let dst = temp.path().join("pob.part");
+        let stale = manager.partial_download_path("older_release");
+        std::fs::create_dir_all(stale.parent().unwrap()).unwrap();
+        std::fs::write(&stale, b"stale").unwrap();
+
+        let file = tokio::fs::File::create(&dst).await.unwrap();
+        file.set_len(1024).await.unwrap();
+        let mut writer = tokio::io::BufWriter::new(file);
+        writer.write_all(b"0123456789").await.unwrap();
+
+        let partial = manager.partial_download_path("drive_id");
+        manager
+            .stash_partial_download(writer, 10, 1024, &dst, &partial)
             .await;
 
-        if let Err(e) = result {
-            tracing::error!(phase = "rollback", error = %e, "Installation failed, attempting rollback");
-            self.rollback(&ctx).await;
-            return Err(e);
+        assert!(!dst.exists());
+        assert!(!stale.exists());
+        assert_eq!(std::fs::read(&partial).unwrap(), b"0123456789");
+        assert_eq!(
+            read_partial_meta(&partial_meta_path(&partial)).await,
+            Some(1024)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_keep_failed_download_moves_archive_and_prunes() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(&temp.path().join("data"));
+        let temp_dir = temp.path().join("task");
+        std::fs::create_dir_all(&temp_dir).unwrap();
+        let download = PobManager::download_path(&temp_dir, "pob.zip");
+        std::fs::write(&download, b"archive").unwrap();
+
+        // Off by default: left for the temp dir cleanup
+        assert!(
+            manager
+                .keep_failed_download(&temp_dir, "pob.zip")
+                .await
+                .is_none()
+        );
+        assert!(download.exists());
+
+        let failed_dir = manager.failed_downloads_dir();
+        std::fs::create_dir_all(&failed_dir).unwrap();
+        for (i, name) in ["a.zip", "b.zip", "c.zip"].iter().enumerate() {
+            let file = std::fs::File::create(failed_dir.join(name)).unwrap();
+            let age = Duration::from_secs(3600 * (3 - i as u64));
+            file.set_modified(std::time::SystemTime::now() - age)
+                .unwrap();
         }
 
-        ctx.swapped = true;
+        let mut config = manager.config();
+        config.keep_download_on_failure = true;
+        manager.set_config(config).await.unwrap();
 
-        // Success: cleanup
-        self.cleanup_success(&ctx, &temp_zip_path).await;
+        let kept = manager
+            .keep_failed_download(&temp_dir, "pob.zip")
+            .await
+            .unwrap();
+        assert!(!download.exists());
+        assert_eq!(kept.parent(), Some(failed_dir.as_path()));
+        assert_eq!(std::fs::read(&kept).unwrap(), b"archive");
 
-        tracing::info!("=== INSTALL SUCCESS ===");
-        Ok(())
+        let mut remaining: Vec<_> = std::fs::read_dir(&failed_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        remaining.sort();
+        assert_eq!(remaining.len(), KEPT_FAILED_DOWNLOADS);
+        assert!(!remaining.contains(&"a.zip".into()));
+        assert!(remaining.contains(&kept.file_name().unwrap().to_owned()));
     }
 
-    /// Finish installation: swap → restore → save version
-    async fn finish_install(
-        &self,
-        extract_dir: &Path,
-        install_path: &Path,
-        file_info: &GoogleDriveFileInfo,
-        reporter: &InstallReporter,
-    ) -> Result<(), PobError> {
-        // 4. Swap (rename .new to install_path)
-        tracing::info!(
-            phase = "rename",
-            from = %extract_dir.display(),
-            to = %install_path.display(),
-            "Starting rename phase"
+    #[tokio::test]
+    async fn test_revert_to_previous_swaps_installs_and_keeps_user_data() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let reporter = InstallReporter::new("revert", Arc::new(MockProgressSink::new()));
+
+        assert!(matches!(
+            manager.revert_to_previous(&reporter).await,
+            Err(PobError::NoPreviousVersion)
+        ));
+
+        let install = manager.install_path();
+        let previous = manager.previous_install_path();
+        write_marker(&install, "current");
+        write_marker(&install.join("POE1 POB/Builds"), "new build");
+        write_marker(&previous, "previous");
+        let version = PobVersion {
+            version: "2024.01.01".into(),
+            installed_at: String::new(),
+            file_id: String::new(),
+        };
+        std::fs::write(
+            previous.join("pob_version.json"),
+            serde_json::to_string(&version).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            manager.previous_version().await.unwrap().unwrap().version,
+            "2024.01.01"
         );
-        self.rename(extract_dir, install_path, reporter).await?;
-        tracing::info!(phase = "rename", "Rename completed");
 
-        // 5. Restore user data
-        tracing::info!(phase = "restore", "Starting restore phase");
-        match self.restore(reporter).await {
-            Ok(()) => {
-                tracing::info!(phase = "restore", "Restore completed successfully");
-            }
-            Err(e) => {
-                tracing::error!(phase = "restore", error = %e, "Failed to restore user data after swap");
-                reporter.report(
-                    InstallPhase::Restoring,
-                    InstallStatus::Failed {
-                        reason: format!("백업 복원 실패: {}. 백업 폴더를 확인해주세요.", e),
-                    },
-                );
-            }
-        }
+        let reverted = manager.revert_to_previous(&reporter).await.unwrap();
+        assert_eq!(reverted.unwrap().version, "2024.01.01");
+        assert_eq!(read_marker(&install), "previous");
+        assert_eq!(read_marker(&previous), "current");
+        assert_eq!(read_marker(&install.join("POE1 POB/Builds")), "new build");
+        assert!(!install.with_extension("old").exists());
+    }
 
-        // 6. Save version info
-        tracing::info!(phase = "finalize", "Saving version info");
-        let version = PobVersion::try_from(file_info)?;
-        self.save_version_info(&version).await?;
-        tracing::info!(phase = "finalize", "Version info saved");
+    #[tokio::test]
+    async fn test_revert_to_previous_keeps_unbacked_user_data() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let reporter = InstallReporter::new("revert", Arc::new(MockProgressSink::new()));
+        let install = manager.install_path();
+        let previous = manager.previous_install_path();
 
-        Ok(())
+        // Installed under `Continue`: the current install only has the defaults
+        write_marker(&install.join("POE1 POB/Builds"), "default");
+        write_marker(&previous.join("POE1 POB/Builds"), "user");
+        std::fs::write(previous.join(UNBACKED_MARKER), b"").unwrap();
+
+        manager.revert_to_previous(&reporter).await.unwrap();
+
+        assert_eq!(read_marker(&install.join("POE1 POB/Builds")), "user");
+        assert!(!holds_unbacked_data(&install));
+        assert_eq!(read_marker(&previous.join("POE1 POB/Builds")), "default");
     }
 
-    /// Rollback on failure - restore from .old if available
-    async fn rollback(&self, ctx: &InstallContext) {
-        let old_path = ctx.install_path.with_extension("old");
+    #[tokio::test]
+    async fn test_rename_reports_byte_progress_across_devices() {
+        let temp = tempfile::tempdir().unwrap();
+        let fs = FaultyFs::new();
+        let manager = test_manager(temp.path()).with_fs(fs.clone());
+        let (install, extract) = stage_swap(&manager);
+        let sink = Arc::new(MockProgressSink::new());
+        let reporter = InstallReporter::new("swap", sink.clone());
 
-        if old_path.exists() {
-            tracing::info!(phase = "rollback", path = %old_path.display(), "Restoring from .old");
+        fs.fail_with(FsOp::Rename, &install, std::io::ErrorKind::CrossesDevices);
+        manager.rename(&extract, &install, &reporter).await.unwrap();
 
-            // Remove partial installation
-            if ctx.install_path.exists() {
-                tracing::warn!(phase = "rollback", "Removing partial installation");
-                tokio::fs::remove_dir_all(&ctx.install_path).await.ok();
-            }
+        assert_eq!(read_marker(&install), "new");
+        assert!(!extract.exists());
+        // Started once for the phase, then again with the copy's size in bytes
+        let sizes: Vec<_> = sink
+            .get_events()
+            .iter()
+            .filter(|e| e.phase == InstallPhase::Moving)
+            .filter_map(|e| match e.status {
+                InstallStatus::Started { total_size, .. } => Some(total_size),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(sizes, [None, NonZeroU32::new(3)]);
+    }
 
-            // Restore from .old
-            if let Err(e) = tokio::fs::rename(&old_path, &ctx.install_path).await {
-                tracing::error!(
-                    phase = "rollback",
-                    error = %e,
-                    old = %old_path.display(),
-                    target = %ctx.install_path.display(),
-                    "CRITICAL: Failed to rollback from .old, manual intervention required"
-                );
-            } else {
-                tracing::info!(phase = "rollback", "Successfully restored from .old");
-            }
-        } else {
-            tracing::warn!(phase = "rollback", "No .old directory to rollback from");
-        }
+    #[tokio::test]
+    async fn test_rollback_restores_old_after_failed_swap() {
+        let temp = tempfile::tempdir().unwrap();
+        let fs = FaultyFs::new();
+        let manager = test_manager(temp.path()).with_fs(fs.clone());
+        let (install, extract) = stage_swap(&manager);
+        let sink = Arc::new(MockProgressSink::new());
+        let reporter = InstallReporter::new("swap", sink.clone());
 
-        // Cleanup: remove .new if exists
-        if let Some(ref extract_dir) = ctx.extract_dir
-            && extract_dir.exists()
-        {
-            tracing::info!(operation = "cleanup", path = %extract_dir.display(), "Cleaning up .new directory");
-            tokio::fs::remove_dir_all(extract_dir).await.ok();
-        }
+        // install -> .old succeeds, .new -> install fails
+        fs.fail(FsOp::Rename, &install);
+        assert!(manager.rename(&extract, &install, &reporter).await.is_err());
+        assert!(!install.exists());
+        assert!(install.with_extension("old").exists());
+
+        let ctx = InstallContext {
+            extract_dir: Some(extract.clone()),
+            install_path: install.clone(),
+            backup_path: Some(manager.backup_dir().join("20240520-120000")),
+            ..Default::default()
+        };
+        manager.rollback(&ctx, &reporter).await;
+
+        assert_eq!(read_marker(&install), "old");
+        assert!(!install.with_extension("old").exists());
+        assert!(!extract.exists());
+        let last = sink.get_events().pop().unwrap();
+        assert_eq!(last.phase, InstallPhase::RollingBack);
+        assert!(matches!(last.status, InstallStatus::Completed { .. }));
     }
 
-    /// Cleanup after successful installation
-    async fn cleanup_success(&self, ctx: &InstallContext, temp_zip_path: &Path) {
-        tracing::info!(
-            operation = "cleanup",
-            "Installation successful, cleaning up temporary directories"
+    #[tokio::test]
+    async fn test_check_downgrade_requires_allow_downgrade() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = test_manager(temp.path());
+        let release = |version: &str| GoogleDriveFileInfo {
+            name: format!("POE1&2 통합 한글 POB ({version}).zip"),
+            ..GoogleDriveFileInfo::test_release("drive_id")
+        };
+
+        // Nothing installed: anything goes
+        assert!(
+            manager
+                .check_downgrade(&release("2024.05.20"), false)
+                .await
+                .is_ok()
         );
 
-        // Remove temp ZIP
-        if temp_zip_path.exists() {
-            tracing::debug!(operation = "cleanup", path = %temp_zip_path.display(), "Removing temp ZIP");
-            tokio::fs::remove_file(temp_zip_path).await.ok();
-        }
+        std::fs::create_dir_all(manager.install_path()).unwrap();
+        manager
+            .save_version_info(&PobVersion {
+                version: "2024.06.11".to_string(),
+                installed_at: "2024-06-11T00:00:00Z".to_string(),
+                file_id: "drive_id".to_string(),
+            })
+            .await
+            .unwrap();
 
-        // Remove .old
-        let old_path = ctx.install_path.with_extension("old");
-        if old_path.exists() {
-            tracing::debug!(operation = "cleanup", path = %old_path.display(), "Removing .old");
-            tokio::fs::remove_dir_all(&old_path).await.ok();
+        match manager.check_downgrade(&release("2024.05.20"), false).await {
+            Err(PobError::Downgrade { installed, target }) => {
+                assert_eq!(installed, "2024.06.11");
+                assert_eq!(target, "2024.05.20");
+            }
+            other => panic!("Expected Downgrade, got {other:?}"),
         }
+        assert!(
+            manager
+                .check_downgrade(&release("2024.05.20"), true)
+                .await
+                .is_ok()
+        );
+        assert!(
+            manager
+                .check_downgrade(&release("2024.07.02"), false)
+                .await
+                .is_ok()
+        );
+    }
 
-        // Remove .new (should not exist at this point, but just in case)
-        if let Some(ref extract_dir) = ctx.extract_dir
-            && extract_dir.exists()
-        {
-            tracing::debug!(operation = "cleanup", path = %extract_dir.display(), "Removing .new");
-            tokio::fs::remove_dir_all(extract_dir).await.ok();
-        }
+    #[tokio::test]
+    async fn test_installed_version_reads_complete_file_during_save() {
+        let temp = tempfile::tempdir().unwrap();
+        let fs = FaultyFs::new();
+        let manager = test_manager(temp.path()).with_fs(fs.clone());
+        std::fs::create_dir_all(manager.install_path()).unwrap();
+        assert!(manager.installed_version().await.unwrap().is_none());
+
+        let version = |v: &str| PobVersion {
+            version: v.to_string(),
+            installed_at: "2024-05-20T00:00:00Z".to_string(),
+            file_id: "drive_id".to_string(),
+        };
+        manager
+            .save_version_info(&version("2024.05.20"))
+            .await
+            .unwrap();
+
+        // A save stopped between writing the temp file and the rename leaves the old file readable
+        let path = manager.version_file_path();
+        fs.fail(FsOp::Rename, &path);
+        assert!(
+            manager
+                .save_version_info(&version("2024.06.01"))
+                .await
+                .is_err()
+        );
+
+        let read = manager.installed_version().await.unwrap().unwrap();
+        assert_eq!(read.version, "2024.05.20");
+        assert!(!path.with_extension("json.tmp").exists());
+
+        manager
+            .save_version_info(&version("2024.06.01"))
+            .await
+            .unwrap();
+        let read = manager.installed_version().await.unwrap().unwrap();
+        assert_eq!(read.version, "2024.06.01");
     }
 
-    /// Uninstall PoB - removes the installation directory
-    pub async fn uninstall(&self, reporter: &InstallReporter) -> Result<(), PobError> {
-        if self.is_pob_running() {
-            return Err(PobError::ProcessRunning);
-        }
+    #[tokio::test]
+    async fn test_cancel_during_rollback_is_deferred() {
+        let temp = tempfile::tempdir().unwrap();
+        let fs = FaultyFs::new();
+        let manager = test_manager(temp.path()).with_fs(fs.clone());
+        let (install, extract) = stage_swap(&manager);
+        let sink = Arc::new(MockProgressSink::new());
+        let reporter = InstallReporter::new("swap", sink.clone());
+        let token = CancellationToken::new();
 
-        let path = self.install_path();
+        // The swap's .new -> install rename fails; the rollback's .old -> install
+        // rename then succeeds, with a cancel landing right as it runs
+        fs.fail(FsOp::Rename, &install);
+        fs.cancel_on(FsOp::Rename, &install, token.clone());
 
-        if path.exists() {
-            tracing::info!(phase = "uninstall", path = %path.display(), "Starting uninstall");
-            reporter.report(
-                InstallPhase::Uninstalling,
-                InstallStatus::Started { total_size: None },
-            );
+        let ctx = InstallContext {
+            extract_dir: Some(extract.clone()),
+            install_path: install.clone(),
+            backup_path: Some(manager.backup_dir().join("20240520-120000")),
+            ..Default::default()
+        };
+        let file_info = GoogleDriveFileInfo::test_release("drive_id");
+        let result = manager
+            .finish_or_rollback(
+                &ctx,
+                &file_info,
+                &mut PhaseClock::start(),
+                &reporter,
+                &token,
+            )
+            .await;
+
+        assert!(token.is_cancelled());
+        // The original failure is reported, not a cancel, and the install is fully old
+        assert!(matches!(result, Err(PobError::Io(_))));
+        assert_eq!(read_marker(&install), "old");
+        assert!(!install.with_extension("old").exists());
+        assert!(!extract.exists());
+        let last = sink.get_events().pop().unwrap();
+        assert_eq!(last.phase, InstallPhase::RollingBack);
+        assert!(matches!(last.status, InstallStatus::Completed { .. }));
+    }
 
-            tokio::fs::remove_dir_all(&path).await?;
+    #[tokio::test]
+    async fn test_rollback_reports_failure_and_keeps_old() {
+        let temp = tempfile::tempdir().unwrap();
+        let fs = FaultyFs::new();
+        let manager = test_manager(temp.path()).with_fs(fs.clone());
+        let (install, extract) = stage_swap(&manager);
+        let sink = Arc::new(MockProgressSink::new());
+        let reporter = InstallReporter::new("swap", sink.clone());
 
-            reporter.report(InstallPhase::Uninstalling, InstallStatus::Completed);
-            tracing::info!(phase = "uninstall", "Uninstall completed");
-        } else {
-            tracing::debug!(phase = "uninstall", "No installation found, skipping");
-        }
+        // Both the swap and the rollback's restore rename fail
+        fs.fail(FsOp::Rename, &install);
+        fs.fail(FsOp::Rename, &install);
+        assert!(manager.rename(&extract, &install, &reporter).await.is_err());
 
-        Ok(())
+        let ctx = InstallContext {
+            extract_dir: Some(extract),
+            install_path: install.clone(),
+            ..Default::default()
+        };
+        manager.rollback(&ctx, &reporter).await;
+
+        assert_eq!(read_marker(&install.with_extension("old")), "old");
+        let last = sink.get_events().pop().unwrap();
+        assert!(matches!(last.status, InstallStatus::Failed { .. }));
     }
 }
@@ -1,34 +1,146 @@
-use std::sync::Mutex;
+use std::{collections::HashMap, sync::Mutex};
 
+use serde::{Deserialize, Serialize};
+use specta::Type;
 use tokio_util::sync::CancellationToken;
 
 pub mod version;
 
+pub mod backup_manifest;
+pub mod config;
+pub mod diagnostics;
 pub mod error;
+pub mod fs;
 pub mod google_drive;
 pub mod manager;
 pub mod progress;
+pub mod reclaim;
+pub mod recovery;
+pub mod snapshot;
+pub mod stats;
+pub mod verify;
 
-/// Holds the active installation's cancellation token (if any).
-/// Used to safely cancel ongoing install operations.
+/// Cancellation tokens of the running operations, keyed by their `task_id`.
+/// Used to safely cancel one ongoing operation without touching the others.
 #[derive(Debug, Default)]
-pub struct InstallCancelToken(Mutex<Option<CancellationToken>>);
+pub struct InstallCancelTokens(Mutex<HashMap<String, CancellationToken>>);
 
-impl InstallCancelToken {
-    /// Store a new cancellation token for the current install.
-    pub fn set(&self, token: CancellationToken) {
-        *self.0.lock().unwrap() = Some(token);
+impl InstallCancelTokens {
+    /// Store the cancellation token of the operation `task_id`.
+    pub fn register(&self, task_id: &str, token: CancellationToken) {
+        self.0.lock().unwrap().insert(task_id.to_string(), token);
     }
 
-    /// Clear the stored token (call on install completion).
-    pub fn take(&self) -> Option<CancellationToken> {
-        self.0.lock().unwrap().take()
+    /// Clear the token of `task_id` (call on operation completion).
+    pub fn remove(&self, task_id: &str) -> Option<CancellationToken> {
+        self.0.lock().unwrap().remove(task_id)
     }
 
-    /// Cancel the current install if one is in progress.
-    pub fn cancel(&self) {
-        if let Some(token) = self.0.lock().unwrap().as_ref() {
+    /// Whether any operation currently holds a token.
+    pub fn is_active(&self) -> bool {
+        !self.0.lock().unwrap().is_empty()
+    }
+
+    /// Cancel the operation `task_id`. `false` if it isn't running.
+    pub fn cancel(&self, task_id: &str) -> bool {
+        match self.0.lock().unwrap().get(task_id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Cancel every running operation.
+    pub fn cancel_all(&self) {
+        for token in self.0.lock().unwrap().values() {
             token.cancel();
         }
     }
 }
+
+/// Which game's PoB tree a launch targets; the install holds one per game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum GameProfile {
+    Poe1,
+    Poe2,
+}
+
+impl GameProfile {
+    /// Folder of this game's PoB under the install path.
+    pub fn dir_name(self) -> &'static str {
+        match self {
+            GameProfile::Poe1 => "POE1 POB",
+            GameProfile::Poe2 => "POE2 POB",
+        }
+    }
+}
+
+/// Result of `quiesce`/`resume_activity`.
+#[derive(Debug, Clone, Copy, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityState {
+    /// New network activity is refused until resumed
+    pub quiesced: bool,
+    /// An in-flight operation was asked to cancel
+    pub cancelled_operation: bool,
+}
+
+/// Result of `install_pob` and the repair commands.
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallOutcome {
+    /// Id carried by the operation's progress events
+    pub task_id: String,
+    /// What the archive would install, only for dry runs
+    pub dry_run: Option<DryRunReport>,
+}
+
+/// Result of a dry-run `install_pob`: what the archive would install.
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunReport {
+    /// Top-level folder stripped from every entry, for archives that wrap the install in one
+    pub detected_prefix: Option<String>,
+    /// Archive entries, folders included
+    pub file_count: u32,
+    /// Uncompressed size of all entries
+    pub total_bytes: f64,
+}
+
+/// Result of `update_download_estimate`, for confirming large downloads up front.
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateDownloadEstimate {
+    /// Version the update would install
+    pub version: String,
+    /// Archive size, `None` if Drive didn't report one
+    pub bytes: Option<f64>,
+    /// Size is known and at least the configured large-download threshold
+    pub is_large: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_tokens_cancel_only_their_task() {
+        let tokens = InstallCancelTokens::default();
+        let install = CancellationToken::new();
+        let verify = CancellationToken::new();
+        tokens.register("pob:install", install.clone());
+        tokens.register("pob:verify", verify.clone());
+
+        assert!(tokens.cancel("pob:install"));
+        assert!(install.is_cancelled());
+        assert!(!verify.is_cancelled());
+        assert!(!tokens.cancel("pob:unknown"));
+
+        tokens.remove("pob:install");
+        tokens.remove("pob:verify");
+        assert!(!tokens.is_active());
+    }
+}
@@ -1,15 +1,26 @@
 use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{BufRead, BufReader, Write},
     num::NonZeroU32,
+    path::Path,
     sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use specta::Type;
 use tauri_specta::Event;
 
 const PROGRESS_THROTTLE_MS: u64 = 100;
 
+/// Upper bound on InProgress events written to the per-operation event log
+const EVENT_LOG_MAX_IN_PROGRESS: usize = 2000;
+/// Minimum percent advance before another InProgress event is logged for a phase
+const EVENT_LOG_PERCENT_STEP: f64 = 1.0;
+/// Span of recent samples averaged by [`ThroughputWindow`]
+const THROUGHPUT_WINDOW: Duration = Duration::from_secs(2);
+
 // ============================================================================
 // Progress Sink Abstraction
 // ============================================================================
@@ -17,6 +28,9 @@ const PROGRESS_THROTTLE_MS: u64 = 100;
 /// Trait for emitting progress events. Enables testing without Tauri runtime.
 pub trait ProgressSink: Send + Sync {
     fn emit(&self, progress: InstallProgress);
+
+    /// Emit the combined progress of a multi-phase operation. Ignored by default.
+    fn emit_overall(&self, _progress: OverallProgress) {}
 }
 
 /// Reporter that holds task_id and sink. Clone-friendly for spawn_blocking.
@@ -24,6 +38,13 @@ pub trait ProgressSink: Send + Sync {
 pub struct InstallReporter {
     task_id: String,
     sink: Arc<dyn ProgressSink>,
+    overall: Option<Arc<OverallTracker>>,
+}
+
+/// Folds per-phase percents into one figure that never moves backwards.
+struct OverallTracker {
+    weights: PhaseWeights,
+    percent: Mutex<f64>,
 }
 
 impl InstallReporter {
@@ -31,13 +52,45 @@ impl InstallReporter {
         Self {
             task_id: task_id.into(),
             sink,
+            overall: None,
         }
     }
 
+    /// Also emit [`OverallProgress`] across the phases in `weights`.
+    pub fn with_phase_weights(mut self, weights: PhaseWeights) -> Self {
+        self.overall = Some(Arc::new(OverallTracker {
+            weights,
+            percent: Mutex::new(0.0),
+        }));
+        self
+    }
+
     /// Report progress with the stored task_id.
     pub fn report(&self, phase: InstallPhase, status: InstallStatus) {
+        let phase_percent = match status {
+            InstallStatus::Started { .. } => Some(0.0),
+            InstallStatus::InProgress { percent, .. } => Some(percent),
+            InstallStatus::Completed { .. } => Some(100.0),
+            InstallStatus::Failed { .. } | InstallStatus::Cancelled => None,
+        };
         self.sink
             .emit(InstallProgress::new(&self.task_id, phase, status));
+
+        if let Some(tracker) = &self.overall
+            && let Some(percent) =
+                phase_percent.and_then(|p| tracker.weights.overall_percent(phase, p))
+        {
+            let mut last = tracker.percent.lock().unwrap();
+            if percent <= *last {
+                return;
+            }
+            *last = percent;
+            self.sink.emit_overall(OverallProgress {
+                task_id: self.task_id.clone(),
+                phase,
+                percent,
+            });
+        }
     }
 
     /// Get the task_id for this reporter.
@@ -51,15 +104,18 @@ impl InstallReporter {
 pub struct TauriProgressSink {
     app: tauri::AppHandle,
     last_emit: Mutex<Instant>,
+    last_overall_emit: Mutex<Instant>,
     throttle_duration: Duration,
 }
 
 impl TauriProgressSink {
     pub fn new(app: tauri::AppHandle) -> Self {
+        // Initialize in the past to allow first emit immediately
+        let past = Instant::now() - Duration::from_millis(2 * PROGRESS_THROTTLE_MS);
         Self {
             app,
-            // Initialize in the past to allow first emit immediately
-            last_emit: Mutex::new(Instant::now() - Duration::from_millis(2 * PROGRESS_THROTTLE_MS)),
+            last_emit: Mutex::new(past),
+            last_overall_emit: Mutex::new(past),
             throttle_duration: Duration::from_millis(PROGRESS_THROTTLE_MS),
         }
     }
@@ -88,13 +144,136 @@ impl ProgressSink for TauriProgressSink {
             );
         }
     }
+
+    fn emit_overall(&self, progress: OverallProgress) {
+        // Throttled separately so it isn't starved by the per-phase events
+        if progress.percent < 100.0 {
+            let mut last = self.last_overall_emit.lock().unwrap();
+            if last.elapsed() < self.throttle_duration {
+                return;
+            }
+            *last = Instant::now();
+        }
+
+        if let Err(e) = progress.emit(&self.app) {
+            tracing::warn!(
+                task_id = %progress.task_id,
+                phase = ?progress.phase,
+                error = %e,
+                "Failed to emit overall progress event"
+            );
+        }
+    }
+}
+
+/// Wrapping sink that records the operation's events to a JSONL file for postmortem.
+/// The file is truncated on creation, so it always holds the most recent operation.
+/// InProgress events are sampled (per-phase percent steps) and capped; every other
+/// status is always written, so the log ends with how the operation finished.
+pub struct RecordingProgressSink {
+    inner: Arc<dyn ProgressSink>,
+    state: Mutex<RecorderState>,
+}
+
+struct RecorderState {
+    file: Option<File>,
+    in_progress_written: usize,
+    last_percent: Option<(InstallPhase, f64)>,
+}
+
+impl RecordingProgressSink {
+    pub fn new(inner: Arc<dyn ProgressSink>, path: &Path) -> Self {
+        let file = File::create(path)
+            .inspect_err(|e| {
+                tracing::warn!(
+                    operation = "event_log",
+                    path = %path.display(),
+                    error = %e,
+                    "Failed to create event log, events will not be recorded"
+                );
+            })
+            .ok();
+
+        Self {
+            inner,
+            state: Mutex::new(RecorderState {
+                file,
+                in_progress_written: 0,
+                last_percent: None,
+            }),
+        }
+    }
+
+    fn record(&self, progress: &InstallProgress) {
+        let mut state = self.state.lock().unwrap();
+        if state.file.is_none() {
+            return;
+        }
+
+        let in_progress = matches!(progress.status, InstallStatus::InProgress { .. });
+        if let InstallStatus::InProgress { percent, .. } = progress.status {
+            if state.in_progress_written >= EVENT_LOG_MAX_IN_PROGRESS {
+                return;
+            }
+            // A drop (e.g. a retry restarting the phase) starts sampling afresh
+            if let Some((phase, last)) = state.last_percent
+                && phase == progress.phase
+                && percent >= last
+                && percent - last < EVENT_LOG_PERCENT_STEP
+            {
+                return;
+            }
+            state.last_percent = Some((progress.phase, percent));
+        }
+
+        let Ok(line) = serde_json::to_string(progress) else {
+            return;
+        };
+        let file = state.file.as_mut().expect("checked above");
+        if let Err(e) = writeln!(file, "{line}") {
+            tracing::warn!(
+                operation = "event_log",
+                error = %e,
+                "Failed to write event log, disabling recording"
+            );
+            state.file = None;
+            return;
+        }
+        if in_progress {
+            state.in_progress_written += 1;
+        }
+    }
+}
+
+impl ProgressSink for RecordingProgressSink {
+    fn emit(&self, progress: InstallProgress) {
+        self.record(&progress);
+        self.inner.emit(progress);
+    }
+
+    // Not recorded: replay can recompute it from the per-phase events
+    fn emit_overall(&self, progress: OverallProgress) {
+        self.inner.emit_overall(progress);
+    }
+}
+
+/// Read back a recorded event log. Malformed lines (e.g. a line cut short by a crash) are skipped.
+pub fn read_event_log(path: &Path) -> std::io::Result<Vec<InstallProgress>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut events = Vec::new();
+    for line in reader.lines() {
+        if let Ok(event) = serde_json::from_str(&line?) {
+            events.push(event);
+        }
+    }
+    Ok(events)
 }
 
 // ============================================================================
 // InstallProgress (Event Payload)
 // ============================================================================
 
-#[derive(Debug, Clone, Serialize, Type, Event)]
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
 #[serde(rename_all = "camelCase")]
 pub struct InstallProgress {
     pub task_id: String,
@@ -113,24 +292,193 @@ impl InstallProgress {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Type)]
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase", tag = "status")]
 pub enum InstallStatus {
+    #[serde(rename_all = "camelCase")]
     Started {
         #[serde(skip_serializing_if = "Option::is_none")]
         total_size: Option<NonZeroU32>,
+        /// Expected duration of the phase, when it can be predicted
+        #[serde(skip_serializing_if = "Option::is_none")]
+        estimated_secs: Option<u32>,
     },
+    #[serde(rename_all = "camelCase")]
     InProgress {
         percent: f64,
+        /// Recent transfer rate, only for phases that move bytes over the network
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bytes_per_sec: Option<f64>,
+        /// Seconds left at `bytes_per_sec`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        eta_secs: Option<u32>,
+    },
+    Completed {
+        /// Transfer speed summary, only for phases that move bytes over the network
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        speed: Option<TransferSpeed>,
     },
-    Completed,
     Failed {
         reason: String,
     },
     Cancelled,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Type)]
+impl InstallStatus {
+    /// Progress without throughput, for phases that don't transfer bytes.
+    pub fn in_progress(percent: f64) -> Self {
+        Self::InProgress {
+            percent,
+            bytes_per_sec: None,
+            eta_secs: None,
+        }
+    }
+}
+
+/// Progress of a whole install across its weighted phases (see [`PhaseWeights`]).
+/// Only increases; the per-phase [`InstallProgress`] events still fire alongside.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+#[serde(rename_all = "camelCase")]
+pub struct OverallProgress {
+    pub task_id: String,
+    /// Phase whose progress produced this value
+    pub phase: InstallPhase,
+    /// 0–100 over the whole install
+    pub percent: f64,
+}
+
+/// Fire-once event carrying the final outcome of an install, uninstall or revert.
+/// Lets consumers that don't care about progress (tray, notifications) skip `InstallProgress`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, Event)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallFinished {
+    pub task_id: String,
+    pub result: InstallResult,
+    /// Wall-clock time from the start of the operation to termination
+    pub elapsed_ms: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum InstallResult {
+    Success {
+        /// Version now installed, `None` after an uninstall or a dry run
+        version: Option<String>,
+    },
+    Cancelled,
+    Failed {
+        /// `ErrorKind` tag of the failure (e.g. `network`, `conflict`)
+        code: String,
+        message: String,
+        /// Downloaded archive kept for inspection (`keepDownloadOnFailure`)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        kept_download: Option<String>,
+    },
+}
+
+/// Min/avg/peak throughput of a finished transfer, in bytes per second.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferSpeed {
+    pub min_bytes_per_sec: f64,
+    pub avg_bytes_per_sec: f64,
+    pub peak_bytes_per_sec: f64,
+}
+
+/// Collects instantaneous speed samples between progress reports.
+#[derive(Debug)]
+pub struct SpeedTracker {
+    start: Instant,
+    last_sample: Instant,
+    last_bytes: u64,
+    min: Option<f64>,
+    peak: Option<f64>,
+}
+
+impl SpeedTracker {
+    pub fn new(start: Instant) -> Self {
+        Self {
+            start,
+            last_sample: start,
+            last_bytes: 0,
+            min: None,
+            peak: None,
+        }
+    }
+
+    /// Record the speed since the previous sample, given the running byte total.
+    pub fn sample(&mut self, total_bytes: u64, now: Instant) {
+        let elapsed = now.duration_since(self.last_sample).as_secs_f64();
+        if elapsed <= 0.0 {
+            return;
+        }
+        let speed = total_bytes.saturating_sub(self.last_bytes) as f64 / elapsed;
+        self.min = Some(self.min.map_or(speed, |min| min.min(speed)));
+        self.peak = Some(self.peak.map_or(speed, |peak| peak.max(speed)));
+        self.last_sample = now;
+        self.last_bytes = total_bytes;
+    }
+
+    /// Summarize the transfer. Transfers too short to sample report the average for all three.
+    pub fn summary(&self, total_bytes: u64, now: Instant) -> Option<TransferSpeed> {
+        let elapsed = now.duration_since(self.start).as_secs_f64();
+        if elapsed <= 0.0 {
+            return None;
+        }
+        let avg = total_bytes as f64 / elapsed;
+        Some(TransferSpeed {
+            min_bytes_per_sec: self.min.unwrap_or(avg),
+            avg_bytes_per_sec: avg,
+            peak_bytes_per_sec: self.peak.unwrap_or(avg),
+        })
+    }
+}
+
+/// Moving average of the transfer rate over the last [`THROUGHPUT_WINDOW`],
+/// smoothing out bursty connections for live speed and ETA readouts.
+#[derive(Debug, Default)]
+pub struct ThroughputWindow {
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl ThroughputWindow {
+    /// Record the running byte total at `now`, dropping samples older than the window.
+    pub fn record(&mut self, total_bytes: u64, now: Instant) {
+        self.samples.push_back((now, total_bytes));
+        // Keep one sample at or beyond the window edge so the span stays ~2s
+        while self.samples.len() > 2 && now.duration_since(self.samples[1].0) >= THROUGHPUT_WINDOW {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Bytes per second across the window, once two samples exist.
+    pub fn bytes_per_sec(&self) -> Option<f64> {
+        let (first_at, first) = *self.samples.front()?;
+        let (last_at, last) = *self.samples.back()?;
+        let elapsed = last_at.duration_since(first_at).as_secs_f64();
+        (elapsed > 0.0).then(|| last.saturating_sub(first) as f64 / elapsed)
+    }
+
+    /// `InProgress` for `done` of `total` bytes, with the windowed rate and ETA.
+    pub fn status(&self, done: u64, total: u64) -> InstallStatus {
+        let percent = if total > 0 {
+            done as f64 / total as f64 * 100.0
+        } else {
+            0.0
+        };
+        let bytes_per_sec = self.bytes_per_sec();
+        let eta_secs = bytes_per_sec
+            .filter(|rate| *rate > 0.0 && total > 0)
+            .map(|rate| (total.saturating_sub(done) as f64 / rate).ceil() as u32);
+        InstallStatus::InProgress {
+            percent,
+            bytes_per_sec,
+            eta_secs,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
 pub enum InstallPhase {
     Downloading,
@@ -141,6 +489,48 @@ pub enum InstallPhase {
     Finalizing,
     Uninstalling,
     Preparing,
+    RollingBack,
+    Relocating,
+    Verifying,
+}
+
+/// Relative share of each install phase in an overall progress figure.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseWeights(Vec<(InstallPhase, f64)>);
+
+impl PhaseWeights {
+    /// Weights in install order. `None` if they don't sum to a positive total.
+    pub fn new(weights: Vec<(InstallPhase, f64)>) -> Option<Self> {
+        let total: f64 = weights.iter().map(|(_, w)| w.max(0.0)).sum();
+        (total > 0.0).then_some(Self(weights))
+    }
+
+    pub fn phases(&self) -> impl Iterator<Item = InstallPhase> + '_ {
+        self.0.iter().map(|(phase, _)| *phase)
+    }
+
+    /// Overall percent when `phase` is `phase_percent` done, or `None` for a
+    /// phase that carries no weight (e.g. Preparing).
+    pub fn overall_percent(&self, phase: InstallPhase, phase_percent: f64) -> Option<f64> {
+        let index = self.0.iter().position(|(p, _)| *p == phase)?;
+        let total: f64 = self.0.iter().map(|(_, w)| w.max(0.0)).sum();
+        let before: f64 = self.0[..index].iter().map(|(_, w)| w.max(0.0)).sum();
+        let current = self.0[index].1.max(0.0) * phase_percent.clamp(0.0, 100.0) / 100.0;
+        Some((before + current) / total * 100.0)
+    }
+}
+
+impl Default for PhaseWeights {
+    /// Static guess for a typical download-bound install.
+    fn default() -> Self {
+        Self(vec![
+            (InstallPhase::Downloading, 0.70),
+            (InstallPhase::Extracting, 0.20),
+            (InstallPhase::BackingUp, 0.04),
+            (InstallPhase::Moving, 0.02),
+            (InstallPhase::Restoring, 0.04),
+        ])
+    }
 }
 
 #[test]
@@ -148,33 +538,44 @@ fn test_dummy() {
     let p = InstallProgress {
         task_id: "task1".to_string(),
         phase: InstallPhase::Moving,
-        status: InstallStatus::InProgress { percent: 35.7 },
+        status: InstallStatus::in_progress(35.7),
     };
 
     println!("{}", serde_json::to_string_pretty(&p).unwrap());
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use std::sync::{Arc, Mutex as StdMutex};
 
     /// Mock ProgressSink for testing - captures all emitted progress
-    struct MockProgressSink {
+    pub(crate) struct MockProgressSink {
         events: Arc<StdMutex<Vec<InstallProgress>>>,
+        overall: StdMutex<Vec<OverallProgress>>,
     }
 
     impl MockProgressSink {
-        fn new() -> Self {
+        pub(crate) fn new() -> Self {
             Self {
                 events: Arc::new(StdMutex::new(Vec::new())),
+                overall: StdMutex::new(Vec::new()),
             }
         }
 
-        fn get_events(&self) -> Vec<InstallProgress> {
+        pub(crate) fn get_events(&self) -> Vec<InstallProgress> {
             self.events.lock().unwrap().clone()
         }
 
+        pub(crate) fn overall_percents(&self) -> Vec<f64> {
+            self.overall
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|p| p.percent)
+                .collect()
+        }
+
         #[allow(dead_code)]
         fn event_count(&self) -> usize {
             self.events.lock().unwrap().len()
@@ -185,6 +586,57 @@ mod tests {
         fn emit(&self, progress: InstallProgress) {
             self.events.lock().unwrap().push(progress);
         }
+
+        fn emit_overall(&self, progress: OverallProgress) {
+            self.overall.lock().unwrap().push(progress);
+        }
+    }
+
+    /// Mock ProgressSink that also records when each event arrived,
+    /// so throttling/heartbeat behavior can be asserted on spacing.
+    pub(crate) struct TimedMockProgressSink {
+        events: StdMutex<Vec<(Instant, InstallProgress)>>,
+    }
+
+    impl TimedMockProgressSink {
+        pub(crate) fn new() -> Self {
+            Self {
+                events: StdMutex::new(Vec::new()),
+            }
+        }
+
+        pub(crate) fn events(&self) -> Vec<(Instant, InstallProgress)> {
+            self.events.lock().unwrap().clone()
+        }
+
+        /// Gaps between consecutive events matching `filter`.
+        pub(crate) fn intervals_where(
+            &self,
+            filter: impl Fn(&InstallProgress) -> bool,
+        ) -> Vec<Duration> {
+            let times: Vec<Instant> = self
+                .events
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(_, p)| filter(p))
+                .map(|(t, _)| *t)
+                .collect();
+            times.windows(2).map(|w| w[1] - w[0]).collect()
+        }
+
+        /// Smallest gap between consecutive InProgress events, if there are at least two.
+        pub(crate) fn min_in_progress_interval(&self) -> Option<Duration> {
+            self.intervals_where(|p| matches!(p.status, InstallStatus::InProgress { .. }))
+                .into_iter()
+                .min()
+        }
+    }
+
+    impl ProgressSink for TimedMockProgressSink {
+        fn emit(&self, progress: InstallProgress) {
+            self.events.lock().unwrap().push((Instant::now(), progress));
+        }
     }
 
     #[test]
@@ -196,7 +648,10 @@ mod tests {
 
         reporter.report(
             InstallPhase::Downloading,
-            InstallStatus::Started { total_size: None },
+            InstallStatus::Started {
+                total_size: None,
+                estimated_secs: None,
+            },
         );
 
         let events = sink.get_events();
@@ -212,16 +667,22 @@ mod tests {
 
         reporter.report(
             InstallPhase::Downloading,
-            InstallStatus::Started { total_size: None },
+            InstallStatus::Started {
+                total_size: None,
+                estimated_secs: None,
+            },
         );
+        reporter.report(InstallPhase::Downloading, InstallStatus::in_progress(50.0));
         reporter.report(
             InstallPhase::Downloading,
-            InstallStatus::InProgress { percent: 50.0 },
+            InstallStatus::Completed { speed: None },
         );
-        reporter.report(InstallPhase::Downloading, InstallStatus::Completed);
         reporter.report(
             InstallPhase::Extracting,
-            InstallStatus::Started { total_size: None },
+            InstallStatus::Started {
+                total_size: None,
+                estimated_secs: None,
+            },
         );
 
         let events = sink.get_events();
@@ -231,7 +692,7 @@ mod tests {
         assert!(matches!(events[0].phase, InstallPhase::Downloading));
         assert!(matches!(events[0].status, InstallStatus::Started { .. }));
         assert!(matches!(events[1].status, InstallStatus::InProgress { .. }));
-        assert!(matches!(events[2].status, InstallStatus::Completed));
+        assert!(matches!(events[2].status, InstallStatus::Completed { .. }));
         assert!(matches!(events[3].phase, InstallPhase::Extracting));
     }
 
@@ -243,9 +704,15 @@ mod tests {
 
         reporter.report(
             InstallPhase::Downloading,
-            InstallStatus::Started { total_size: None },
+            InstallStatus::Started {
+                total_size: None,
+                estimated_secs: None,
+            },
+        );
+        reporter_clone.report(
+            InstallPhase::Downloading,
+            InstallStatus::Completed { speed: None },
         );
-        reporter_clone.report(InstallPhase::Downloading, InstallStatus::Completed);
 
         let events = sink.get_events();
         assert_eq!(events.len(), 2);
@@ -256,7 +723,7 @@ mod tests {
         let progress = InstallProgress {
             task_id: "ser_test".to_string(),
             phase: InstallPhase::Moving,
-            status: InstallStatus::InProgress { percent: 75.5 },
+            status: InstallStatus::in_progress(75.5),
         };
 
         let json = serde_json::to_value(&progress).unwrap();
@@ -266,17 +733,30 @@ mod tests {
         assert_eq!(json["percent"], 75.5);
     }
 
+    #[test]
+    fn test_started_serialization() {
+        let status = InstallStatus::Started {
+            total_size: NonZeroU32::new(1000),
+            estimated_secs: Some(45),
+        };
+        let json = serde_json::to_value(&status).unwrap();
+
+        assert_eq!(json["totalSize"], 1000);
+        assert_eq!(json["estimatedSecs"], 45);
+    }
+
     #[test]
     fn test_install_status_variants() {
         let test_cases = vec![
             (
                 InstallStatus::Started {
                     total_size: NonZeroU32::new(1000),
+                    estimated_secs: Some(45),
                 },
                 "started",
             ),
-            (InstallStatus::InProgress { percent: 42.0 }, "inProgress"),
-            (InstallStatus::Completed, "completed"),
+            (InstallStatus::in_progress(42.0), "inProgress"),
+            (InstallStatus::Completed { speed: None }, "completed"),
             (
                 InstallStatus::Failed {
                     reason: "test error".to_string(),
@@ -298,6 +778,132 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_install_finished_serialization() {
+        let finished = InstallFinished {
+            task_id: "done_test".to_string(),
+            result: InstallResult::Failed {
+                code: "network".to_string(),
+                message: "timeout".to_string(),
+                kept_download: None,
+            },
+            elapsed_ms: 1500,
+        };
+
+        let json = serde_json::to_value(&finished).unwrap();
+        assert_eq!(json["taskId"], "done_test");
+        assert_eq!(json["elapsedMs"], 1500);
+        assert_eq!(json["result"]["status"], "failed");
+        assert_eq!(json["result"]["code"], "network");
+        assert!(json["result"].get("keptDownload").is_none());
+
+        let success = serde_json::to_value(InstallResult::Success {
+            version: Some("3.1.0".to_string()),
+        })
+        .unwrap();
+        assert_eq!(
+            success,
+            serde_json::json!({ "status": "success", "version": "3.1.0" })
+        );
+    }
+
+    #[test]
+    fn test_completed_speed_serialization() {
+        let plain = serde_json::to_value(InstallStatus::Completed { speed: None }).unwrap();
+        assert_eq!(plain, serde_json::json!({ "status": "completed" }));
+
+        let with_speed = InstallStatus::Completed {
+            speed: Some(TransferSpeed {
+                min_bytes_per_sec: 1.0,
+                avg_bytes_per_sec: 2.0,
+                peak_bytes_per_sec: 3.0,
+            }),
+        };
+        let json = serde_json::to_value(&with_speed).unwrap();
+        assert_eq!(json["speed"]["avgBytesPerSec"], 2.0);
+        assert_eq!(json["speed"]["peakBytesPerSec"], 3.0);
+
+        // Event logs written before speeds existed must still parse
+        let old: InstallStatus = serde_json::from_str(r#"{"status":"completed"}"#).unwrap();
+        assert!(matches!(old, InstallStatus::Completed { speed: None }));
+    }
+
+    #[test]
+    fn test_speed_tracker_summary() {
+        let start = Instant::now();
+        let mut tracker = SpeedTracker::new(start);
+        tracker.sample(1000, start + Duration::from_secs(1));
+        tracker.sample(4000, start + Duration::from_secs(2));
+        tracker.sample(6000, start + Duration::from_secs(3));
+
+        let speed = tracker
+            .summary(6000, start + Duration::from_secs(3))
+            .unwrap();
+        assert_eq!(speed.min_bytes_per_sec, 1000.0);
+        assert_eq!(speed.avg_bytes_per_sec, 2000.0);
+        assert_eq!(speed.peak_bytes_per_sec, 3000.0);
+
+        // Too fast to sample: all three collapse to the average
+        let quick = SpeedTracker::new(start)
+            .summary(500, start + Duration::from_millis(50))
+            .unwrap();
+        assert_eq!(quick.min_bytes_per_sec, quick.avg_bytes_per_sec);
+        assert_eq!(quick.peak_bytes_per_sec, 10_000.0);
+    }
+
+    #[test]
+    fn test_in_progress_throughput_fields_are_camel_case() {
+        let status = InstallStatus::InProgress {
+            percent: 10.0,
+            bytes_per_sec: Some(2048.0),
+            eta_secs: Some(5),
+        };
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["bytesPerSec"], 2048.0);
+        assert_eq!(json["etaSecs"], 5);
+
+        // Phases without a byte rate serialize as before
+        let json = serde_json::to_value(InstallStatus::in_progress(10.0)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "status": "inProgress", "percent": 10.0 })
+        );
+    }
+
+    #[test]
+    fn test_throughput_window_averages_recent_samples() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut window = ThroughputWindow::default();
+        window.record(0, at(0));
+        assert_eq!(window.bytes_per_sec(), None);
+
+        window.record(1000, at(1));
+        window.record(2000, at(2));
+        window.record(8000, at(3));
+        // The sample at 0s fell out of the 2s window
+        assert_eq!(window.bytes_per_sec(), Some(3500.0));
+
+        match window.status(8000, 15000) {
+            InstallStatus::InProgress {
+                percent,
+                bytes_per_sec,
+                eta_secs,
+            } => {
+                assert!((percent - 53.33).abs() < 0.01);
+                assert_eq!(bytes_per_sec, Some(3500.0));
+                assert_eq!(eta_secs, Some(2));
+            }
+            other => panic!("Expected InProgress, got {other:?}"),
+        }
+
+        // Unknown size: rate but no ETA
+        assert!(matches!(
+            window.status(8000, 0),
+            InstallStatus::InProgress { eta_secs: None, .. }
+        ));
+    }
+
     #[test]
     fn test_install_phase_serialization() {
         let phases = vec![
@@ -309,13 +915,16 @@ mod tests {
             (InstallPhase::Finalizing, "finalizing"),
             (InstallPhase::Uninstalling, "uninstalling"),
             (InstallPhase::Preparing, "preparing"),
+            (InstallPhase::RollingBack, "rollingBack"),
+            (InstallPhase::Relocating, "relocating"),
+            (InstallPhase::Verifying, "verifying"),
         ];
 
         for (phase, expected) in phases {
             let progress = InstallProgress {
                 task_id: "phase_test".to_string(),
                 phase,
-                status: InstallStatus::Completed,
+                status: InstallStatus::Completed { speed: None },
             };
 
             let json = serde_json::to_value(&progress).unwrap();
@@ -323,6 +932,243 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_default_phase_weights() {
+        let weights = PhaseWeights::default();
+        assert_eq!(
+            weights.overall_percent(InstallPhase::Downloading, 50.0),
+            Some(35.0)
+        );
+        assert_eq!(
+            weights.overall_percent(InstallPhase::Extracting, 0.0),
+            Some(70.0)
+        );
+        assert_eq!(weights.overall_percent(InstallPhase::Preparing, 50.0), None);
+        assert!(PhaseWeights::new(vec![(InstallPhase::Moving, 0.0)]).is_none());
+    }
+
+    #[test]
+    fn test_reporter_emits_monotonic_overall_progress() {
+        let sink = Arc::new(MockProgressSink::new());
+        let weights = PhaseWeights::new(vec![
+            (InstallPhase::Downloading, 0.5),
+            (InstallPhase::Extracting, 0.5),
+        ])
+        .unwrap();
+        let reporter = InstallReporter::new("task1", sink.clone()).with_phase_weights(weights);
+
+        reporter.report(InstallPhase::Preparing, InstallStatus::in_progress(50.0));
+        reporter.report(InstallPhase::Downloading, InstallStatus::in_progress(50.0));
+        // A retried download restarts at zero; the total holds
+        reporter.report(
+            InstallPhase::Downloading,
+            InstallStatus::Started {
+                total_size: None,
+                estimated_secs: None,
+            },
+        );
+        reporter.report(InstallPhase::Downloading, InstallStatus::in_progress(80.0));
+        reporter.report(InstallPhase::Extracting, InstallStatus::in_progress(50.0));
+        reporter.report(
+            InstallPhase::Extracting,
+            InstallStatus::Completed { speed: None },
+        );
+
+        assert_eq!(sink.overall_percents(), [25.0, 40.0, 75.0, 100.0]);
+        // Per-phase events are unaffected
+        assert_eq!(sink.get_events().len(), 6);
+    }
+
+    #[test]
+    fn test_reporter_without_weights_emits_no_overall() {
+        let sink = Arc::new(MockProgressSink::new());
+        let reporter = InstallReporter::new("task1", sink.clone());
+        reporter.report(InstallPhase::Downloading, InstallStatus::in_progress(50.0));
+        assert!(sink.overall_percents().is_empty());
+    }
+
+    #[test]
+    fn test_recording_sink_writes_and_forwards() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("last_install.jsonl");
+        let inner = Arc::new(MockProgressSink::new());
+        let reporter = InstallReporter::new(
+            "record_test",
+            Arc::new(RecordingProgressSink::new(inner.clone(), &path)),
+        );
+
+        reporter.report(
+            InstallPhase::Downloading,
+            InstallStatus::Started {
+                total_size: None,
+                estimated_secs: None,
+            },
+        );
+        reporter.report(
+            InstallPhase::Downloading,
+            InstallStatus::Completed { speed: None },
+        );
+
+        assert_eq!(inner.get_events().len(), 2);
+
+        let recorded = read_event_log(&path).unwrap();
+        assert_eq!(recorded.len(), 2);
+        assert_eq!(recorded[0].task_id, "record_test");
+        assert!(matches!(
+            recorded[1].status,
+            InstallStatus::Completed { .. }
+        ));
+    }
+
+    #[test]
+    fn test_recording_sink_samples_in_progress() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("last_install.jsonl");
+        let inner = Arc::new(MockProgressSink::new());
+        let sink = RecordingProgressSink::new(inner.clone(), &path);
+
+        for i in 0..100 {
+            sink.emit(InstallProgress::new(
+                "sample_test",
+                InstallPhase::Extracting,
+                InstallStatus::in_progress(i as f64 * 0.25),
+            ));
+        }
+
+        // Every event reaches the inner sink, but only whole-percent steps are logged
+        assert_eq!(inner.get_events().len(), 100);
+        let recorded = read_event_log(&path).unwrap();
+        assert_eq!(recorded.len(), 25);
+    }
+
+    #[test]
+    fn test_recording_sink_resamples_after_percent_drops() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("last_install.jsonl");
+        let sink = RecordingProgressSink::new(Arc::new(MockProgressSink::new()), &path);
+
+        for percent in [10.0, 50.0, 0.5, 0.8, 2.0] {
+            sink.emit(InstallProgress::new(
+                "retry_test",
+                InstallPhase::Downloading,
+                InstallStatus::in_progress(percent),
+            ));
+        }
+
+        let recorded = read_event_log(&path).unwrap();
+        let percents: Vec<f64> = recorded
+            .iter()
+            .filter_map(|e| match e.status {
+                InstallStatus::InProgress { percent, .. } => Some(percent),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(percents, [10.0, 50.0, 0.5, 2.0]);
+    }
+
+    #[test]
+    fn test_recording_sink_keeps_final_status_past_cap() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("last_install.jsonl");
+        let sink = RecordingProgressSink::new(Arc::new(MockProgressSink::new()), &path);
+
+        for i in 0..=EVENT_LOG_MAX_IN_PROGRESS {
+            sink.emit(InstallProgress::new(
+                "cap_test",
+                InstallPhase::Extracting,
+                InstallStatus::in_progress(i as f64),
+            ));
+        }
+        sink.emit(InstallProgress::new(
+            "cap_test",
+            InstallPhase::Extracting,
+            InstallStatus::Failed {
+                reason: "disk full".into(),
+            },
+        ));
+
+        let recorded = read_event_log(&path).unwrap();
+        assert_eq!(recorded.len(), EVENT_LOG_MAX_IN_PROGRESS + 1);
+        assert!(matches!(
+            recorded.last().unwrap().status,
+            InstallStatus::Failed { .. }
+        ));
+    }
+
+    #[test]
+    fn test_recording_sink_overwrites_previous_operation() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("last_install.jsonl");
+
+        let first = RecordingProgressSink::new(Arc::new(MockProgressSink::new()), &path);
+        first.emit(InstallProgress::new(
+            "first",
+            InstallPhase::Preparing,
+            InstallStatus::Completed { speed: None },
+        ));
+        drop(first);
+
+        let second = RecordingProgressSink::new(Arc::new(MockProgressSink::new()), &path);
+        second.emit(InstallProgress::new(
+            "second",
+            InstallPhase::Preparing,
+            InstallStatus::Cancelled,
+        ));
+
+        let recorded = read_event_log(&path).unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].task_id, "second");
+    }
+
+    #[test]
+    fn test_read_event_log_skips_truncated_line() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("last_install.jsonl");
+        std::fs::write(
+            &path,
+            "{\"taskId\":\"t\",\"phase\":\"moving\",\"status\":\"completed\"}\n{\"taskId\":\"t\",\"pha",
+        )
+        .unwrap();
+
+        let recorded = read_event_log(&path).unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert!(matches!(recorded[0].phase, InstallPhase::Moving));
+    }
+
+    #[test]
+    fn test_timed_sink_records_spacing() {
+        let sink = Arc::new(TimedMockProgressSink::new());
+        let reporter = InstallReporter::new("timed_test", sink.clone());
+
+        reporter.report(
+            InstallPhase::Downloading,
+            InstallStatus::Started {
+                total_size: None,
+                estimated_secs: None,
+            },
+        );
+        for percent in [10.0, 20.0, 30.0] {
+            std::thread::sleep(Duration::from_millis(20));
+            reporter.report(
+                InstallPhase::Downloading,
+                InstallStatus::in_progress(percent),
+            );
+        }
+        reporter.report(
+            InstallPhase::Downloading,
+            InstallStatus::Completed { speed: None },
+        );
+
+        let events = sink.events();
+        assert_eq!(events.len(), 5);
+        assert!(events.windows(2).all(|w| w[0].0 <= w[1].0));
+
+        let in_progress =
+            sink.intervals_where(|p| matches!(p.status, InstallStatus::InProgress { .. }));
+        assert_eq!(in_progress.len(), 2);
+        assert!(sink.min_in_progress_interval().unwrap() >= Duration::from_millis(20));
+    }
+
     #[test]
     fn test_progress_throttle_constant() {
         // Verify constant is set
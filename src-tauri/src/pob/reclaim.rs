@@ -0,0 +1,161 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use specta::Type;
+
+use crate::pob::diagnostics::logs_newest_first;
+
+/// Outcome of [`reclaim`].
+#[derive(Debug, Clone, Default, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReclaimReport {
+    pub freed_bytes: f64,
+    /// Removed paths, in removal order
+    pub removed: Vec<String>,
+    /// `freed_bytes` reached the requested target
+    pub target_met: bool,
+}
+
+/// Delete `candidates` in order until at least `target_bytes` are freed.
+/// Missing paths are skipped, and a path that can't be deleted is logged and
+/// skipped. Blocking.
+pub fn reclaim(candidates: &[PathBuf], target_bytes: u64) -> ReclaimReport {
+    let mut report = ReclaimReport::default();
+    let mut freed = 0u64;
+
+    for path in candidates {
+        if freed >= target_bytes {
+            break;
+        }
+        let Ok(metadata) = std::fs::symlink_metadata(path) else {
+            continue;
+        };
+
+        let (size, result) = if metadata.is_dir() {
+            (dir_size(path), std::fs::remove_dir_all(path))
+        } else {
+            (metadata.len(), std::fs::remove_file(path))
+        };
+        match result {
+            Ok(()) => {
+                tracing::info!(
+                    operation = "reclaim",
+                    path = %path.display(),
+                    bytes = size,
+                    "Removed to free disk space"
+                );
+                freed += size;
+                report.removed.push(path.display().to_string());
+            }
+            Err(e) => {
+                tracing::warn!(
+                    operation = "reclaim",
+                    path = %path.display(),
+                    error = %e,
+                    "Failed to remove, skipping"
+                );
+            }
+        }
+    }
+
+    report.freed_bytes = freed as f64;
+    report.target_met = freed >= target_bytes;
+    report
+}
+
+/// `pob_*` task directories in `temp_dir` (see `generate_task_id`).
+pub fn task_temp_dirs(temp_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(temp_dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_dir()
+                && path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|name| name.starts_with("pob_"))
+        })
+        .collect()
+}
+
+/// Every log in `log_dir` except the newest (still being written), oldest first.
+pub fn surplus_logs(log_dir: &Path) -> Vec<PathBuf> {
+    let mut logs = logs_newest_first(log_dir);
+    if !logs.is_empty() {
+        logs.remove(0);
+    }
+    logs.reverse();
+    logs
+}
+
+/// Total size of regular files under `path`. Symlinks are not followed.
+pub(crate) fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(ty) if ty.is_dir() => dir_size(&entry.path()),
+            Ok(ty) if ty.is_file() => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            _ => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reclaim_stops_at_target() {
+        let temp = tempfile::tempdir().unwrap();
+        let first = temp.path().join("pob_1");
+        let second = temp.path().join("pob_2");
+        let keep = temp.path().join("other");
+        for dir in [&first, &second, &keep] {
+            std::fs::create_dir_all(dir.join("nested")).unwrap();
+            std::fs::write(dir.join("nested/data.bin"), [0u8; 100]).unwrap();
+        }
+
+        let mut candidates = task_temp_dirs(temp.path());
+        candidates.sort();
+        assert_eq!(candidates, [first.clone(), second.clone()]);
+
+        let report = reclaim(&candidates, 50);
+        assert_eq!(report.freed_bytes, 100.0);
+        assert!(report.target_met);
+        assert!(!first.exists());
+        assert!(second.exists());
+        assert!(keep.exists());
+
+        let report = reclaim(&[temp.path().join("missing"), second.clone()], 1000);
+        assert_eq!(report.removed.len(), 1);
+        assert!(!report.target_met);
+    }
+
+    #[test]
+    fn test_surplus_logs_keep_newest() {
+        let temp = tempfile::tempdir().unwrap();
+        let now = std::time::SystemTime::now();
+        for (name, age) in [("old.log", 20), ("current.log", 0), ("older.log", 30)] {
+            let path = temp.path().join(name);
+            std::fs::write(&path, "log").unwrap();
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(now - std::time::Duration::from_secs(age))
+                .unwrap();
+        }
+
+        let names: Vec<_> = surplus_logs(temp.path())
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["older.log", "old.log"]);
+    }
+}
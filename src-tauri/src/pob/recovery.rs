@@ -0,0 +1,137 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::pob::{error::PobError, google_drive::GoogleDriveFileInfo, progress::InstallPhase};
+
+/// Stage an install had reached, persisted as `<data_dir>/install_state.json`
+/// at each transition and removed when the install returns. Finding it at
+/// startup means the app died mid-install.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallState {
+    pub phase: InstallPhase,
+    pub file_id: String,
+    pub file_name: String,
+    /// User data was backed up before the swap
+    pub backed_up: bool,
+    pub updated_at: String,
+}
+
+impl InstallState {
+    pub fn new(file_info: &GoogleDriveFileInfo, phase: InstallPhase, backed_up: bool) -> Self {
+        Self {
+            phase,
+            file_id: file_info.id.clone(),
+            file_name: file_info.name.clone(),
+            backed_up,
+            updated_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Load the state at `path`, `None` if absent or unreadable.
+    pub fn load(path: &Path) -> Option<Self> {
+        let data = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&data)
+            .inspect_err(|e| {
+                tracing::warn!(
+                    operation = "recovery",
+                    path = %path.display(),
+                    error = %e,
+                    "Ignoring unreadable install state"
+                );
+            })
+            .ok()
+    }
+
+    pub async fn save(&self, path: &Path) -> Result<(), PobError> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let data = serde_json::to_string_pretty(self)?;
+        tokio::fs::write(path, data).await?;
+        Ok(())
+    }
+
+    /// Whether the new install had been moved into place.
+    /// While `Moving`, that depends on which directories are left.
+    pub fn swapped(&self, old_exists: bool, new_exists: bool) -> bool {
+        match self.phase {
+            InstallPhase::Restoring | InstallPhase::Finalizing => true,
+            InstallPhase::Moving => old_exists && !new_exists,
+            _ => false,
+        }
+    }
+}
+
+/// What startup recovery did about an interrupted install, or recommends.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "camelCase", tag = "action")]
+pub enum RecoveryAction {
+    /// No interrupted install
+    None,
+    /// A half-extracted `.new` was removed; the install was never touched
+    CleanedUp,
+    /// The install was missing mid-swap, so `.old` was moved back in place
+    RolledBack,
+    /// The new version is in place but user data may not have been restored;
+    /// restoring the newest backup is recommended
+    #[serde(rename_all = "camelCase")]
+    RestoreUserData { file_name: String },
+    /// Interrupted before the swap; installing `file_name` again resumes the download
+    #[serde(rename_all = "camelCase")]
+    ResumeInstall { file_id: String, file_name: String },
+}
+
+impl RecoveryAction {
+    /// Whether the user has to act on it.
+    pub fn needs_prompt(&self) -> bool {
+        matches!(
+            self,
+            Self::RestoreUserData { .. } | Self::ResumeInstall { .. }
+        )
+    }
+}
+
+/// Resolve the install state location under `data_dir`.
+pub fn install_state_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("install_state.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_info() -> GoogleDriveFileInfo {
+        GoogleDriveFileInfo::test_release("drive_id")
+    }
+
+    #[tokio::test]
+    async fn test_install_state_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = install_state_path(temp.path());
+        assert!(InstallState::load(&path).is_none());
+
+        InstallState::new(&file_info(), InstallPhase::BackingUp, false)
+            .save(&path)
+            .await
+            .unwrap();
+        let state = InstallState::load(&path).unwrap();
+        assert_eq!(state.phase, InstallPhase::BackingUp);
+        assert_eq!(state.file_id, "drive_id");
+
+        std::fs::write(&path, "{").unwrap();
+        assert!(InstallState::load(&path).is_none());
+    }
+
+    #[test]
+    fn test_swapped_while_moving_depends_on_leftovers() {
+        let state = |phase| InstallState::new(&file_info(), phase, true);
+        assert!(!state(InstallPhase::Extracting).swapped(true, false));
+        assert!(state(InstallPhase::Restoring).swapped(false, false));
+        // `.new` still there: the swap hadn't moved it in yet
+        assert!(!state(InstallPhase::Moving).swapped(true, true));
+        assert!(state(InstallPhase::Moving).swapped(true, false));
+    }
+}
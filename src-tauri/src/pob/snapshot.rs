@@ -0,0 +1,285 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::pob::{error::PobError, reclaim::dir_size};
+
+/// Metadata file written at the root of every snapshot directory
+pub const SNAPSHOT_META: &str = "snapshot.json";
+
+/// Directory name format of snapshots and install backups, in local time
+pub const STAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// Labels longer than this are cut off
+const MAX_LABEL_CHARS: usize = 100;
+
+/// A manual copy of the user data in an install, taken on request and never
+/// rotated by installs. Lives in `<data_dir>/snapshots/<id>/`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupSnapshot {
+    /// Directory name under `snapshots/`, `%Y%m%d-%H%M%S` with a suffix on collision
+    pub id: String,
+    pub label: Option<String>,
+    /// PoB version installed when the snapshot was taken
+    pub version: Option<String>,
+    pub created_at: String,
+    /// Backup targets that existed and were copied
+    pub entries: u32,
+}
+
+impl BackupSnapshot {
+    pub async fn save(&self, dir: &Path) -> Result<(), PobError> {
+        let data = serde_json::to_string_pretty(self)?;
+        tokio::fs::write(dir.join(SNAPSHOT_META), data).await?;
+        Ok(())
+    }
+
+    /// Read the metadata of the snapshot at `dir`, `None` if it has none.
+    pub fn load(dir: &Path) -> Option<Self> {
+        let data = std::fs::read_to_string(dir.join(SNAPSHOT_META)).ok()?;
+        serde_json::from_str(&data).ok()
+    }
+}
+
+/// Completed snapshots in `dir`, newest first. Staging dirs and ones without
+/// readable metadata are left out.
+pub fn snapshots_newest_first(dir: &Path) -> Vec<BackupSnapshot> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut snapshots: Vec<_> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && path.extension().is_none_or(|ext| ext != "new"))
+        .filter_map(|path| BackupSnapshot::load(&path))
+        .collect();
+    snapshots.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    snapshots
+}
+
+/// Trim `label` and drop it if blank.
+pub fn normalize_label(label: Option<String>) -> Option<String> {
+    let label = label?;
+    let label = label.trim();
+    (!label.is_empty()).then(|| label.chars().take(MAX_LABEL_CHARS).collect())
+}
+
+/// First free snapshot directory for `stamp` under `snapshots_dir`.
+pub fn unique_snapshot_dir(snapshots_dir: &Path, stamp: &str) -> (String, PathBuf) {
+    let mut id = stamp.to_string();
+    let mut n = 2;
+    while snapshots_dir.join(&id).exists() {
+        id = format!("{stamp}-{n}");
+        n += 1;
+    }
+    let path = snapshots_dir.join(&id);
+    (id, path)
+}
+
+/// A backup taken automatically before an install replaced user data.
+/// Lives in `<install_root>/backup/<name>/`; the oldest are pruned past `max_backups`.
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupEntry {
+    /// Directory name under `backup/`, `%Y%m%d-%H%M%S` with a suffix on collision
+    pub name: String,
+    pub created_at: String,
+    /// Total size of the backed up files
+    pub size_bytes: f64,
+}
+
+/// Timestamp and collision suffix of a backup directory name, `None` for
+/// anything else (staging dirs, the pre-rotation flat layout).
+fn parse_backup_name(name: &str) -> Option<(NaiveDateTime, u32)> {
+    let stamp = NaiveDateTime::parse_from_str(name.get(..15)?, STAMP_FORMAT).ok()?;
+    let n = match &name[15..] {
+        "" => 1,
+        rest => rest.strip_prefix('-')?.parse().ok()?,
+    };
+    Some((stamp, n))
+}
+
+/// Names of the completed backups in `dir`, newest first.
+pub fn backups_newest_first(dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<_> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|ty| ty.is_dir()))
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let key = parse_backup_name(&name)?;
+            Some((key, name))
+        })
+        .collect();
+    backups.sort_by(|a, b| b.0.cmp(&a.0));
+    backups.into_iter().map(|(_, name)| name).collect()
+}
+
+/// Name for a new backup in `dir` taken at `stamp`. Unlike
+/// [`unique_snapshot_dir`] it sorts after every existing backup from the same
+/// second, even when lower suffixes were freed by pruning.
+pub fn next_backup_name(dir: &Path, stamp: &str) -> String {
+    let taken = backups_newest_first(dir)
+        .iter()
+        .filter(|name| name.starts_with(stamp))
+        .filter_map(|name| parse_backup_name(name))
+        .map(|(_, n)| n)
+        .max();
+    match taken {
+        Some(n) => format!("{stamp}-{}", n + 1),
+        None => stamp.to_string(),
+    }
+}
+
+/// Describe the backup `name` in `dir`, walking it for its size.
+pub fn backup_entry(dir: &Path, name: String) -> BackupEntry {
+    let created_at = parse_backup_name(&name)
+        .map(
+            |(stamp, _)| match Local.from_local_datetime(&stamp).earliest() {
+                Some(local) => local.to_rfc3339(),
+                None => stamp.and_utc().to_rfc3339(),
+            },
+        )
+        .unwrap_or_default();
+    BackupEntry {
+        size_bytes: dir_size(&dir.join(&name)) as f64,
+        name,
+        created_at,
+    }
+}
+
+/// Backups in `dir` left half-copied by an interrupted install (`<name>.new`).
+pub fn staged_backups(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "new"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_label() {
+        assert_eq!(normalize_label(None), None);
+        assert_eq!(normalize_label(Some("   ".into())), None);
+        assert_eq!(
+            normalize_label(Some("  before 3.25 ".into())),
+            Some("before 3.25".to_string())
+        );
+        let long = "가".repeat(MAX_LABEL_CHARS + 10);
+        assert_eq!(
+            normalize_label(Some(long)).unwrap().chars().count(),
+            MAX_LABEL_CHARS
+        );
+    }
+
+    #[tokio::test]
+    async fn test_snapshots_newest_first() {
+        let temp = tempfile::tempdir().unwrap();
+        for (id, created_at) in [
+            ("20260101-120000", "2026-01-01T03:00:00+00:00"),
+            ("20260102-120000", "2026-01-02T03:00:00+00:00"),
+        ] {
+            let dir = temp.path().join(id);
+            std::fs::create_dir_all(&dir).unwrap();
+            let snapshot = BackupSnapshot {
+                id: id.to_string(),
+                label: None,
+                version: None,
+                created_at: created_at.to_string(),
+                entries: 1,
+            };
+            snapshot.save(&dir).await.unwrap();
+        }
+        std::fs::create_dir_all(temp.path().join("20260103-120000.new")).unwrap();
+        std::fs::create_dir_all(temp.path().join("no-meta")).unwrap();
+
+        let ids: Vec<_> = snapshots_newest_first(temp.path())
+            .into_iter()
+            .map(|snapshot| snapshot.id)
+            .collect();
+        assert_eq!(ids, ["20260102-120000", "20260101-120000"]);
+    }
+
+    #[test]
+    fn test_unique_snapshot_dir_suffixes_collisions() {
+        let temp = tempfile::tempdir().unwrap();
+        let (id, path) = unique_snapshot_dir(temp.path(), "20260101-120000");
+        assert_eq!(id, "20260101-120000");
+        std::fs::create_dir_all(&path).unwrap();
+
+        let (id, _) = unique_snapshot_dir(temp.path(), "20260101-120000");
+        assert_eq!(id, "20260101-120000-2");
+    }
+
+    #[test]
+    fn test_backups_newest_first() {
+        let temp = tempfile::tempdir().unwrap();
+        for name in [
+            "20260101-120000",
+            "20260301-080000",
+            "20260101-120000-2",
+            "20260101-120000-10",
+            "20260401-000000.new",
+            "POE1 POB",
+        ] {
+            std::fs::create_dir_all(temp.path().join(name)).unwrap();
+        }
+        std::fs::write(temp.path().join("20260501-000000"), "not a dir").unwrap();
+
+        assert_eq!(
+            backups_newest_first(temp.path()),
+            [
+                "20260301-080000",
+                "20260101-120000-10",
+                "20260101-120000-2",
+                "20260101-120000",
+            ]
+        );
+        assert_eq!(
+            staged_backups(temp.path()),
+            [temp.path().join("20260401-000000.new")]
+        );
+    }
+
+    #[test]
+    fn test_next_backup_name_sorts_last() {
+        let temp = tempfile::tempdir().unwrap();
+        assert_eq!(
+            next_backup_name(temp.path(), "20260101-120000"),
+            "20260101-120000"
+        );
+
+        // `20260101-120000` itself was pruned, `-3` is the newest
+        for name in ["20260101-120000-2", "20260101-120000-3", "20260101-115959"] {
+            std::fs::create_dir_all(temp.path().join(name)).unwrap();
+        }
+        assert_eq!(
+            next_backup_name(temp.path(), "20260101-120000"),
+            "20260101-120000-4"
+        );
+    }
+
+    #[test]
+    fn test_backup_entry() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join("20260101-120000/POE1 POB");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("Settings.xml"), "12345").unwrap();
+
+        let entry = backup_entry(temp.path(), "20260101-120000".into());
+        assert_eq!(entry.size_bytes, 5.0);
+        assert!(entry.created_at.starts_with("2026-01-01T12:00:00"));
+    }
+}
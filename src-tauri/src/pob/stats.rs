@@ -0,0 +1,164 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::pob::{
+    error::PobError,
+    progress::{InstallPhase, PhaseWeights},
+};
+
+/// Smoothing factor for learned phase durations; higher favors the latest install.
+const PHASE_EWMA_ALPHA: f64 = 0.3;
+
+/// Measurements from previous installs, persisted as `<data_dir>/install_stats.json`.
+/// Used only for estimates; losing the file just means no estimate next time.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InstallStats {
+    /// Uncompressed bytes written per second during the last extraction
+    pub extract_bytes_per_sec: Option<f64>,
+    /// Smoothed seconds spent in each install phase over past installs
+    pub phase_secs: HashMap<InstallPhase, f64>,
+}
+
+impl InstallStats {
+    /// Load stats from `path`, falling back to empty stats if absent or unreadable.
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub async fn save(&self, path: &Path) -> Result<(), PobError> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let data = serde_json::to_string_pretty(self)?;
+        tokio::fs::write(path, data).await?;
+        Ok(())
+    }
+
+    /// Estimated seconds to extract `total_bytes`, if a throughput has been measured.
+    pub fn estimate_extract_secs(&self, total_bytes: u64) -> Option<u32> {
+        let rate = self.extract_bytes_per_sec.filter(|r| *r > 0.0)?;
+        Some((total_bytes as f64 / rate).ceil() as u32)
+    }
+
+    /// Fold one install's measured phase durations into the running averages.
+    pub fn record_phase_secs(&mut self, laps: &[(InstallPhase, f64)]) {
+        for &(phase, secs) in laps {
+            self.phase_secs
+                .entry(phase)
+                .and_modify(|avg| *avg = PHASE_EWMA_ALPHA * secs + (1.0 - PHASE_EWMA_ALPHA) * *avg)
+                .or_insert(secs);
+        }
+    }
+
+    /// Overall-progress weights from learned durations, or the static defaults
+    /// until every weighted phase has been measured at least once.
+    pub fn phase_weights(&self) -> PhaseWeights {
+        let defaults = PhaseWeights::default();
+        let learned: Option<Vec<(InstallPhase, f64)>> = defaults
+            .phases()
+            .map(|phase| self.phase_secs.get(&phase).map(|secs| (phase, *secs)))
+            .collect();
+        learned.and_then(PhaseWeights::new).unwrap_or(defaults)
+    }
+}
+
+/// Measures consecutive install phases for [`InstallStats::record_phase_secs`].
+#[derive(Debug)]
+pub struct PhaseClock {
+    last: Instant,
+    laps: Vec<(InstallPhase, f64)>,
+}
+
+impl PhaseClock {
+    pub fn start() -> Self {
+        Self {
+            last: Instant::now(),
+            laps: Vec::new(),
+        }
+    }
+
+    /// Attribute the time since the previous lap (or start) to `phase`.
+    pub fn lap(&mut self, phase: InstallPhase) {
+        let now = Instant::now();
+        self.laps
+            .push((phase, now.duration_since(self.last).as_secs_f64()));
+        self.last = now;
+    }
+
+    pub fn laps(&self) -> &[(InstallPhase, f64)] {
+        &self.laps
+    }
+}
+
+/// Resolve the stats file location under `data_dir`.
+pub fn stats_file_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("install_stats.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_extract_secs() {
+        let stats = InstallStats {
+            extract_bytes_per_sec: Some(100.0 * 1024.0 * 1024.0),
+            ..Default::default()
+        };
+
+        assert_eq!(stats.estimate_extract_secs(450 * 1024 * 1024), Some(5));
+        assert_eq!(stats.estimate_extract_secs(0), Some(0));
+    }
+
+    #[test]
+    fn test_estimate_without_measurement() {
+        assert_eq!(InstallStats::default().estimate_extract_secs(1024), None);
+
+        let zero = InstallStats {
+            extract_bytes_per_sec: Some(0.0),
+            ..Default::default()
+        };
+        assert_eq!(zero.estimate_extract_secs(1024), None);
+    }
+
+    #[test]
+    fn test_phase_weights_fall_back_until_all_phases_measured() {
+        let mut stats = InstallStats::default();
+        stats.record_phase_secs(&[(InstallPhase::Downloading, 10.0)]);
+        assert_eq!(stats.phase_weights(), PhaseWeights::default());
+
+        stats.record_phase_secs(&[
+            (InstallPhase::Extracting, 30.0),
+            (InstallPhase::BackingUp, 0.0),
+            (InstallPhase::Moving, 0.0),
+            (InstallPhase::Restoring, 0.0),
+        ]);
+        // IO-bound machine: extraction dominates the bar
+        let weights = stats.phase_weights();
+        assert_eq!(
+            weights.overall_percent(InstallPhase::Downloading, 100.0),
+            Some(25.0)
+        );
+        assert_eq!(
+            weights.overall_percent(InstallPhase::Extracting, 50.0),
+            Some(62.5)
+        );
+    }
+
+    #[test]
+    fn test_record_phase_secs_smooths() {
+        let mut stats = InstallStats::default();
+        stats.record_phase_secs(&[(InstallPhase::Downloading, 10.0)]);
+        stats.record_phase_secs(&[(InstallPhase::Downloading, 20.0)]);
+        assert_eq!(stats.phase_secs[&InstallPhase::Downloading], 13.0);
+    }
+}
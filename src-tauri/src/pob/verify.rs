@@ -0,0 +1,906 @@
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{self, Read},
+    num::NonZeroU32,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tokio_util::sync::CancellationToken;
+
+use crate::pob::{
+    error::PobError,
+    manager::{
+        decode_entry_name, decode_raw_entry_name, enclosed_entry_path, is_user_data_path,
+        nested_prefix,
+    },
+    progress::{InstallPhase, InstallReporter, InstallStatus},
+};
+
+/// Upper bound on entries returned in an [`InstallDiff`]; the counts stay exact.
+pub const MAX_DIFF_ENTRIES: usize = 500;
+
+/// Manifest written next to the extracted files (see [`InstallManifest`]).
+pub const MANIFEST_FILE_NAME: &str = "install_manifest.json";
+
+/// Files the app itself writes into the install folder; never reported as extra.
+const APP_MANAGED_FILES: &[&str] = &["pob_version.json", MANIFEST_FILE_NAME];
+
+const PROGRESS_REPORT_INTERVAL_MS: u64 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum DiffKind {
+    /// Size or CRC differs from the archive
+    Modified,
+    /// In the archive but not installed
+    Missing,
+    /// Installed but not in the archive
+    Extra,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FileDiff {
+    /// Install-relative path with `/` separators
+    pub path: String,
+    pub kind: DiffKind,
+}
+
+/// Result of comparing an install against a release archive.
+#[derive(Debug, Clone, Default, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallDiff {
+    pub entries: Vec<FileDiff>,
+    pub modified: u32,
+    pub missing: u32,
+    pub extra: u32,
+    /// Archive size of the modified and missing files, roughly what an update rewrites
+    pub changed_bytes: f64,
+    /// `entries` was cut off at [`MAX_DIFF_ENTRIES`]
+    pub truncated: bool,
+}
+
+impl InstallDiff {
+    pub fn is_clean(&self) -> bool {
+        self.modified == 0 && self.missing == 0 && self.extra == 0
+    }
+
+    fn push(&mut self, path: &Path, kind: DiffKind) {
+        match kind {
+            DiffKind::Modified => self.modified += 1,
+            DiffKind::Missing => self.missing += 1,
+            DiffKind::Extra => self.extra += 1,
+        }
+        if self.entries.len() < MAX_DIFF_ENTRIES {
+            self.entries.push(FileDiff {
+                path: path.to_string_lossy().replace('\\', "/"),
+                kind,
+            });
+        } else {
+            self.truncated = true;
+        }
+    }
+}
+
+/// Top-level folders every release ships. Only their presence is checked: an
+/// archive without one is rejected and an install missing one is reported, but
+/// what is inside them isn't (see [`InstallDiff`] for that).
+pub const REQUIRED_DIRS: &[&str] = &["POE1 POB", "POE2 POB", "Data"];
+
+/// Structural check of an install, e.g. after a crash mid-install. Only
+/// looks at what exists; [`InstallDiff`] compares file contents.
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityReport {
+    pub exe_exists: bool,
+    /// `pob_version.json` exists and parses
+    pub version_file_valid: bool,
+    /// Entries of [`REQUIRED_DIRS`] that are missing
+    pub missing_dirs: Vec<String>,
+    /// Release recorded in the version file, what a repair reinstalls
+    pub file_id: Option<String>,
+}
+
+impl IntegrityReport {
+    pub fn is_healthy(&self) -> bool {
+        self.exe_exists && self.version_file_valid && self.missing_dirs.is_empty()
+    }
+}
+
+/// A backup file that could not be read back.
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupIssue {
+    /// Backup-relative path with `/` separators
+    pub path: String,
+    pub reason: String,
+}
+
+/// Result of reading back every file in the backup (see [`check_backup`]).
+#[derive(Debug, Clone, Default, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupCheck {
+    /// Files read back in full
+    pub readable: u32,
+    pub unreadable: Vec<BackupIssue>,
+    /// `unreadable` was cut off at [`MAX_DIFF_ENTRIES`]
+    pub truncated: bool,
+}
+
+impl BackupCheck {
+    pub fn is_clean(&self) -> bool {
+        self.unreadable.is_empty()
+    }
+
+    fn push_issue(&mut self, path: &Path, error: &io::Error) {
+        if self.unreadable.len() < MAX_DIFF_ENTRIES {
+            self.unreadable.push(BackupIssue {
+                path: path.to_string_lossy().replace('\\', "/"),
+                reason: error.to_string(),
+            });
+        } else {
+            self.truncated = true;
+        }
+    }
+}
+
+/// Expected state of one extracted game file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestEntry {
+    /// Install-relative path with `/` separators
+    pub path: String,
+    pub size: u64,
+    pub crc32: u32,
+    /// Modification time (Unix seconds) as left on disk by extraction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mtime: Option<u64>,
+}
+
+/// Game files written by the last extraction, so the install can be checked
+/// without the release archive. User-data paths are never recorded.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallManifest {
+    pub entries: Vec<ManifestEntry>,
+}
+
+impl InstallManifest {
+    pub fn push(&mut self, path: &Path, size: u64, crc32: u32, mtime: Option<SystemTime>) {
+        self.entries.push(ManifestEntry {
+            path: path.to_string_lossy().replace('\\', "/"),
+            size,
+            crc32,
+            mtime: mtime.and_then(unix_secs),
+        });
+    }
+
+    /// Load the manifest from `install_path`, `None` if the install predates manifests.
+    pub fn load(install_path: &Path) -> Result<Option<Self>, PobError> {
+        match std::fs::read_to_string(install_path.join(MANIFEST_FILE_NAME)) {
+            Ok(data) => Ok(Some(serde_json::from_str(&data)?)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, install_path: &Path) -> Result<(), PobError> {
+        let data = serde_json::to_string(self)?;
+        std::fs::write(install_path.join(MANIFEST_FILE_NAME), data)?;
+        Ok(())
+    }
+}
+
+fn unix_secs(time: SystemTime) -> Option<u64> {
+    time.duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs())
+}
+
+/// Check `install_path` against its manifest. Quick mode compares existence,
+/// size and mtime only; `full` recomputes every CRC instead of trusting mtime.
+/// Paths under `user_data_targets` are ignored. Blocking.
+pub(crate) fn verify_manifest(
+    install_path: &Path,
+    manifest: &InstallManifest,
+    full: bool,
+    user_data_targets: &[PathBuf],
+    cancel_token: &CancellationToken,
+    reporter: &InstallReporter,
+) -> Result<InstallDiff, PobError> {
+    let entry_count = manifest.entries.len();
+    reporter.report(
+        InstallPhase::Verifying,
+        InstallStatus::Started {
+            total_size: NonZeroU32::new(entry_count as u32),
+            estimated_secs: None,
+        },
+    );
+
+    let mut diff = InstallDiff::default();
+    let mut expected = HashSet::new();
+    let mut last_report = Instant::now();
+
+    for (i, entry) in manifest.entries.iter().enumerate() {
+        if cancel_token.is_cancelled() {
+            tracing::info!(phase = "verify", "Verification cancelled");
+            reporter.report(InstallPhase::Verifying, InstallStatus::Cancelled);
+            return Err(PobError::Cancelled);
+        }
+
+        let path = PathBuf::from(&entry.path);
+        let installed = install_path.join(&path);
+        let kind = match std::fs::metadata(&installed) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Some(DiffKind::Missing),
+            Err(e) => return Err(e.into()),
+            Ok(metadata) if !metadata.is_file() || metadata.len() != entry.size => {
+                Some(DiffKind::Modified)
+            }
+            Ok(_) if full && file_crc32(&installed)? != entry.crc32 => Some(DiffKind::Modified),
+            Ok(metadata)
+                if !full
+                    && entry.mtime.is_some()
+                    && metadata.modified().ok().and_then(unix_secs) != entry.mtime =>
+            {
+                Some(DiffKind::Modified)
+            }
+            Ok(_) => None,
+        };
+        if let Some(kind) = kind {
+            diff.push(&path, kind);
+            diff.changed_bytes += entry.size as f64;
+        }
+        expected.insert(path);
+
+        if last_report.elapsed() >= Duration::from_millis(PROGRESS_REPORT_INTERVAL_MS) {
+            let percent = (i + 1) as f64 / entry_count as f64 * 100.0;
+            reporter.report(InstallPhase::Verifying, InstallStatus::in_progress(percent));
+            last_report = Instant::now();
+        }
+    }
+
+    push_extra_files(install_path, &expected, user_data_targets, &mut diff)?;
+
+    tracing::info!(
+        phase = "verify",
+        full,
+        modified = diff.modified,
+        missing = diff.missing,
+        extra = diff.extra,
+        "Manifest verification finished"
+    );
+    reporter.report(
+        InstallPhase::Verifying,
+        InstallStatus::Completed { speed: None },
+    );
+    Ok(diff)
+}
+
+/// Read every file under `backup_path` end to end without writing anything,
+/// reporting byte-based progress. Backups record no checksums, so a file counts
+/// as restorable when it opens and reads back at its listed size. Blocking.
+pub(crate) fn check_backup(
+    backup_path: &Path,
+    cancel_token: &CancellationToken,
+    reporter: &InstallReporter,
+) -> Result<BackupCheck, PobError> {
+    let mut files = Vec::new();
+    collect_files(backup_path, Path::new(""), &mut files)?;
+    let sizes: Vec<u64> = files
+        .iter()
+        .map(|path| {
+            std::fs::metadata(backup_path.join(path))
+                .map(|m| m.len())
+                .unwrap_or(0)
+        })
+        .collect();
+    let total_bytes: u64 = sizes.iter().sum();
+
+    reporter.report(
+        InstallPhase::Verifying,
+        InstallStatus::Started {
+            total_size: NonZeroU32::new(total_bytes as u32),
+            estimated_secs: None,
+        },
+    );
+
+    let mut check = BackupCheck::default();
+    let mut read_bytes = 0u64;
+    let mut last_report = Instant::now();
+
+    for (path, expected_len) in files.iter().zip(sizes) {
+        if cancel_token.is_cancelled() {
+            tracing::info!(phase = "verify", "Backup check cancelled");
+            reporter.report(InstallPhase::Verifying, InstallStatus::Cancelled);
+            return Err(PobError::Cancelled);
+        }
+
+        match read_len(&backup_path.join(path)) {
+            Ok(len) if len == expected_len => check.readable += 1,
+            Ok(len) => check.push_issue(
+                path,
+                &io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!("read {len} of {expected_len} bytes"),
+                ),
+            ),
+            Err(e) => check.push_issue(path, &e),
+        }
+        read_bytes += expected_len;
+
+        if total_bytes > 0
+            && last_report.elapsed() >= Duration::from_millis(PROGRESS_REPORT_INTERVAL_MS)
+        {
+            let percent = read_bytes as f64 / total_bytes as f64 * 100.0;
+            reporter.report(InstallPhase::Verifying, InstallStatus::in_progress(percent));
+            last_report = Instant::now();
+        }
+    }
+
+    tracing::info!(
+        phase = "verify",
+        readable = check.readable,
+        unreadable = check.unreadable.len(),
+        "Backup check finished"
+    );
+    reporter.report(
+        InstallPhase::Verifying,
+        InstallStatus::Completed { speed: None },
+    );
+    Ok(check)
+}
+
+/// Read a file to the end, returning the number of bytes read.
+fn read_len(path: &Path) -> io::Result<u64> {
+    io::copy(&mut File::open(path)?, &mut io::sink())
+}
+
+/// CRC32 of a file's contents, as stored in ZIP headers.
+pub fn file_crc32(path: &Path) -> io::Result<u32> {
+    let mut file = File::open(path)?;
+    let mut hasher = crc32fast::Hasher::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher.finalize())
+}
+
+/// Structure and safety findings for an arbitrary archive (see [`inspect_archive`]).
+#[derive(Debug, Clone, Default, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveReport {
+    pub file_count: u32,
+    /// Sum of the uncompressed sizes declared in the central directory
+    pub total_uncompressed_bytes: f64,
+    /// Folder wrapping the release folders, stripped on extraction
+    pub nested_prefix: Option<String>,
+    /// `POE1 POB/`, `POE2 POB/` or `Data/` was found; extraction refuses archives without
+    pub has_required_folders: bool,
+    /// Entries extraction would refuse or skip for escaping the install folder
+    pub dangerous_paths: Vec<String>,
+    /// Entries marked as Unix symlinks, which extraction would write as plain files
+    pub symlinks: Vec<String>,
+    /// A list was cut off at [`MAX_DIFF_ENTRIES`]
+    pub truncated: bool,
+}
+
+impl ArchiveReport {
+    fn push(list: &mut Vec<String>, truncated: &mut bool, name: String) {
+        if list.len() < MAX_DIFF_ENTRIES {
+            list.push(name);
+        } else {
+            *truncated = true;
+        }
+    }
+}
+
+/// Read the central directory of the ZIP at `zip_path` and check it the way
+/// extraction would, without writing anything. Blocking.
+pub(crate) fn inspect_archive(zip_path: &Path) -> Result<ArchiveReport, PobError> {
+    /// File type bits of a Unix mode, and the symlink type
+    const S_IFMT: u32 = 0o170000;
+    const S_IFLNK: u32 = 0o120000;
+
+    let mut archive = zip::ZipArchive::new(File::open(zip_path)?)?;
+    let mut report = ArchiveReport {
+        file_count: archive.len() as u32,
+        ..Default::default()
+    };
+    let mut names = Vec::with_capacity(archive.len());
+
+    for i in 0..archive.len() {
+        let file = archive.by_index_raw(i)?;
+        let name = decode_entry_name(&file).to_string();
+        report.total_uncompressed_bytes += file.size() as f64;
+
+        if enclosed_entry_path(&name).is_none() {
+            let raw_name = String::from_utf8_lossy(file.name_raw()).into_owned();
+            ArchiveReport::push(&mut report.dangerous_paths, &mut report.truncated, raw_name);
+        }
+        if file
+            .unix_mode()
+            .is_some_and(|mode| mode & S_IFMT == S_IFLNK)
+        {
+            ArchiveReport::push(&mut report.symlinks, &mut report.truncated, name.clone());
+        }
+        names.push(name);
+    }
+
+    if let Ok(prefix) = nested_prefix(names.iter().map(String::as_str)) {
+        report.has_required_folders = true;
+        report.nested_prefix = prefix.map(|p| p.to_string_lossy().replace('\\', "/"));
+    }
+
+    tracing::info!(
+        operation = "inspect_archive",
+        path = %zip_path.display(),
+        files = report.file_count,
+        dangerous = report.dangerous_paths.len(),
+        symlinks = report.symlinks.len(),
+        has_required_folders = report.has_required_folders,
+        "Inspected archive"
+    );
+    Ok(report)
+}
+
+/// Compare `install_path` against the archive at `zip_path` using the central
+/// directory's sizes and CRCs, without extracting. Paths under
+/// `user_data_targets` are ignored on both sides. Blocking.
+pub(crate) fn diff_install(
+    zip_path: &Path,
+    install_path: &Path,
+    user_data_targets: &[PathBuf],
+    cancel_token: &CancellationToken,
+    reporter: &InstallReporter,
+) -> Result<InstallDiff, PobError> {
+    let mut archive = zip::ZipArchive::new(File::open(zip_path)?)?;
+    let mut entries = Vec::with_capacity(archive.len());
+    for i in 0..archive.len() {
+        let file = archive.by_index_raw(i)?;
+        entries.push(ArchiveEntry {
+            name: decode_entry_name(&file).to_string(),
+            size: file.size(),
+            crc32: file.crc32(),
+        });
+    }
+    diff_entries(
+        &entries,
+        install_path,
+        user_data_targets,
+        cancel_token,
+        reporter,
+    )
+}
+
+/// [`diff_install`] against archive entries already read from a central directory.
+pub(crate) fn diff_entries(
+    entries: &[ArchiveEntry],
+    install_path: &Path,
+    user_data_targets: &[PathBuf],
+    cancel_token: &CancellationToken,
+    reporter: &InstallReporter,
+) -> Result<InstallDiff, PobError> {
+    let skip_prefix = nested_prefix(entries.iter().map(|entry| entry.name.as_str()))?;
+    let entry_count = entries.len();
+
+    reporter.report(
+        InstallPhase::Verifying,
+        InstallStatus::Started {
+            total_size: NonZeroU32::new(entry_count as u32),
+            estimated_secs: None,
+        },
+    );
+
+    let mut diff = InstallDiff::default();
+    let mut expected = HashSet::new();
+    let mut last_report = Instant::now();
+
+    for (i, entry) in entries.iter().enumerate() {
+        if cancel_token.is_cancelled() {
+            tracing::info!(phase = "verify", "Verification cancelled");
+            reporter.report(InstallPhase::Verifying, InstallStatus::Cancelled);
+            return Err(PobError::Cancelled);
+        }
+
+        if entry.is_dir() {
+            continue;
+        }
+        let Some(path) = enclosed_entry_path(&entry.name) else {
+            continue;
+        };
+        let path = match skip_prefix {
+            Some(ref prefix) => path
+                .strip_prefix(prefix)
+                .map(Path::to_path_buf)
+                .unwrap_or(path),
+            None => path,
+        };
+        if is_user_data_path(user_data_targets, &path) {
+            continue;
+        }
+
+        let installed = install_path.join(&path);
+        let kind = match std::fs::metadata(&installed) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Some(DiffKind::Missing),
+            Err(e) => return Err(e.into()),
+            Ok(metadata) if !metadata.is_file() || metadata.len() != entry.size => {
+                Some(DiffKind::Modified)
+            }
+            Ok(_) if file_crc32(&installed)? != entry.crc32 => Some(DiffKind::Modified),
+            Ok(_) => None,
+        };
+        if let Some(kind) = kind {
+            diff.push(&path, kind);
+            diff.changed_bytes += entry.size as f64;
+        }
+        expected.insert(path);
+
+        if last_report.elapsed() >= Duration::from_millis(PROGRESS_REPORT_INTERVAL_MS) {
+            let percent = (i + 1) as f64 / entry_count as f64 * 100.0;
+            reporter.report(InstallPhase::Verifying, InstallStatus::in_progress(percent));
+            last_report = Instant::now();
+        }
+    }
+
+    push_extra_files(install_path, &expected, user_data_targets, &mut diff)?;
+
+    tracing::info!(
+        phase = "verify",
+        modified = diff.modified,
+        missing = diff.missing,
+        extra = diff.extra,
+        changed_bytes = diff.changed_bytes,
+        "Verification finished"
+    );
+    reporter.report(
+        InstallPhase::Verifying,
+        InstallStatus::Completed { speed: None },
+    );
+    Ok(diff)
+}
+
+/// One entry of a release archive's central directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ArchiveEntry {
+    pub name: String,
+    pub size: u64,
+    pub crc32: u32,
+}
+
+impl ArchiveEntry {
+    fn is_dir(&self) -> bool {
+        self.name.ends_with(['/', '\\'])
+    }
+}
+
+/// Bytes to read from the end of an archive to be sure of covering the
+/// end-of-central-directory record, including a maximum-length comment.
+pub const EOCD_SEARCH_BYTES: u64 = 22 + u16::MAX as u64;
+
+/// Locate the central directory as `(offset, size)` from the last bytes of an
+/// archive. `None` without an end-of-central-directory record, or for ZIP64.
+pub(crate) fn find_central_directory(tail: &[u8]) -> Option<(u64, u64)> {
+    const EOCD_SIGNATURE: [u8; 4] = 0x06054b50u32.to_le_bytes();
+
+    let last = tail.len().checked_sub(22)?;
+    let pos = (0..=last)
+        .rev()
+        .find(|&i| tail[i..i + 4] == EOCD_SIGNATURE)?;
+    let field = |at: usize| u32::from_le_bytes(tail[pos + at..pos + at + 4].try_into().unwrap());
+    let (size, offset) = (field(12), field(16));
+    if size == u32::MAX || offset == u32::MAX {
+        return None;
+    }
+    Some((u64::from(offset), u64::from(size)))
+}
+
+/// Parse central directory file headers. `None` if malformed or ZIP64.
+///
+/// Names are decoded like [`decode_entry_name`], with a CP437 fallback.
+pub(crate) fn parse_central_directory(mut data: &[u8]) -> Option<Vec<ArchiveEntry>> {
+    const HEADER_SIGNATURE: u32 = 0x02014b50;
+    const HEADER_LEN: usize = 46;
+
+    let mut entries = Vec::new();
+    while !data.is_empty() {
+        let header = data.get(..HEADER_LEN)?;
+        let u16_at = |at: usize| usize::from(u16::from_le_bytes([header[at], header[at + 1]]));
+        let u32_at = |at: usize| u32::from_le_bytes(header[at..at + 4].try_into().unwrap());
+        if u32_at(0) != HEADER_SIGNATURE {
+            return None;
+        }
+        let (crc32, size) = (u32_at(16), u32_at(24));
+        if size == u32::MAX {
+            return None;
+        }
+
+        let name_end = HEADER_LEN + u16_at(28);
+        let record_end = name_end + u16_at(30) + u16_at(32);
+        let name = data.get(HEADER_LEN..name_end)?;
+        entries.push(ArchiveEntry {
+            name: decode_raw_entry_name(name),
+            size: u64::from(size),
+            crc32,
+        });
+        data = data.get(record_end..)?;
+    }
+    Some(entries)
+}
+
+/// Report installed files outside `expected` as extra, except user data and app files.
+fn push_extra_files(
+    install_path: &Path,
+    expected: &HashSet<PathBuf>,
+    user_data_targets: &[PathBuf],
+    diff: &mut InstallDiff,
+) -> io::Result<()> {
+    let mut installed_files = Vec::new();
+    collect_files(install_path, Path::new(""), &mut installed_files)?;
+    for path in installed_files {
+        if !expected.contains(&path)
+            && !is_user_data_path(user_data_targets, &path)
+            && !APP_MANAGED_FILES.iter().any(|name| path == Path::new(name))
+        {
+            diff.push(&path, DiffKind::Extra);
+        }
+    }
+    Ok(())
+}
+
+/// Collect regular files under `root.join(relative)` as root-relative paths.
+/// Symlinks are not followed.
+fn collect_files(root: &Path, relative: &Path, out: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in std::fs::read_dir(root.join(relative))? {
+        let entry = entry?;
+        let ty = entry.file_type()?;
+        let path = relative.join(entry.file_name());
+        if ty.is_dir() {
+            collect_files(root, &path, out)?;
+        } else if ty.is_file() {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, sync::Arc};
+
+    use zip::{CompressionMethod, write::SimpleFileOptions};
+
+    use super::*;
+    use crate::pob::progress::tests::MockProgressSink;
+
+    fn write_zip(path: &Path, entries: &[(&str, &str)]) {
+        let mut writer = zip::ZipWriter::new(File::create(path).unwrap());
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        for (name, content) in entries {
+            writer.start_file(*name, options).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    fn write_file(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_inspect_archive_reports_structure_and_hazards() {
+        let temp = tempfile::tempdir().unwrap();
+        let zip_path = temp.path().join("mirror.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        for (name, content) in [
+            ("PoeCharm/POE1 POB/Launch.lua", "lua"),
+            ("PoeCharm/Data/../../evil.dll", "evil"),
+        ] {
+            writer.start_file(name, options).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer
+            .add_symlink("PoeCharm/Data/link", "/etc/passwd", options)
+            .unwrap();
+        writer.finish().unwrap();
+
+        let report = inspect_archive(&zip_path).unwrap();
+        assert_eq!(report.file_count, 3);
+        assert!(report.has_required_folders);
+        assert_eq!(report.nested_prefix.as_deref(), Some("PoeCharm"));
+        assert_eq!(report.dangerous_paths, ["PoeCharm/Data/../../evil.dll"]);
+        assert_eq!(report.symlinks, ["PoeCharm/Data/link"]);
+        assert!(report.total_uncompressed_bytes >= 7.0);
+        assert!(!report.truncated);
+
+        let unrelated = temp.path().join("unrelated.zip");
+        write_zip(&unrelated, &[("readme.txt", "hi")]);
+        let report = inspect_archive(&unrelated).unwrap();
+        assert!(!report.has_required_folders);
+        assert!(report.nested_prefix.is_none());
+    }
+
+    #[test]
+    fn test_file_crc32() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("file.txt");
+        std::fs::write(&path, "123456789").unwrap();
+        // Standard CRC-32 check value
+        assert_eq!(file_crc32(&path).unwrap(), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_parse_central_directory_from_tail() {
+        let temp = tempfile::tempdir().unwrap();
+        let zip_path = temp.path().join("latest.zip");
+        write_zip(
+            &zip_path,
+            &[("Data/a.txt", "aaa"), ("POE1 POB/한글.lua", "bb")],
+        );
+        let data = std::fs::read(&zip_path).unwrap();
+
+        let (offset, size) = find_central_directory(&data).unwrap();
+        let directory = &data[offset as usize..(offset + size) as usize];
+        let entries = parse_central_directory(directory).unwrap();
+        assert_eq!(
+            entries,
+            [
+                ArchiveEntry {
+                    name: "Data/a.txt".to_string(),
+                    size: 3,
+                    crc32: crc32fast::hash(b"aaa"),
+                },
+                ArchiveEntry {
+                    name: "POE1 POB/한글.lua".to_string(),
+                    size: 2,
+                    crc32: crc32fast::hash(b"bb"),
+                },
+            ]
+        );
+
+        assert!(find_central_directory(&data[..data.len() - 22]).is_none());
+        assert!(parse_central_directory(&directory[..directory.len() - 1]).is_none());
+    }
+
+    #[test]
+    fn test_diff_install() {
+        let temp = tempfile::tempdir().unwrap();
+        let zip_path = temp.path().join("latest.zip");
+        write_zip(
+            &zip_path,
+            &[
+                ("PoeCharm/POE1 POB/Launch.lua", "launch"),
+                ("PoeCharm/POE1 POB/Builds/default.xml", "<Build/>"),
+                ("PoeCharm/Data/same.txt", "same"),
+                ("PoeCharm/Data/changed.txt", "original"),
+                ("PoeCharm/Data/missing.txt", "gone"),
+            ],
+        );
+
+        let install = temp.path().join("install");
+        write_file(&install.join("POE1 POB/Launch.lua"), "launch");
+        write_file(&install.join("POE1 POB/Builds/mine.xml"), "<Build/>");
+        write_file(&install.join("Data/same.txt"), "same");
+        write_file(&install.join("Data/changed.txt"), "tampered");
+        write_file(&install.join("Data/extra.txt"), "extra");
+        write_file(&install.join("pob_version.json"), "{}");
+
+        let targets = vec![PathBuf::from("POE1 POB/Builds")];
+        let reporter = InstallReporter::new("verify", Arc::new(MockProgressSink::new()));
+        let diff = diff_install(
+            &zip_path,
+            &install,
+            &targets,
+            &CancellationToken::new(),
+            &reporter,
+        )
+        .unwrap();
+
+        let mut entries: Vec<(String, DiffKind)> = diff
+            .entries
+            .iter()
+            .map(|e| (e.path.clone(), e.kind))
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            entries,
+            [
+                ("Data/changed.txt".to_string(), DiffKind::Modified),
+                ("Data/extra.txt".to_string(), DiffKind::Extra),
+                ("Data/missing.txt".to_string(), DiffKind::Missing),
+            ]
+        );
+        assert!(!diff.is_clean());
+        assert!(!diff.truncated);
+    }
+
+    #[test]
+    fn test_verify_manifest_quick_and_full() {
+        let temp = tempfile::tempdir().unwrap();
+        let install = temp.path();
+        write_file(&install.join("Data/same.txt"), "same");
+        write_file(&install.join("Data/flipped.txt"), "abcd");
+        write_file(&install.join("POE1 POB/Builds/mine.xml"), "<Build/>");
+        write_file(&install.join("Data/extra.txt"), "extra");
+
+        let mut manifest = InstallManifest::default();
+        for (name, content) in [("Data/same.txt", "same"), ("Data/flipped.txt", "abcd")] {
+            let path = install.join(name);
+            let mtime = std::fs::metadata(&path).unwrap().modified().ok();
+            manifest.push(
+                Path::new(name),
+                content.len() as u64,
+                file_crc32(&path).unwrap(),
+                mtime,
+            );
+        }
+        manifest.push(Path::new("Data/missing.txt"), 4, 0, None);
+        manifest.save(install).unwrap();
+        let manifest = InstallManifest::load(install).unwrap().unwrap();
+
+        // Same size, restored mtime: only the CRC pass can tell
+        let flipped = install.join("Data/flipped.txt");
+        let mtime = std::fs::metadata(&flipped).unwrap().modified().unwrap();
+        std::fs::write(&flipped, "dcba").unwrap();
+        File::options()
+            .write(true)
+            .open(&flipped)
+            .unwrap()
+            .set_modified(mtime)
+            .unwrap();
+
+        let targets = vec![PathBuf::from("POE1 POB/Builds")];
+        let reporter = InstallReporter::new("verify", Arc::new(MockProgressSink::new()));
+        let cancel = CancellationToken::new();
+        let quick =
+            verify_manifest(install, &manifest, false, &targets, &cancel, &reporter).unwrap();
+        assert_eq!((quick.modified, quick.missing, quick.extra), (0, 1, 1));
+
+        let full = verify_manifest(install, &manifest, true, &targets, &cancel, &reporter).unwrap();
+        assert_eq!((full.modified, full.missing, full.extra), (1, 1, 1));
+        assert!(
+            full.entries
+                .iter()
+                .any(|e| e.path == "Data/flipped.txt" && e.kind == DiffKind::Modified)
+        );
+    }
+
+    #[test]
+    fn test_check_backup() {
+        let temp = tempfile::tempdir().unwrap();
+        write_file(&temp.path().join("POE1 POB/Builds/a.xml"), "<Build/>");
+        write_file(&temp.path().join("POE1 POB/Settings.xml"), "<Settings/>");
+
+        let sink = Arc::new(MockProgressSink::new());
+        let reporter = InstallReporter::new("backup", sink.clone());
+        let check = check_backup(temp.path(), &CancellationToken::new(), &reporter).unwrap();
+        assert_eq!(check.readable, 2);
+        assert!(check.is_clean());
+        // Sized in bytes, like the progress that follows
+        assert!(matches!(
+            sink.get_events()[0].status,
+            InstallStatus::Started { total_size, .. } if total_size == NonZeroU32::new(19)
+        ));
+    }
+
+    #[test]
+    fn test_diff_install_cancelled() {
+        let temp = tempfile::tempdir().unwrap();
+        let zip_path = temp.path().join("latest.zip");
+        write_zip(&zip_path, &[("Data/a.txt", "a")]);
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+
+        let reporter = InstallReporter::new("verify", Arc::new(MockProgressSink::new()));
+        let result = diff_install(&zip_path, temp.path(), &[], &cancel, &reporter);
+        assert!(matches!(result, Err(PobError::Cancelled)));
+    }
+}
@@ -1,5 +1,9 @@
-use std::sync::LazyLock;
+use std::{
+    cmp::{Ordering, Reverse},
+    sync::{LazyLock, RwLock},
+};
 
+use chrono::{Datelike, NaiveDate};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use specta::Type;
@@ -14,6 +18,90 @@ pub struct PobVersion {
     pub file_id: String,
 }
 
+impl PobVersion {
+    /// Whether this version was released after `other`, comparing dates rather
+    /// than strings. `false` when either isn't a `YYYY.MM.DD` date.
+    pub fn is_newer_than(&self, other: &str) -> bool {
+        compare_versions(&self.version, other) == Some(Ordering::Greater)
+    }
+
+    /// Release date, `None` when the version isn't a `YYYY.MM.DD` date.
+    pub fn date(&self) -> Option<NaiveDate> {
+        version_date(&self.version).ok()
+    }
+
+    /// The version as a date, failing with [`PobError::VersionParseError`]
+    /// for a stored version that isn't `YYYY.MM.DD`.
+    pub fn parsed(&self) -> Result<ParsedVersion, PobError> {
+        ParsedVersion::parse(&self.version)
+    }
+
+    /// Days between this release and `latest`, negative when this one is newer.
+    /// `None` when either isn't a `YYYY.MM.DD` date.
+    pub fn days_behind(&self, latest: &PobVersion) -> Option<i64> {
+        days_between(&self.version, &latest.version)
+    }
+}
+
+/// A `YYYY.MM.DD` version split into its date parts. Orders chronologically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ParsedVersion {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl ParsedVersion {
+    /// Parse a `YYYY.MM.DD` version naming a real calendar date.
+    pub fn parse(version: &str) -> Result<Self, PobError> {
+        let date = version_date(version)?;
+        Ok(Self {
+            year: date.year() as u16,
+            month: date.month() as u8,
+            day: date.day() as u8,
+        })
+    }
+
+    pub fn date(&self) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(self.year.into(), self.month.into(), self.day.into())
+    }
+}
+
+impl std::fmt::Display for ParsedVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}.{:02}.{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Days from version `from` to version `to`. `None` unless both are `YYYY.MM.DD` dates.
+pub fn days_between(from: &str, to: &str) -> Option<i64> {
+    let from = ParsedVersion::parse(from).ok()?.date()?;
+    let to = ParsedVersion::parse(to).ok()?.date()?;
+    Some((to - from).num_days())
+}
+
+/// Versions order by release date, undated ones first, then by the version
+/// string. `installed_at` and `file_id` don't take part.
+impl Ord for PobVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.date(), &self.version).cmp(&(other.date(), &other.version))
+    }
+}
+
+impl PartialOrd for PobVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for PobVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.version == other.version
+    }
+}
+
+impl Eq for PobVersion {}
+
 impl TryFrom<GoogleDriveFileInfo> for PobVersion {
     type Error = PobError;
 
@@ -37,17 +125,203 @@ impl TryFrom<&GoogleDriveFileInfo> for PobVersion {
     }
 }
 
+/// Release naming of the default Google Drive folder.
+pub const DEFAULT_NAME_PATTERN: &str =
+    r"POE1&2 통합 한글 POB\s?\((?P<version>\d{4}\.\d{2}\.\d{2})\).zip";
+
+/// Patterns tried in order when no custom pattern is set: the exact naming,
+/// then the same naming with any spacing and trailing suffixes (` (v2)`), then
+/// any `YYYY.MM.DD` date in a `.zip` name.
+pub const DEFAULT_NAME_PATTERNS: &[&str] = &[
+    DEFAULT_NAME_PATTERN,
+    r"POE1&2\s*통합\s*한글\s*POB\s*\((?P<version>\d{4}\.\d{2}\.\d{2})\)",
+    r"(?:^|[^\d.])(?P<version>\d{4}\.\d{2}\.\d{2})(?:[^\d.].*)?\.zip$",
+];
+
+/// Patterns used by [`parse_from_name`], replaced via [`set_name_pattern`].
+static NAME_PATTERNS: LazyLock<RwLock<Vec<Regex>>> =
+    LazyLock::new(|| RwLock::new(default_name_patterns()));
+
+/// Compiled [`DEFAULT_NAME_PATTERNS`].
+pub fn default_name_patterns() -> Vec<Regex> {
+    DEFAULT_NAME_PATTERNS
+        .iter()
+        .map(|pattern| Regex::new(pattern).unwrap())
+        .collect()
+}
+
+/// Compile a release filename pattern, which must have a named `version` group.
+pub fn compile_name_pattern(pattern: &str) -> Result<Regex, PobError> {
+    let re = Regex::new(pattern).map_err(|e| {
+        PobError::InvalidConfig(format!("파일 이름 패턴을 해석할 수 없습니다: {e}"))
+    })?;
+    if !re.capture_names().any(|name| name == Some("version")) {
+        return Err(PobError::InvalidConfig(
+            "파일 이름 패턴에 `version` 그룹이 없습니다".into(),
+        ));
+    }
+    Ok(re)
+}
+
+/// Parse release names with `pattern` alone from now on, or the default
+/// pattern set for `None`.
+pub fn set_name_pattern(pattern: Option<&str>) -> Result<(), PobError> {
+    let patterns = match pattern {
+        Some(pattern) => vec![compile_name_pattern(pattern)?],
+        None => default_name_patterns(),
+    };
+    *NAME_PATTERNS.write().unwrap() = patterns;
+    Ok(())
+}
+
 pub fn parse_from_name(name: &str) -> Result<String, PobError> {
-    static RE: LazyLock<Regex> = LazyLock::new(|| {
-        Regex::new(r"POE1&2 통합 한글 POB\s?\((\d{4}\.\d{2}\.\d{2})\).zip").unwrap()
-    });
+    parse_from_name_with(&NAME_PATTERNS.read().unwrap(), name)
+}
 
-    RE.captures(name)
-        .and_then(|caps| caps.get(1))
+/// Version from the first of `patterns` that matches `name`.
+pub fn parse_from_name_with(patterns: &[Regex], name: &str) -> Result<String, PobError> {
+    patterns
+        .iter()
+        .find_map(|re| parse_with(re, name).ok())
+        .ok_or_else(|| PobError::VersionParseError(name.to_string()))
+}
+
+fn parse_with(re: &Regex, name: &str) -> Result<String, PobError> {
+    re.captures(name)
+        .and_then(|caps| caps.name("version"))
         .map(|m| m.as_str().to_string())
         .ok_or_else(|| PobError::VersionParseError(name.to_string()))
 }
 
+/// Validate a bare version string as it appears inside release names (`YYYY.MM.DD`).
+pub fn validate_version(version: &str) -> Result<String, PobError> {
+    static RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\d{4}\.\d{2}\.\d{2}$").unwrap());
+
+    let version = version.trim();
+    if RE.is_match(version) {
+        Ok(version.to_string())
+    } else {
+        Err(PobError::VersionParseError(version.to_string()))
+    }
+}
+
+/// Outcome of running a candidate pattern against one sample filename.
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PatternSample {
+    pub name: String,
+    /// Version the pattern would extract
+    pub parsed: Option<String>,
+    /// Why nothing was extracted
+    pub error: Option<String>,
+}
+
+/// Dry-run `pattern` against `samples` without touching the active pattern.
+pub fn test_name_pattern(
+    pattern: &str,
+    samples: Vec<String>,
+) -> Result<Vec<PatternSample>, PobError> {
+    let re = compile_name_pattern(pattern)?;
+    Ok(samples
+        .into_iter()
+        .map(|name| match parse_with(&re, &name) {
+            Ok(version) => PatternSample {
+                name,
+                parsed: Some(version),
+                error: None,
+            },
+            Err(e) => PatternSample {
+                name,
+                parsed: None,
+                error: Some(e.to_string()),
+            },
+        })
+        .collect())
+}
+
+/// Calendar date of a `YYYY.MM.DD` version.
+pub fn version_date(version: &str) -> Result<NaiveDate, PobError> {
+    let version = validate_version(version)?;
+    NaiveDate::parse_from_str(&version, "%Y.%m.%d")
+        .map_err(|_| PobError::VersionParseError(version))
+}
+
+/// Order two versions by release date. `None` unless both are `YYYY.MM.DD` dates.
+pub fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
+    Some(version_date(a).ok()?.cmp(&version_date(b).ok()?))
+}
+
+/// Release date parsed from a filename with the active pattern.
+fn release_date(name: &str) -> Option<NaiveDate> {
+    parse_from_name(name).and_then(|v| version_date(&v)).ok()
+}
+
+/// Sort releases newest first by the date in their name, breaking same-day
+/// ties by filename. Names without a parseable date sort last.
+pub fn sort_newest_first(files: &mut [GoogleDriveFileInfo]) {
+    files.sort_by_cached_key(|file| Reverse((release_date(&file.name), file.name.clone())));
+}
+
+/// How the installed version relates to the latest release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum UpdateState {
+    NotInstalled,
+    UpToDate,
+    UpdateAvailable,
+    /// The install is newer than the latest release (a manual install, or an old
+    /// file re-sorted to latest on Drive); installing "latest" would downgrade
+    InstalledNewer,
+}
+
+/// Result of `check_for_update`.
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateStatus {
+    pub installed: Option<String>,
+    pub latest: String,
+    /// Also `true` when nothing is installed, so the UI prompts a first install
+    pub update_available: bool,
+    /// How many days older the installed release is than the latest, negative
+    /// when it's newer. `None` when nothing is installed or either isn't a date.
+    pub days_behind: Option<i32>,
+}
+
+impl UpdateState {
+    /// Versions that can't be ordered count as an update whenever they differ.
+    pub fn classify(installed: Option<&str>, latest: &str) -> Self {
+        let Some(installed) = installed else {
+            return Self::NotInstalled;
+        };
+        match compare_versions(installed, latest) {
+            Some(Ordering::Greater) => Self::InstalledNewer,
+            Some(Ordering::Equal) => Self::UpToDate,
+            Some(Ordering::Less) => Self::UpdateAvailable,
+            None if installed == latest => Self::UpToDate,
+            None => Self::UpdateAvailable,
+        }
+    }
+}
+
+/// Keep non-folder files whose parsed version date lies within `from..=to`,
+/// newest first. Names without a parseable date are dropped.
+pub fn versions_in_range(
+    files: Vec<GoogleDriveFileInfo>,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Vec<GoogleDriveFileInfo> {
+    let mut dated: Vec<(NaiveDate, GoogleDriveFileInfo)> = files
+        .into_iter()
+        .filter(|file| !file.is_folder)
+        .filter_map(|file| Some((release_date(&file.name)?, file)))
+        .filter(|(date, _)| from.is_none_or(|from| *date >= from))
+        .filter(|(date, _)| to.is_none_or(|to| *date <= to))
+        .collect();
+
+    dated.sort_by(|a, b| b.0.cmp(&a.0));
+    dated.into_iter().map(|(_, file)| file).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -57,8 +331,7 @@ mod tests {
         let test_cases = vec![
             ("POE1&2 통합 한글 POB (2024.01.15).zip", "2024.01.15"),
             ("POE1&2 통합 한글 POB(2024.12.31).zip", "2024.12.31"),
-            // Regex uses \s? which means 0 or 1 whitespace, so 2 spaces won't match
-            // ("POE1&2 통합 한글 POB  (2025.06.01).zip", "2025.06.01"),
+            ("POE1&2 통합 한글 POB  (2025.06.01).zip", "2025.06.01"),
         ];
 
         for (input, expected) in test_cases {
@@ -91,11 +364,7 @@ mod tests {
 
     #[test]
     fn test_version_try_from_google_drive_info() {
-        let file_info = GoogleDriveFileInfo {
-            id: "test_file_id".to_string(),
-            name: "POE1&2 통합 한글 POB (2024.05.20).zip".to_string(),
-            is_folder: false,
-        };
+        let file_info = GoogleDriveFileInfo::test_release("test_file_id");
 
         let result = PobVersion::try_from(&file_info);
         assert!(result.is_ok());
@@ -109,19 +378,268 @@ mod tests {
     #[test]
     fn test_version_try_from_invalid_name() {
         let file_info = GoogleDriveFileInfo {
-            id: "test_file_id".to_string(),
             name: "invalid_filename.zip".to_string(),
-            is_folder: false,
+            ..GoogleDriveFileInfo::test_release("test_file_id")
         };
 
         let result = PobVersion::try_from(&file_info);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_validate_version() {
+        assert_eq!(validate_version(" 2024.05.20 ").unwrap(), "2024.05.20");
+        assert!(validate_version("2024.5.20").is_err());
+        assert!(validate_version("POE1&2 통합 한글 POB (2024.05.20).zip").is_err());
+    }
+
+    #[test]
+    fn test_test_name_pattern() {
+        let samples = vec!["PoB v2.49.3.zip".to_string(), "readme.txt".to_string()];
+        let results = test_name_pattern(r"PoB v(?P<version>[\d.]+)\.zip", samples).unwrap();
+
+        assert_eq!(results[0].parsed.as_deref(), Some("2.49.3"));
+        assert!(results[0].error.is_none());
+        assert!(results[1].parsed.is_none());
+        assert!(results[1].error.as_deref().unwrap().contains("readme.txt"));
+
+        assert!(test_name_pattern(r"PoB v(\d+", vec![]).is_err());
+    }
+
+    #[test]
+    fn test_is_newer_than_compares_dates() {
+        let version = |v: &str| PobVersion {
+            version: v.to_string(),
+            installed_at: String::new(),
+            file_id: String::new(),
+        };
+        assert!(version("2024.10.02").is_newer_than("2024.09.30"));
+        assert!(!version("2024.09.30").is_newer_than("2024.10.02"));
+        assert!(!version("2024.05.20").is_newer_than("2024.05.20"));
+        assert!(!version("2.49.3").is_newer_than("2024.05.20"));
+    }
+
+    #[test]
+    fn test_parsed_version() {
+        let parsed = ParsedVersion::parse("2024.05.20").unwrap();
+        assert_eq!(
+            parsed,
+            ParsedVersion {
+                year: 2024,
+                month: 5,
+                day: 20
+            }
+        );
+        assert_eq!(parsed.to_string(), "2024.05.20");
+        assert!(parsed < ParsedVersion::parse("2024.10.02").unwrap());
+        assert!(parsed > ParsedVersion::parse("2023.12.31").unwrap());
+
+        for malformed in ["2.49.3", "2024.13.01", "2024.02.30", ""] {
+            assert!(
+                matches!(
+                    ParsedVersion::parse(malformed),
+                    Err(PobError::VersionParseError(_))
+                ),
+                "{malformed:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_days_behind() {
+        let version = |v: &str| PobVersion {
+            version: v.to_string(),
+            installed_at: String::new(),
+            file_id: String::new(),
+        };
+        assert_eq!(
+            version("2024.05.20").days_behind(&version("2024.06.01")),
+            Some(12)
+        );
+        // Across a leap day
+        assert_eq!(
+            version("2024.02.28").days_behind(&version("2024.03.01")),
+            Some(2)
+        );
+        assert_eq!(
+            version("2024.06.01").days_behind(&version("2024.05.20")),
+            Some(-12)
+        );
+        assert_eq!(version("custom").days_behind(&version("2024.05.20")), None);
+        assert!(version("custom").parsed().is_err());
+    }
+
+    #[test]
+    fn test_pob_version_orders_by_date() {
+        let version = |v: &str, file_id: &str| PobVersion {
+            version: v.to_string(),
+            installed_at: String::new(),
+            file_id: file_id.to_string(),
+        };
+        assert_eq!(
+            version("2024.05.20", "a").date(),
+            NaiveDate::from_ymd_opt(2024, 5, 20)
+        );
+        assert!(version("2.49.3", "a").date().is_none());
+
+        let mut versions = vec![
+            version("2024.10.02", "a"),
+            version("custom", "b"),
+            version("2024.09.30", "c"),
+        ];
+        versions.sort();
+        let sorted: Vec<&str> = versions.iter().map(|v| v.version.as_str()).collect();
+        assert_eq!(sorted, ["custom", "2024.09.30", "2024.10.02"]);
+
+        // Same release from a different file is still the same version
+        assert_eq!(version("2024.05.20", "a"), version("2024.05.20", "b"));
+    }
+
+    #[test]
+    fn test_sort_newest_first() {
+        let file = |name: &str| GoogleDriveFileInfo {
+            name: name.to_string(),
+            ..GoogleDriveFileInfo::test_release(name)
+        };
+        let mut files = vec![
+            file("notes.txt"),
+            file("POE1&2 통합 한글 POB (2024.05.20).zip"),
+            file("POE1&2 통합 한글 POB(2024.06.11).zip"),
+            file("POE1&2 통합 한글 POB (2024.06.11).zip"),
+            file("README.txt"),
+        ];
+
+        sort_newest_first(&mut files);
+        let names: Vec<&str> = files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                // Same day: filename breaks the tie
+                "POE1&2 통합 한글 POB(2024.06.11).zip",
+                "POE1&2 통합 한글 POB (2024.06.11).zip",
+                "POE1&2 통합 한글 POB (2024.05.20).zip",
+                // Undated names last
+                "notes.txt",
+                "README.txt",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_state_classify() {
+        assert_eq!(
+            UpdateState::classify(None, "2024.05.20"),
+            UpdateState::NotInstalled
+        );
+        assert_eq!(
+            UpdateState::classify(Some("2024.05.20"), "2024.05.20"),
+            UpdateState::UpToDate
+        );
+        assert_eq!(
+            UpdateState::classify(Some("2024.05.20"), "2024.06.11"),
+            UpdateState::UpdateAvailable
+        );
+        assert_eq!(
+            UpdateState::classify(Some("2024.06.11"), "2024.05.20"),
+            UpdateState::InstalledNewer
+        );
+        // Custom patterns without dates fall back to inequality
+        assert_eq!(
+            UpdateState::classify(Some("2.49.3"), "2.49.1"),
+            UpdateState::UpdateAvailable
+        );
+    }
+
+    #[test]
+    fn test_versions_in_range() {
+        let file = |name: &str| GoogleDriveFileInfo {
+            name: name.to_string(),
+            ..GoogleDriveFileInfo::test_release(name)
+        };
+        let files = vec![
+            file("POE1&2 통합 한글 POB (2024.05.20).zip"),
+            file("POE1&2 통합 한글 POB (2024.07.02).zip"),
+            file("notes.txt"),
+            file("POE1&2 통합 한글 POB (2024.06.11).zip"),
+            file("POE1&2 통합 한글 POB (2024.04.30).zip"),
+        ];
+
+        let from = version_date("2024.05.01").ok();
+        let to = version_date("2024.06.30").ok();
+        let names: Vec<String> = versions_in_range(files.clone(), from, to)
+            .into_iter()
+            .map(|f| f.name)
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "POE1&2 통합 한글 POB (2024.06.11).zip",
+                "POE1&2 통합 한글 POB (2024.05.20).zip",
+            ]
+        );
+
+        // Unbounded: every dated release, newest first
+        let all = versions_in_range(files, None, None);
+        assert_eq!(all.len(), 4);
+        assert_eq!(all[0].name, "POE1&2 통합 한글 POB (2024.07.02).zip");
+
+        assert!(version_date("2024.02.30").is_err());
+    }
+
     #[test]
     fn test_version_regex_compiles() {
         // Ensure regex pattern is valid at compile time (via test)
-        let _ = Regex::new(r"POE1&2 통합 한글 POB\s?\((\d{4}\.\d{2}\.\d{2})\).zip")
-            .expect("Version regex pattern is invalid");
+        for pattern in DEFAULT_NAME_PATTERNS {
+            compile_name_pattern(pattern).expect("Version regex pattern is invalid");
+        }
+    }
+
+    #[test]
+    fn test_parse_from_name_with_fallbacks() {
+        let patterns = default_name_patterns();
+        let cases = [
+            ("POE1&2 통합 한글 POB (2024.05.20) (v2).zip", "2024.05.20"),
+            ("POE1&2  통합  한글 POB  (2024.05.20).zip", "2024.05.20"),
+            ("POE1&2 통합 한글 POB (2024.05.20)-fixed.zip", "2024.05.20"),
+            // Renamed upstream: only the date fallback matches
+            ("PoB 한글판 2024.07.01.zip", "2024.07.01"),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(
+                parse_from_name_with(&patterns, name).unwrap(),
+                expected,
+                "Input: {name}"
+            );
+        }
+        assert!(parse_from_name_with(&patterns, "PoB 12024.07.011.zip").is_err());
+        // Dated files that aren't releases
+        assert!(parse_from_name_with(&patterns, "패치노트 2024.08.01.txt").is_err());
+        assert!(parse_from_name_with(&patterns, "2024.08.01.zip.txt").is_err());
+
+        // The exact pattern alone still rejects the variants
+        let exact = &patterns[..1];
+        assert!(parse_from_name_with(exact, "POE1&2 통합 한글 POB (2024.05.20) (v2).zip").is_err());
+        assert!(parse_from_name_with(&[], "POE1&2 통합 한글 POB (2024.05.20).zip").is_err());
+    }
+
+    #[test]
+    fn test_custom_name_pattern() {
+        let re =
+            compile_name_pattern(r"Path of Building v(?P<version>\d+\.\d+\.\d+)\.zip").unwrap();
+        assert_eq!(
+            parse_with(&re, "Path of Building v2.49.3.zip").unwrap(),
+            "2.49.3"
+        );
+        assert!(parse_with(&re, "POE1&2 통합 한글 POB (2024.05.20).zip").is_err());
+
+        // Uncompilable, or missing the named group
+        assert!(matches!(
+            compile_name_pattern(r"POB (\d+"),
+            Err(PobError::InvalidConfig(_))
+        ));
+        assert!(matches!(
+            compile_name_pattern(r"POB (\d+)\.zip"),
+            Err(PobError::InvalidConfig(_))
+        ));
     }
 }
@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use tokio_util::sync::CancellationToken;
 use zip::DateTime;
 
 /// Generate a unique task ID with prefix, timestamp, and random suffix.
@@ -25,17 +26,96 @@ pub fn generate_task_id(prefix: &str) -> String {
     format!("{prefix}_{timestamp:x}_{random:04x}")
 }
 
-pub async fn async_copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), std::io::Error> {
+/// Whether `task_id` is one [`generate_task_id`] made for `prefix`. Task ids
+/// name temp directories, so ones from the frontend are checked with this.
+pub fn is_task_id(task_id: &str, prefix: &str) -> bool {
+    let Some(rest) = task_id
+        .strip_prefix(prefix)
+        .and_then(|rest| rest.strip_prefix('_'))
+    else {
+        return false;
+    };
+    let is_hex = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_hexdigit());
+    match rest.split_once('_') {
+        Some((timestamp, random)) => is_hex(timestamp) && random.len() == 4 && is_hex(random),
+        None => false,
+    }
+}
+
+/// Directory nesting deeper than this is treated as a cycle and aborts the copy.
+const MAX_COPY_DEPTH: usize = 64;
+
+/// Recursively copy `src` into `dst`.
+///
+/// Symlinks and junctions to directories are skipped (with a warning) rather than
+/// followed, so a link pointing at an ancestor can't recurse forever. File symlinks
+/// are copied as regular files; dangling links are skipped.
+///
+/// Stops between entries once `cancel_token` fires, failing with `Interrupted`.
+pub async fn async_copy_dir_recursive(
+    src: &Path,
+    dst: &Path,
+    cancel_token: &CancellationToken,
+) -> Result<(), std::io::Error> {
+    copy_dir_recursive_inner(src, dst, 0, cancel_token).await
+}
+
+async fn copy_dir_recursive_inner(
+    src: &Path,
+    dst: &Path,
+    depth: usize,
+    cancel_token: &CancellationToken,
+) -> Result<(), std::io::Error> {
+    if depth > MAX_COPY_DEPTH {
+        return Err(std::io::Error::other(format!(
+            "directory nesting exceeds {MAX_COPY_DEPTH} levels (possible cycle): {}",
+            src.display()
+        )));
+    }
+
     tokio::fs::create_dir_all(dst).await?;
     let mut entries = tokio::fs::read_dir(src).await?;
 
     while let Some(entry) = entries.next_entry().await? {
+        if cancel_token.is_cancelled() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Interrupted,
+                "copy cancelled",
+            ));
+        }
         let ty = entry.file_type().await?;
         let src_path = entry.path();
         let dst_path = dst.join(entry.file_name());
 
-        if ty.is_dir() {
-            Box::pin(async_copy_dir_recursive(&src_path, &dst_path)).await?;
+        if ty.is_symlink() {
+            match tokio::fs::metadata(&src_path).await {
+                Ok(target) if target.is_file() => {
+                    tokio::fs::copy(&src_path, &dst_path).await?;
+                }
+                Ok(_) => {
+                    tracing::warn!(
+                        operation = "copy",
+                        path = %src_path.display(),
+                        "Skipping directory symlink/junction"
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        operation = "copy",
+                        path = %src_path.display(),
+                        error = %e,
+                        "Skipping dangling symlink"
+                    );
+                }
+            }
+        } else if ty.is_dir() {
+            Box::pin(copy_dir_recursive_inner(
+                &src_path,
+                &dst_path,
+                depth + 1,
+                cancel_token,
+            ))
+            .await?;
         } else {
             tokio::fs::copy(&src_path, &dst_path).await?;
         }
@@ -110,6 +190,15 @@ mod tests {
         assert_ne!(id1, id2, "Task IDs should be unique");
     }
 
+    #[test]
+    fn test_is_task_id() {
+        assert!(is_task_id(&generate_task_id("pob"), "pob"));
+        assert!(!is_task_id(&generate_task_id("pob"), "relocate"));
+        assert!(!is_task_id("pob_18abc1234def", "pob"));
+        assert!(!is_task_id("pob_18abc1234def_a3f2/..", "pob"));
+        assert!(!is_task_id("pob_../../x_a3f2", "pob"));
+    }
+
     #[test]
     fn test_generate_task_id_prefix() {
         let prefixes = vec!["pob", "task", "download", "extract"];
@@ -124,6 +213,28 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_copy_dir_recursive_stops_when_cancelled() {
+        let temp = tempfile::tempdir().unwrap();
+        let src = temp.path().join("src");
+        std::fs::create_dir_all(src.join("nested")).unwrap();
+        std::fs::write(src.join("nested/a.txt"), "a").unwrap();
+
+        let dst = temp.path().join("dst");
+        async_copy_dir_recursive(&src, &dst, &CancellationToken::new())
+            .await
+            .unwrap();
+        assert!(dst.join("nested/a.txt").is_file());
+
+        let cancelled = CancellationToken::new();
+        cancelled.cancel();
+        let err = async_copy_dir_recursive(&src, &temp.path().join("dst2"), &cancelled)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::Interrupted);
+        assert!(!temp.path().join("dst2/nested").exists());
+    }
+
     #[test]
     fn test_datetime_to_systemtime_valid() {
         // Valid datetime: 2024-05-20 14:30:00
@@ -182,7 +293,7 @@ mod tests {
             .unwrap();
 
         // Copy
-        let result = async_copy_dir_recursive(&src, &dst).await;
+        let result = async_copy_dir_recursive(&src, &dst, &CancellationToken::new()).await;
         assert!(result.is_ok());
 
         // Verify
@@ -200,4 +311,40 @@ mod tests {
         assert_eq!(content1, "content1");
         assert_eq!(content2, "content2");
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_async_copy_dir_recursive_skips_symlink_loop() {
+        use tempfile::tempdir;
+
+        let temp = tempdir().unwrap();
+        let src = temp.path().join("src");
+        let dst = temp.path().join("dst");
+
+        tokio::fs::create_dir_all(src.join("subdir")).await.unwrap();
+        tokio::fs::write(src.join("file.txt"), b"content")
+            .await
+            .unwrap();
+        // Self-referential link back to an ancestor, plus a file link and a dangling link
+        std::os::unix::fs::symlink(&src, src.join("subdir/loop")).unwrap();
+        std::os::unix::fs::symlink(src.join("file.txt"), src.join("link.txt")).unwrap();
+        std::os::unix::fs::symlink(src.join("missing"), src.join("dangling")).unwrap();
+
+        async_copy_dir_recursive(&src, &dst, &CancellationToken::new())
+            .await
+            .unwrap();
+
+        assert!(dst.join("subdir").is_dir());
+        assert!(!dst.join("subdir/loop").exists());
+        assert!(!dst.join("dangling").exists());
+
+        let link_meta = tokio::fs::symlink_metadata(dst.join("link.txt"))
+            .await
+            .unwrap();
+        assert!(link_meta.is_file());
+        let content = tokio::fs::read_to_string(dst.join("link.txt"))
+            .await
+            .unwrap();
+        assert_eq!(content, "content");
+    }
 }
@@ -5,9 +5,41 @@
 
 
 export const commands = {
-async fetchPob(refresh: boolean) : Promise<Result<GoogleDriveFileInfo, ErrorKind>> {
+async fetchPob(refresh: boolean, folderId: string | null) : Promise<Result<GoogleDriveFileInfo, ErrorKind>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_pob", { refresh }) };
+    return { status: "ok", data: await TAURI_INVOKE("fetch_pob", { refresh, folderId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listVersions(from: string | null, to: string | null, withMetadata: boolean) : Promise<Result<GoogleDriveFileInfo[], ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_versions", { from, to, withMetadata }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateState(refresh: boolean) : Promise<Result<UpdateState, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_state", { refresh }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async checkForUpdate() : Promise<Result<UpdateStatus, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_for_update") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateDownloadEstimate() : Promise<Result<UpdateDownloadEstimate | null, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_download_estimate") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -21,17 +53,73 @@ async installedPobInfo() : Promise<Result<PobVersion | null, ErrorKind>> {
     else return { status: "error", error: e  as any };
 }
 },
-async installPob(fileData: GoogleDriveFileInfo | null) : Promise<Result<boolean, ErrorKind>> {
+async newTaskId() : Promise<Result<string, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("new_task_id") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async installPob(taskId: string, fileData: GoogleDriveFileInfo | null, allowDowngrade: boolean, force: boolean, dryRun: boolean, onBackupFailure: BackupFailurePolicy | null) : Promise<Result<InstallOutcome, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("install_pob", { taskId, fileData, allowDowngrade, force, dryRun, onBackupFailure }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async checkInstallIntegrity() : Promise<Result<IntegrityReport, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_install_integrity") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async reinstallPob(taskId: string, force: boolean) : Promise<Result<InstallOutcome, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reinstall_pob", { taskId, force }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async repairPob(taskId: string) : Promise<Result<InstallOutcome, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("repair_pob", { taskId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async cancelInstallPob(taskId: string) : Promise<Result<null, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_install_pob", { taskId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async takeRecoveryAction() : Promise<Result<RecoveryAction | null, ErrorKind>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("install_pob", { fileData }) };
+    return { status: "ok", data: await TAURI_INVOKE("take_recovery_action") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async cancelInstallPob() : Promise<Result<null, ErrorKind>> {
+async quiesce() : Promise<Result<ActivityState, ErrorKind>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("cancel_install_pob") };
+    return { status: "ok", data: await TAURI_INVOKE("quiesce") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async resumeActivity() : Promise<Result<ActivityState, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("resume_activity") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -45,17 +133,57 @@ async parseVersion(fileName: string) : Promise<Result<string, ErrorKind>> {
     else return { status: "error", error: e  as any };
 }
 },
-async uninstallPob() : Promise<Result<null, ErrorKind>> {
+async testVersionPattern(pattern: string, samples: string[]) : Promise<Result<PatternSample[], ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("test_version_pattern", { pattern, samples }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async uninstallPob(force: boolean) : Promise<Result<string, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("uninstall_pob", { force }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async previousPobInfo() : Promise<Result<PobVersion | null, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("previous_pob_info") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getChangelog() : Promise<Result<string | null, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_changelog") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async revertToPrevious() : Promise<Result<PobVersion | null, ErrorKind>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("uninstall_pob") };
+    return { status: "ok", data: await TAURI_INVOKE("revert_to_previous") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async executePob() : Promise<Result<null, ErrorKind>> {
+async isPobRunning() : Promise<Result<boolean, ErrorKind>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("execute_pob") };
+    return { status: "ok", data: await TAURI_INVOKE("is_pob_running") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async executePob(profile: GameProfile | null) : Promise<Result<null, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("execute_pob", { profile }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -68,6 +196,190 @@ async getInstallPath() : Promise<Result<string, ErrorKind>> {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
+},
+async openInstallFolder() : Promise<Result<null, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_install_folder") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async openLogFolder() : Promise<Result<null, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_log_folder") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getConfig() : Promise<Result<PobConfig, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_config") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setConfig(config: PobConfig) : Promise<Result<null, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_config", { config }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getDownloadConfig() : Promise<Result<DownloadConfig, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_download_config") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setDownloadConfig(config: DownloadConfig) : Promise<Result<null, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_download_config", { config }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async replayLastInstall() : Promise<Result<InstallProgress[], ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("replay_last_install") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async relocateDataDir(newDir: string) : Promise<Result<null, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("relocate_data_dir", { newDir }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setInstallRoot(path: string) : Promise<Result<null, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_install_root", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async rebuildVersionInfo(version: string) : Promise<Result<PobVersion, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("rebuild_version_info", { version }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async diffAgainstLatest() : Promise<Result<InstallDiff, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("diff_against_latest") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async verifyInstall(full: boolean | null) : Promise<Result<InstallDiff, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("verify_install", { full }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async testBackupIntegrity() : Promise<Result<BackupCheck, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("test_backup_integrity") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createManualBackup(label: string | null) : Promise<Result<BackupSnapshot, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_manual_backup", { label }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listBackups() : Promise<Result<BackupEntry[], ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_backups") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async restoreFromBackup(name: string) : Promise<Result<null, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("restore_from_backup", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listSnapshots() : Promise<Result<BackupSnapshot[], ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_snapshots") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async restoreSnapshot(id: string) : Promise<Result<null, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("restore_snapshot", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getBackupTargets() : Promise<Result<string[], ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_backup_targets") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveBackupTargets(targets: string[]) : Promise<Result<null, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_backup_targets", { targets }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async inspectArchive(zipPath: string) : Promise<Result<ArchiveReport, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("inspect_archive", { zipPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async reclaimSpace(targetBytes: number) : Promise<Result<ReclaimReport, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reclaim_space", { targetBytes }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async exportDiagnostics(destZip: string) : Promise<Result<string, ErrorKind>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_diagnostics", { destZip }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
 }
 }
 
@@ -75,9 +387,13 @@ async getInstallPath() : Promise<Result<string, ErrorKind>> {
 
 
 export const events = __makeEvents__<{
-installProgress: InstallProgress
+installFinished: InstallFinished,
+installProgress: InstallProgress,
+overallProgress: OverallProgress
 }>({
-installProgress: "install-progress"
+installFinished: "install-finished",
+installProgress: "install-progress",
+overallProgress: "overall-progress"
 })
 
 /** user-defined constants **/
@@ -86,6 +402,218 @@ installProgress: "install-progress"
 
 /** user-defined types **/
 
+/**
+ * Result of `quiesce`/`resume_activity`.
+ */
+export type ActivityState = { 
+/**
+ * New network activity is refused until resumed
+ */
+quiesced: boolean; 
+/**
+ * An in-flight operation was asked to cancel
+ */
+cancelledOperation: boolean }
+/**
+ * Structure and safety findings for an arbitrary archive (see [`inspect_archive`]).
+ */
+export type ArchiveReport = { fileCount: number; 
+/**
+ * Sum of the uncompressed sizes declared in the central directory
+ */
+totalUncompressedBytes: number; 
+/**
+ * Folder wrapping the release folders, stripped on extraction
+ */
+nestedPrefix: string | null; 
+/**
+ * `POE1 POB/`, `POE2 POB/` or `Data/` was found; extraction refuses archives without
+ */
+hasRequiredFolders: boolean; 
+/**
+ * Entries extraction would refuse or skip for escaping the install folder
+ */
+dangerousPaths: string[]; 
+/**
+ * Entries marked as Unix symlinks, which extraction would write as plain files
+ */
+symlinks: string[]; 
+/**
+ * A list was cut off at [`MAX_DIFF_ENTRIES`]
+ */
+truncated: boolean }
+/**
+ * Result of reading back every file in the backup (see [`check_backup`]).
+ */
+export type BackupCheck = { 
+/**
+ * Files read back in full
+ */
+readable: number; unreadable: BackupIssue[]; 
+/**
+ * `unreadable` was cut off at [`MAX_DIFF_ENTRIES`]
+ */
+truncated: boolean }
+/**
+ * A backup taken automatically before an install replaced user data.
+ * Lives in `<install_root>/backup/<name>/`; the oldest are pruned past `max_backups`.
+ */
+export type BackupEntry = { 
+/**
+ * Directory name under `backup/`, `%Y%m%d-%H%M%S` with a suffix on collision
+ */
+name: string; createdAt: string; 
+/**
+ * Total size of the backed up files
+ */
+sizeBytes: number }
+/**
+ * Handling of a failed user-data backup, after the new version was already extracted.
+ */
+export type BackupFailurePolicy = 
+/**
+ * Fail the install with a `backup_failed` conflict the UI can prompt on
+ */
+"abort" | 
+/**
+ * Install without a backup and skip restoring; user data stays in the previous install
+ */
+"continue" | 
+/**
+ * Skip this update with an `update_skipped` conflict, leaving the existing install untouched
+ */
+"skipUpdate"
+/**
+ * A backup file that could not be read back.
+ */
+export type BackupIssue = { 
+/**
+ * Backup-relative path with `/` separators
+ */
+path: string; reason: string }
+/**
+ * A manual copy of the user data in an install, taken on request and never
+ * rotated by installs. Lives in `<data_dir>/snapshots/<id>/`.
+ */
+export type BackupSnapshot = { 
+/**
+ * Directory name under `snapshots/`, `%Y%m%d-%H%M%S` with a suffix on collision
+ */
+id: string; label: string | null; 
+/**
+ * PoB version installed when the snapshot was taken
+ */
+version: string | null; createdAt: string; 
+/**
+ * Backup targets that existed and were copied
+ */
+entries: number }
+/**
+ * Why an operation was refused as a conflict, so the UI can offer the right remedy.
+ */
+export type ConflictCode = 
+/**
+ * Another install/uninstall/check holds the operation lock (wait)
+ */
+"operation_in_progress" | 
+/**
+ * PoB is running (close PoB)
+ */
+"pob_running" | 
+/**
+ * Install folder is locked by another process (close PoB/Explorer)
+ */
+"file_in_use" | 
+/**
+ * Target folder can't be written (pick another folder)
+ */
+"dir_not_writable" | 
+/**
+ * Network activity is paused (resume)
+ */
+"quiesced" | 
+/**
+ * User data couldn't be backed up before an update (pick a backup failure policy)
+ */
+"backup_failed" | 
+/**
+ * Update skipped after a failed backup under the `SkipUpdate` policy (install unchanged)
+ */
+"update_skipped" | 
+/**
+ * Target release is older than the install (confirm, then retry with `allow_downgrade`)
+ */
+"downgrade"
+export type ConflictInfo = { code: ConflictCode; 
+/**
+ * Korean display string, for when the UI has no special handling for `code`
+ */
+detail: string }
+/**
+ * Handling of ZIP entries with absolute or `..`-escaping paths.
+ */
+export type DangerousPathPolicy = 
+/**
+ * Skip such entries and keep extracting; the total is logged
+ */
+"skip" | 
+/**
+ * Treat the archive as untrusted and fail the extraction
+ */
+"abort"
+export type DiffKind = 
+/**
+ * Size or CRC differs from the archive
+ */
+"modified" | 
+/**
+ * In the archive but not installed
+ */
+"missing" | 
+/**
+ * Installed but not in the archive
+ */
+"extra"
+/**
+ * Tunable parameters of the archive download.
+ */
+export type DownloadConfig = { 
+/**
+ * Write buffer size in bytes
+ */
+bufferSize: number; 
+/**
+ * Preallocate the target file to the reported content length
+ */
+preallocate: boolean; 
+/**
+ * Maximum downloads in flight at once across all operations
+ */
+maxConcurrentDownloads: number; 
+/**
+ * Updates at least this many MiB are flagged as large before downloading
+ */
+largeDownloadMb: number; 
+/**
+ * Size probes in flight at once when listing versions with metadata
+ */
+metadataPrefetchConcurrency: number }
+/**
+ * Result of a dry-run `install_pob`: what the archive would install.
+ */
+export type DryRunReport = { 
+/**
+ * Top-level folder stripped from every entry, for archives that wrap the install in one
+ */
+detectedPrefix: string | null; 
+/**
+ * Archive entries, folders included
+ */
+fileCount: number; 
+/**
+ * Uncompressed size of all entries
+ */
+totalBytes: number }
 /**
  * IPC error type for frontend consumption.
  * Designed for UI-actionable categories, not implementation details.
@@ -110,15 +638,301 @@ export type ErrorKind =
 /**
  * Conflict state (e.g., PoB is running)
  */
-{ kind: "conflict"; message: string } | 
+{ kind: "conflict"; message: ConflictInfo } | 
 /**
  * Other domain errors
  */
 { kind: "domain"; message: string }
-export type GoogleDriveFileInfo = { id: string; name: string; isFolder: boolean }
-export type InstallPhase = "downloading" | "extracting" | "backingUp" | "moving" | "restoring" | "finalizing" | "uninstalling" | "preparing"
-export type InstallProgress = ({ status: "started"; total_size?: number | null } | { status: "inProgress"; percent: number } | { status: "completed" } | { status: "failed"; reason: string } | { status: "cancelled" }) & { taskId: string; phase: InstallPhase }
+export type FileDiff = { 
+/**
+ * Install-relative path with `/` separators
+ */
+path: string; kind: DiffKind }
+/**
+ * Download details of a file, probed with a one-byte Range request.
+ */
+export type FileMetadata = { 
+/**
+ * Size in bytes, if the server reported one
+ */
+size: number | null; acceptsRanges: boolean }
+/**
+ * Which game's PoB tree a launch targets; the install holds one per game.
+ */
+export type GameProfile = "poe1" | "poe2"
+export type GoogleDriveFileInfo = { id: string; name: string; isFolder: boolean; 
+/**
+ * Size in bytes as listed in the folder, rounded to the displayed unit.
+ * `None` for folders or when the listing doesn't show one.
+ */
+size: number | null; 
+/**
+ * Last-modified date as displayed in the folder listing
+ */
+modified: string | null; 
+/**
+ * Probed download details, only when requested (see `PobManager::prefetch_metadata`)
+ */
+metadata?: FileMetadata | null }
+/**
+ * Result of comparing an install against a release archive.
+ */
+export type InstallDiff = { entries: FileDiff[]; modified: number; missing: number; extra: number; 
+/**
+ * Archive size of the modified and missing files, roughly what an update rewrites
+ */
+changedBytes: number; 
+/**
+ * `entries` was cut off at [`MAX_DIFF_ENTRIES`]
+ */
+truncated: boolean }
+/**
+ * Fire-once event carrying the final outcome of an install, uninstall or revert.
+ * Lets consumers that don't care about progress (tray, notifications) skip `InstallProgress`.
+ */
+export type InstallFinished = { taskId: string; result: InstallResult; 
+/**
+ * Wall-clock time from the start of the operation to termination
+ */
+elapsedMs: number }
+/**
+ * Result of `install_pob` and the repair commands.
+ */
+export type InstallOutcome = { 
+/**
+ * Id carried by the operation's progress events
+ */
+taskId: string; 
+/**
+ * What the archive would install, only for dry runs
+ */
+dryRun: DryRunReport | null }
+export type InstallPhase = "downloading" | "extracting" | "backingUp" | "moving" | "restoring" | "finalizing" | "uninstalling" | "preparing" | "rollingBack" | "relocating" | "verifying"
+export type InstallProgress = ({ status: "started"; totalSize?: number | null; 
+/**
+ * Expected duration of the phase, when it can be predicted
+ */
+estimatedSecs?: number | null } | { status: "inProgress"; percent: number; 
+/**
+ * Recent transfer rate, only for phases that move bytes over the network
+ */
+bytesPerSec?: number | null; 
+/**
+ * Seconds left at `bytes_per_sec`
+ */
+etaSecs?: number | null } | { status: "completed"; speed?: TransferSpeed | null } | { status: "failed"; reason: string } | { status: "cancelled" }) & { taskId: string; phase: InstallPhase }
+export type InstallResult = { status: "success"; 
+/**
+ * Version now installed, `None` after an uninstall or a dry run
+ */
+version: string | null } | { status: "cancelled" } | { status: "failed"; 
+/**
+ * `ErrorKind` tag of the failure (e.g. `network`, `conflict`)
+ */
+code: string; message: string; 
+/**
+ * Downloaded archive kept for inspection (`keepDownloadOnFailure`)
+ */
+keptDownload?: string | null }
+/**
+ * Structural check of an install, e.g. after a crash mid-install. Only
+ * looks at what exists; [`InstallDiff`] compares file contents.
+ */
+export type IntegrityReport = { exeExists: boolean; 
+/**
+ * `pob_version.json` exists and parses
+ */
+versionFileValid: boolean; 
+/**
+ * Entries of [`REQUIRED_DIRS`] that are missing
+ */
+missingDirs: string[]; 
+/**
+ * Release recorded in the version file, what a repair reinstalls
+ */
+fileId: string | null }
+/**
+ * Controls whether `set_modified` is applied to extracted files.
+ */
+export type MtimePolicy = 
+/**
+ * Preserve mtime for every file (original behavior)
+ */
+{ kind: "all" } | 
+/**
+ * Never touch mtime (fewest syscalls)
+ */
+{ kind: "none" } | 
+/**
+ * Preserve mtime only for the listed extensions (e.g. `["lua", "xml"]`)
+ */
+{ kind: "extensions"; extensions: string[] }
+/**
+ * Progress of a whole install across its weighted phases (see [`PhaseWeights`]).
+ * Only increases; the per-phase [`InstallProgress`] events still fire alongside.
+ */
+export type OverallProgress = { taskId: string; 
+/**
+ * Phase whose progress produced this value
+ */
+phase: InstallPhase; 
+/**
+ * 0–100 over the whole install
+ */
+percent: number }
+/**
+ * Outcome of running a candidate pattern against one sample filename.
+ */
+export type PatternSample = { name: string; 
+/**
+ * Version the pattern would extract
+ */
+parsed: string | null; 
+/**
+ * Why nothing was extracted
+ */
+error: string | null }
+/**
+ * User-tunable PoB manager settings, persisted as `<data_dir>/config.json`.
+ * Missing fields fall back to their defaults so older files keep loading.
+ */
+export type PobConfig = { 
+/**
+ * Which extracted files get their ZIP modification time restored
+ */
+mtimePolicy: MtimePolicy; 
+/**
+ * Download tuning, applied from the next install onward
+ */
+download: DownloadConfig; 
+/**
+ * Skip extracting archive entries under existing user-data paths,
+ * since restore would overwrite them anyway
+ */
+skipUserDataOnExtract: boolean; 
+/**
+ * What to do with archive entries whose path escapes the install folder
+ */
+onDangerousPath: DangerousPathPolicy; 
+/**
+ * Release filename regex with a named `version` group, for folders that
+ * don't follow the default Korean naming. `None` uses the default.
+ */
+archiveNamePattern: string | null; 
+/**
+ * Move the downloaded archive to `failed/` instead of deleting it when
+ * an install fails after the download, for manual inspection
+ */
+keepDownloadOnFailure: boolean; 
+/**
+ * What to do when user data can't be backed up during an install
+ */
+onBackupFailure: BackupFailurePolicy; 
+/**
+ * Cap on file handles and connections held at once across all
+ * concurrent operations (downloads, extraction, range fetches)
+ */
+maxOpenHandles: number; 
+/**
+ * Folder holding the install and its backup, e.g. on a roomier drive.
+ * `None` keeps them in the data dir. Only changed via `set_install_root`.
+ */
+installRoot: string | null; 
+/**
+ * How many timestamped backups installs keep; the oldest are pruned
+ */
+maxBackups: number; 
+/**
+ * Google Drive API key. When set, the release folder is listed through
+ * the Drive API instead of scraping its page
+ */
+driveApiKey: string | null; 
+/**
+ * How long the latest release found in a folder is reused before it's
+ * fetched again. `0` fetches every time
+ */
+latestCacheTtlSecs: number }
 export type PobVersion = { version: string; installedAt: string; fileId: string }
+/**
+ * Outcome of [`reclaim`].
+ */
+export type ReclaimReport = { freedBytes: number; 
+/**
+ * Removed paths, in removal order
+ */
+removed: string[]; 
+/**
+ * `freed_bytes` reached the requested target
+ */
+targetMet: boolean }
+/**
+ * What startup recovery did about an interrupted install, or recommends.
+ */
+export type RecoveryAction = 
+/**
+ * No interrupted install
+ */
+{ action: "none" } | 
+/**
+ * A half-extracted `.new` was removed; the install was never touched
+ */
+{ action: "cleanedUp" } | 
+/**
+ * The install was missing mid-swap, so `.old` was moved back in place
+ */
+{ action: "rolledBack" } | 
+/**
+ * The new version is in place but user data may not have been restored;
+ * restoring the newest backup is recommended
+ */
+{ action: "restoreUserData"; fileName: string } | 
+/**
+ * Interrupted before the swap; installing `file_name` again resumes the download
+ */
+{ action: "resumeInstall"; fileId: string; fileName: string }
+/**
+ * Min/avg/peak throughput of a finished transfer, in bytes per second.
+ */
+export type TransferSpeed = { minBytesPerSec: number; avgBytesPerSec: number; peakBytesPerSec: number }
+/**
+ * Result of `update_download_estimate`, for confirming large downloads up front.
+ */
+export type UpdateDownloadEstimate = { 
+/**
+ * Version the update would install
+ */
+version: string; 
+/**
+ * Archive size, `None` if Drive didn't report one
+ */
+bytes: number | null; 
+/**
+ * Size is known and at least the configured large-download threshold
+ */
+isLarge: boolean }
+/**
+ * How the installed version relates to the latest release.
+ */
+export type UpdateState = "notInstalled" | "upToDate" | "updateAvailable" | 
+/**
+ * The install is newer than the latest release (a manual install, or an old
+ * file re-sorted to latest on Drive); installing "latest" would downgrade
+ */
+"installedNewer"
+/**
+ * Result of `check_for_update`.
+ */
+export type UpdateStatus = { installed: string | null; latest: string; 
+/**
+ * Also `true` when nothing is installed, so the UI prompts a first install
+ */
+updateAvailable: boolean; 
+/**
+ * How many days older the installed release is than the latest, negative
+ * when it's newer. `None` when nothing is installed or either isn't a date.
+ */
+daysBehind: number | null }
+
 
 /** tauri-specta globals **/
 